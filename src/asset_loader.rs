@@ -1,17 +1,23 @@
+use anyhow::anyhow;
 use image::DynamicImage;
 use maplit::*;
 use std::collections::HashMap;
+use std::path::Path;
+use std::time::Instant;
 
 /// Stores the bytes of the given path into the binary at compile time.
 ///
-/// On run time, calls [`image::load_from_memory`](https://docs.rs/image/0.24.9/image/fn.load_from_memory.html) with the stored binary.
+/// The path is relative to the crate's root directory.
+///
+/// On run time, calls [`image::load_from_memory`](https://docs.rs/image/0.24.9/image/fn.load_from_memory.html) with the stored binary,
+/// converting the result to rgba8 with [`to_rgba8_image()`](to_rgba8_image).
 ///
 /// # Errors
 /// - When [`image::load_from_memory`](https://docs.rs/image/0.24.9/image/fn.load_from_memory.html) returns an error.
 macro_rules! image_from_path {
   ($path:literal) => {
-    match image::load_from_memory(include_bytes!(concat!(env!("PWD"), $path))) {
-      Ok(image) => image,
+    match image::load_from_memory(include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), $path))) {
+      Ok(image) => $crate::asset_loader::to_rgba8_image(image),
       Err(error) => {
         log::error!("Failed to load image at path {:?}", $path);
 
@@ -22,50 +28,334 @@ macro_rules! image_from_path {
 }
 
 pub struct Assets {
-  image_assets: HashMap<&'static str, DynamicImage>,
-  font_assets: HashMap<&'static str, &'static [u8]>,
+  image_assets: HashMap<String, DynamicImage>,
+  /// Every registered font's name and bytes, in the order they were registered.
+  ///
+  /// Loading them into the renderer in this order keeps each font's index the same as its position here.
+  font_assets: Vec<(&'static str, &'static [u8])>,
 }
 
 impl Assets {
   pub fn load_assets() -> Self {
     let image_assets = Self::load_image_assets();
-    let font_assets = Self::load_font_assets();
-
-    Self {
+    let mut assets = Self {
       image_assets,
-      font_assets,
+      font_assets: Vec::new(),
+    };
+
+    for (font_name, font_bytes) in Self::embedded_fonts() {
+      if let Err(error) = assets.register_font(font_name, font_bytes) {
+        log::error!("Failed to register an embedded font. `{:?}`", error);
+      }
     }
+
+    assets
   }
 
-  pub fn get_image(&self, image_name: &'static str) -> Option<&DynamicImage> {
+  /// Loads every embedded asset, logging the name of each one and how long loading took.
+  ///
+  /// Every image is decoded while loading, so nothing is left to stall the first frame that draws it.
+  pub fn preload_all() -> Self {
+    let start = Instant::now();
+    let assets = Self::load_assets();
+
+    log::info!(
+      "Loaded {} images and {} fonts in {:?}",
+      assets.image_assets.len(),
+      assets.font_assets.len(),
+      start.elapsed()
+    );
+    log::info!("images: {:?}", assets.get_image_list());
+    log::info!("fonts: {:?}", assets.get_font_list());
+
+    assets
+  }
+
+  pub fn get_image(&self, image_name: &str) -> Option<&DynamicImage> {
     self.image_assets.get(image_name)
   }
 
-  pub fn get_font(&self, font_name: &'static str) -> Option<&&'static [u8]> {
-    self.font_assets.get(font_name)
+  /// Loads every png and jpg image in the directory, keyed by their file name without the extension.
+  ///
+  /// Like the embedded images, each image is stored as rgba8.
+  /// Images with the same name as an embedded image replace it.
+  /// Files that can't be read or decoded are logged and skipped.
+  ///
+  /// # Errors
+  /// - When the directory can't be read.
+  pub fn load_image_dir(&mut self, path: &Path) -> anyhow::Result<()> {
+    for entry in std::fs::read_dir(path)? {
+      let image_path = match entry {
+        Ok(entry) => entry.path(),
+        Err(error) => {
+          log::warn!("Failed to read an entry in {:?}: `{:?}`", path, error);
+
+          continue;
+        }
+      };
+
+      let is_image = image_path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+          ["png", "jpg", "jpeg"].contains(&extension.to_ascii_lowercase().as_str())
+        });
+
+      if !is_image {
+        continue;
+      }
+
+      let Some(image_name) = image_path.file_stem().and_then(|stem| stem.to_str()) else {
+        continue;
+      };
+
+      match image::open(&image_path) {
+        Ok(image) => {
+          log::info!("Loaded the image {:?} from {:?}", image_name, image_path);
+
+          self
+            .image_assets
+            .insert(image_name.to_string(), to_rgba8_image(image));
+        }
+        Err(error) => log::warn!(
+          "Failed to load the image at {:?}, skipping it. `{:?}`",
+          image_path,
+          error
+        ),
+      }
+    }
+
+    Ok(())
   }
 
-  pub fn image_assets(&self) -> &HashMap<&'static str, DynamicImage> {
+  /// Adds the font under the given name, after every font registered before it.
+  ///
+  /// # Errors
+  /// - When a font has already been registered with the name.
+  pub fn register_font(
+    &mut self,
+    font_name: &'static str,
+    font_bytes: &'static [u8],
+  ) -> anyhow::Result<()> {
+    if self.get_font(font_name).is_some() {
+      return Err(anyhow!(
+        "Attempted to register a second font named `{}`.",
+        font_name
+      ));
+    }
+
+    self.font_assets.push((font_name, font_bytes));
+
+    Ok(())
+  }
+
+  pub fn get_font(&self, font_name: &str) -> Option<&&'static [u8]> {
+    self
+      .font_assets
+      .iter()
+      .find(|(name, _)| *name == font_name)
+      .map(|(_, font_bytes)| font_bytes)
+  }
+
+  /// Returns the name of every loaded font, sorted alphabetically.
+  pub fn get_font_list(&self) -> Vec<&'static str> {
+    let mut font_names: Vec<&'static str> = self
+      .font_assets
+      .iter()
+      .map(|(font_name, _)| *font_name)
+      .collect();
+    font_names.sort_unstable();
+
+    font_names
+  }
+
+  /// Returns the name of every loaded image, sorted alphabetically.
+  pub fn get_image_list(&self) -> Vec<&str> {
+    let mut image_names: Vec<&str> = self.image_assets.keys().map(String::as_str).collect();
+    image_names.sort_unstable();
+
+    image_names
+  }
+
+  pub fn image_assets(&self) -> &HashMap<String, DynamicImage> {
     &self.image_assets
   }
 
-  pub fn font_assets(&self) -> &HashMap<&'static str, &'static [u8]> {
+  /// Returns every font's name and bytes, in the order they were registered.
+  pub fn font_assets(&self) -> &[(&'static str, &'static [u8])] {
     &self.font_assets
   }
 
-  fn load_image_assets() -> HashMap<&'static str, DynamicImage> {
+  fn load_image_assets() -> HashMap<String, DynamicImage> {
     hashmap! {
-      "menu_start_v1" => image_from_path!("/assets/start_v1.png"),
-      "menu_start_v2" => image_from_path!("/assets/start_v2.png"),
-      "menu_options" => image_from_path!("/assets/options.png"),
-      "menu_exit" => image_from_path!("/assets/exit.png"),
-      "menu_background" => image_from_path!("/assets/background.png"),
+      "menu_start_v1".to_string() => image_from_path!("/assets/start_v1.png"),
+      "menu_start_v2".to_string() => image_from_path!("/assets/start_v2.png"),
+      "menu_options".to_string() => image_from_path!("/assets/options.png"),
+      "menu_exit".to_string() => image_from_path!("/assets/exit.png"),
+      "menu_background".to_string() => image_from_path!("/assets/background.png"),
     }
   }
 
-  fn load_font_assets() -> HashMap<&'static str, &'static [u8]> {
-    hashmap! {
-      "gadugi" => include_bytes!(concat!(env!("PWD"), "/assets/gadugi-normal.ttf")) as &[u8],
+  /// The fonts built into the binary, in the order they're registered.
+  ///
+  /// The first font is the one drawn with by default.
+  fn embedded_fonts() -> [(&'static str, &'static [u8]); 1] {
+    [(
+      "gadugi",
+      include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/assets/gadugi-normal.ttf"
+      )),
+    )]
+  }
+}
+
+/// Converts the image to rgba8 if it's stored in any other format.
+///
+/// Images are drawn from their rgba8 bytes, so converting them once when they're loaded
+/// saves drawing from converting them again every frame.
+pub fn to_rgba8_image(image: DynamicImage) -> DynamicImage {
+  match image {
+    DynamicImage::ImageRgba8(_) => image,
+    image => DynamicImage::ImageRgba8(image.to_rgba8()),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use image::GenericImageView;
+  use winit::dpi::LogicalPosition;
+
+  #[test]
+  fn embedded_assets_are_loaded() {
+    let assets = Assets::load_assets();
+
+    for image_name in [
+      "menu_start_v1",
+      "menu_start_v2",
+      "menu_options",
+      "menu_exit",
+      "menu_background",
+    ] {
+      let image = assets.get_image(image_name).unwrap();
+      let (width, height) = image.dimensions();
+
+      assert!(width > 0 && height > 0);
     }
+
+    assert!(assets.get_image("unknown").is_none());
+  }
+
+  #[test]
+  fn images_are_loaded_from_a_directory() {
+    let image_directory =
+      std::env::temp_dir().join(format!("rustris_image_dir_test_{}", std::process::id()));
+    std::fs::create_dir_all(&image_directory).unwrap();
+
+    image::RgbaImage::from_pixel(3, 2, image::Rgba([255, 0, 0, 255]))
+      .save(image_directory.join("custom_image.png"))
+      .unwrap();
+    std::fs::write(image_directory.join("broken_image.png"), "not an image").unwrap();
+    std::fs::write(image_directory.join("notes.txt"), "not an image either").unwrap();
+
+    let mut assets = Assets::load_assets();
+    let result = assets.load_image_dir(&image_directory);
+    std::fs::remove_dir_all(&image_directory).unwrap();
+
+    assert!(result.is_ok());
+    assert_eq!(
+      assets.get_image("custom_image").unwrap().dimensions(),
+      (3, 2)
+    );
+    assert!(assets.get_image("broken_image").is_none());
+    assert!(assets.get_image("notes").is_none());
+    // The embedded images are still available.
+    assert!(assets.get_image("menu_start_v2").is_some());
+  }
+
+  #[test]
+  fn converted_images_match_a_fresh_conversion() {
+    let image = DynamicImage::ImageLuma8(image::GrayImage::from_fn(3, 2, |x, y| {
+      image::Luma([(x * 40 + y * 100) as u8])
+    }));
+
+    let converted_image = to_rgba8_image(image.clone());
+
+    assert_eq!(
+      converted_image.as_rgba8().unwrap().as_raw(),
+      image.to_rgba8().as_raw()
+    );
+  }
+
+  #[test]
+  fn every_loaded_image_is_cached_as_rgba8() {
+    let assets = Assets::load_assets();
+    let mut renderer =
+      crate::renderer::Renderer::new_in_memory(crate::rustris_config::RENDERED_WINDOW_DIMENSIONS);
+    let buffer_dimensions = renderer.buffer_dimensions();
+
+    for (image_name, image) in assets.image_assets() {
+      assert!(image.as_rgba8().is_some(), "{} isn't rgba8", image_name);
+
+      renderer
+        .render_image(&LogicalPosition::new(0, 0), image, &buffer_dimensions)
+        .unwrap();
+    }
+  }
+
+  #[test]
+  fn missing_image_directory_errors() {
+    let mut assets = Assets::load_assets();
+
+    assert!(assets
+      .load_image_dir(Path::new("/this/directory/does/not/exist"))
+      .is_err());
+  }
+
+  #[test]
+  fn embedded_fonts_are_listed() {
+    let assets = Assets::load_assets();
+
+    assert_eq!(assets.get_font_list(), vec!["gadugi"]);
+    assert!(!assets.get_font("gadugi").unwrap().is_empty());
+  }
+
+  #[test]
+  fn preloading_loads_every_asset() {
+    let assets = Assets::preload_all();
+
+    assert_eq!(
+      assets.get_image_list(),
+      vec![
+        "menu_background",
+        "menu_exit",
+        "menu_options",
+        "menu_start_v1",
+        "menu_start_v2",
+      ]
+    );
+    assert_eq!(assets.get_font_list(), vec!["gadugi"]);
+  }
+
+  #[test]
+  fn fonts_are_kept_in_registration_order() {
+    let mut assets = Assets::load_assets();
+    let gadugi = *assets.get_font("gadugi").unwrap();
+
+    assets.register_font("title", &gadugi[..16]).unwrap();
+
+    let font_names: Vec<&str> = assets.font_assets().iter().map(|(name, _)| *name).collect();
+    assert_eq!(font_names, vec!["gadugi", "title"]);
+    assert_eq!(assets.get_font("title").unwrap().len(), 16);
+    assert_eq!(assets.get_font("gadugi").unwrap().len(), gadugi.len());
+  }
+
+  #[test]
+  fn duplicate_font_names_are_rejected() {
+    let mut assets = Assets::load_assets();
+
+    assert!(assets.register_font("gadugi", &[]).is_err());
+    assert_eq!(assets.font_assets().len(), 1);
   }
 }
@@ -1,4 +1,6 @@
+use super::game_settings::Controls;
 use super::world_state::WorldState;
+use serde::{Deserialize, Serialize};
 use winit::keyboard::KeyCode;
 
 /// The variations of player actions depending on the environment.
@@ -17,14 +19,19 @@ pub enum PlayerAction {
 /// The list of actions that can be taken while playing the game.
 ///
 /// These actions consist of piece movement, dropping style, pausing, etc.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GameAction {
   MoveLeft,
   MoveRight,
+  RotateCw,
+  RotateCcw,
+  Rotate180,
   HardDrop,
   SoftDrop,
   Hold,
   Pause,
+  /// Abandons the current game, returning to the main menu.
+  QuitToMenu,
 
   Unknown,
 }
@@ -32,7 +39,7 @@ pub enum GameAction {
 /// The list of actions that can be taken within a menu.
 ///
 /// Menus consist of the main menu, settings menu, pause menu, etc.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MenuAction {
   Up,
   Down,
@@ -65,6 +72,21 @@ impl GameAction {
   pub fn is_empty(&self) -> bool {
     self == &GameAction::Unknown
   }
+
+  /// The order actions pressed on the same frame are applied in, lowest first.
+  ///
+  /// Rotations are applied before movement so a rotation kicked off of a wall is then moved,
+  /// instead of the outcome depending on the order the keys were read in.
+  /// Drops come last so they act on the piece's final position.
+  pub fn resolution_order(&self) -> u8 {
+    match self {
+      GameAction::RotateCw | GameAction::RotateCcw | GameAction::Rotate180 => 0,
+      GameAction::MoveLeft | GameAction::MoveRight => 1,
+      GameAction::SoftDrop => 2,
+      GameAction::HardDrop => 3,
+      GameAction::Hold | GameAction::Pause | GameAction::QuitToMenu | GameAction::Unknown => 4,
+    }
+  }
 }
 
 impl MenuAction {
@@ -74,76 +96,76 @@ impl MenuAction {
   }
 }
 
-// TODO: Make these compatible with changing keybindings in the options.
-
-impl From<KeyCode> for GameAction {
-  fn from(key: KeyCode) -> Self {
-    match key {
-      KeyCode::ArrowLeft | KeyCode::KeyA => GameAction::MoveLeft,
-      KeyCode::ArrowRight | KeyCode::KeyD => GameAction::MoveRight,
-      KeyCode::ArrowDown | KeyCode::KeyS => GameAction::SoftDrop,
-
-      KeyCode::Space => GameAction::HardDrop,
-      KeyCode::ArrowUp => GameAction::Hold,
-      KeyCode::Escape => GameAction::Pause,
-
-      _ => GameAction::Unknown,
+impl From<(WorldState, Vec<KeyCode>, &Controls)> for PlayerAction {
+  /// Resolves the pressed keys into actions through the key bindings of the current context.
+  ///
+  /// In menus, including the game over screen, only the first key bound to an action is used.
+  fn from((world_state, keys, controls): (WorldState, Vec<KeyCode>, &Controls)) -> Self {
+    match world_state {
+      WorldState::Menu | WorldState::GameOver => {
+        let menu_action = keys
+          .iter()
+          .map(|key| controls.menu_action(key))
+          .find(|action| !action.is_empty())
+          .unwrap_or(MenuAction::Unknown);
+
+        PlayerAction::MenuAction(menu_action)
+      }
+      WorldState::Game | WorldState::Countdown => keys
+        .iter()
+        .map(|key| controls.game_action(key))
+        .filter(|action| !action.is_empty())
+        .collect::<Vec<GameAction>>()
+        .into(),
     }
   }
 }
 
-impl From<KeyCode> for MenuAction {
-  fn from(key: KeyCode) -> Self {
-    match key {
-      KeyCode::ArrowUp | KeyCode::KeyW => MenuAction::Up,
-      KeyCode::ArrowDown | KeyCode::KeyS => MenuAction::Down,
-      KeyCode::ArrowLeft | KeyCode::KeyA => MenuAction::Left,
-      KeyCode::ArrowRight | KeyCode::KeyD => MenuAction::Right,
-
-      KeyCode::Enter | KeyCode::KeyZ => MenuAction::Select,
-      KeyCode::Backspace | KeyCode::KeyX | KeyCode::Escape => MenuAction::Back,
-
-      _ => MenuAction::Unknown,
-    }
+impl From<Vec<GameAction>> for PlayerAction {
+  fn from(actions: Vec<GameAction>) -> Self {
+    PlayerAction::GameAction(actions)
   }
 }
 
-impl From<(WorldState, KeyCode)> for PlayerAction {
-  fn from((world_state, key): (WorldState, KeyCode)) -> Self {
-    match world_state {
-      WorldState::Menu => PlayerAction::MenuAction(MenuAction::from(key)),
-      WorldState::Game => PlayerAction::GameAction(vec![GameAction::from(key)]),
-    }
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn keys_resolve_through_the_controls() {
+    let mut controls = Controls::default();
+    controls
+      .rebind_game(GameAction::MoveLeft, KeyCode::KeyJ)
+      .unwrap();
+
+    let player_action = PlayerAction::from((
+      WorldState::Game,
+      vec![KeyCode::KeyJ, KeyCode::KeyA, KeyCode::Space],
+      &controls,
+    ));
+
+    let PlayerAction::GameAction(game_actions) = player_action else {
+      panic!("Expected game actions, got {:?}", player_action);
+    };
+    assert_eq!(
+      game_actions,
+      vec![GameAction::MoveLeft, GameAction::HardDrop]
+    );
   }
-}
 
-impl From<(WorldState, Vec<KeyCode>)> for PlayerAction {
-  fn from((world_state, keys): (WorldState, Vec<KeyCode>)) -> Self {
-    if keys.is_empty() {
-      return PlayerAction::MenuAction(MenuAction::Unknown);
-    }
+  #[test]
+  fn menus_use_the_first_bound_key() {
+    let controls = Controls::default();
 
-    match world_state {
-      WorldState::Menu => PlayerAction::MenuAction(MenuAction::from(keys[0])),
-      WorldState::Game => keys
-        .into_iter()
-        .filter_map(|key| {
-          let action = GameAction::from(key);
-
-          if action != GameAction::Unknown {
-            Some(action)
-          } else {
-            None
-          }
-        })
-        .collect::<Vec<GameAction>>()
-        .into(),
-    }
-  }
-}
+    let player_action = PlayerAction::from((
+      WorldState::Menu,
+      vec![KeyCode::KeyJ, KeyCode::Enter, KeyCode::ArrowUp],
+      &controls,
+    ));
 
-impl From<Vec<GameAction>> for PlayerAction {
-  fn from(actions: Vec<GameAction>) -> Self {
-    PlayerAction::GameAction(actions)
+    assert!(matches!(
+      player_action,
+      PlayerAction::MenuAction(MenuAction::Select)
+    ));
   }
 }
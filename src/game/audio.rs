@@ -0,0 +1,219 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+
+/// The sound effects played during a game.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Sound {
+  Lock,
+  LineClear,
+  Rotate,
+  Hold,
+  GameOver,
+}
+
+impl Sound {
+  pub const ALL: [Sound; 5] = [
+    Sound::Lock,
+    Sound::LineClear,
+    Sound::Rotate,
+    Sound::Hold,
+    Sound::GameOver,
+  ];
+
+  /// The frequency in hertz and length in milliseconds of the tone each sound is made of.
+  fn tone(&self) -> (f32, u32) {
+    match self {
+      Sound::Lock => (220.0, 60),
+      Sound::LineClear => (660.0, 180),
+      Sound::Rotate => (880.0, 30),
+      Sound::Hold => (440.0, 50),
+      Sound::GameOver => (110.0, 600),
+    }
+  }
+}
+
+/// Plays sound effects on the default audio device.
+///
+/// Every clip is created when the audio is loaded, so playing a sound never has to wait on anything.
+/// If there's no audio device, or the game was built without the `audio` feature,
+/// the audio is disabled and playing a sound does nothing.
+pub struct Audio {
+  muted: bool,
+  /// How many sounds have been played while unmuted.
+  play_count: Cell<u32>,
+  clips: HashMap<Sound, Vec<f32>>,
+  #[cfg(feature = "audio")]
+  output: Option<output::AudioOutput>,
+}
+
+impl Audio {
+  /// The amount of samples played per second for every clip.
+  pub const SAMPLE_RATE: u32 = 44100;
+  /// How loud the clips are, from 0.0 to 1.0.
+  const VOLUME: f32 = 0.2;
+
+  /// Loads every clip and opens the default audio device.
+  ///
+  /// Failing to open the device is logged, and leaves the audio disabled.
+  pub fn new(muted: bool) -> Self {
+    let clips = Sound::ALL
+      .into_iter()
+      .map(|sound| (sound, Self::create_clip(sound)))
+      .collect();
+
+    Self {
+      muted,
+      play_count: Cell::new(0),
+      clips,
+      #[cfg(feature = "audio")]
+      output: output::AudioOutput::open()
+        .map_err(|error| {
+          log::warn!("Failed to open the audio device, sound is disabled. `{error}`")
+        })
+        .ok(),
+    }
+  }
+
+  /// Plays the sound, unless the audio is muted.
+  #[cfg_attr(not(feature = "audio"), allow(unused_variables))]
+  pub fn play(&self, sound: Sound) {
+    if self.muted {
+      return;
+    }
+
+    self.play_count.set(self.play_count.get() + 1);
+
+    #[cfg(feature = "audio")]
+    if let (Some(output), Some(clip)) = (&self.output, self.clips.get(&sound)) {
+      if let Err(error) = output.play(clip.clone()) {
+        log::warn!("Failed to play the sound {:?}. `{}`", sound, error);
+      }
+    }
+  }
+
+  pub fn is_muted(&self) -> bool {
+    self.muted
+  }
+
+  pub fn set_muted(&mut self, muted: bool) {
+    self.muted = muted;
+  }
+
+  /// Returns true if sounds are sent to an audio device when played.
+  pub fn is_enabled(&self) -> bool {
+    #[cfg(feature = "audio")]
+    {
+      self.output.is_some()
+    }
+
+    #[cfg(not(feature = "audio"))]
+    {
+      false
+    }
+  }
+
+  /// Returns how many sounds have been played while the audio wasn't muted.
+  ///
+  /// Sounds are counted even when the audio is disabled.
+  pub fn play_count(&self) -> u32 {
+    self.play_count.get()
+  }
+
+  /// Returns the samples of the clip played for the sound.
+  pub fn clip(&self, sound: Sound) -> Option<&[f32]> {
+    self.clips.get(&sound).map(Vec::as_slice)
+  }
+
+  /// Creates a mono clip of a sine wave at the sound's tone, fading out towards the end to avoid a pop.
+  fn create_clip(sound: Sound) -> Vec<f32> {
+    let (frequency, length_ms) = sound.tone();
+    let sample_count = (Self::SAMPLE_RATE * length_ms / 1000) as usize;
+
+    (0..sample_count)
+      .map(|index| {
+        let time = index as f32 / Self::SAMPLE_RATE as f32;
+        let fade = 1.0 - (index as f32 / sample_count as f32);
+
+        (time * frequency * std::f32::consts::TAU).sin() * fade * Self::VOLUME
+      })
+      .collect()
+  }
+}
+
+impl std::fmt::Debug for Audio {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Audio")
+      .field("muted", &self.muted)
+      .field("enabled", &self.is_enabled())
+      .field("play_count", &self.play_count)
+      .finish()
+  }
+}
+
+#[cfg(feature = "audio")]
+mod output {
+  use super::Audio;
+  use rodio::buffer::SamplesBuffer;
+  use rodio::{OutputStream, OutputStreamHandle};
+
+  /// The open audio device. Sound stops once this is dropped.
+  pub struct AudioOutput {
+    _stream: OutputStream,
+    handle: OutputStreamHandle,
+  }
+
+  impl AudioOutput {
+    pub fn open() -> anyhow::Result<Self> {
+      let (stream, handle) = OutputStream::try_default()?;
+
+      Ok(Self {
+        _stream: stream,
+        handle,
+      })
+    }
+
+    pub fn play(&self, clip: Vec<f32>) -> anyhow::Result<()> {
+      self
+        .handle
+        .play_raw(SamplesBuffer::new(1, Audio::SAMPLE_RATE, clip))?;
+
+      Ok(())
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn muted_audio_doesnt_play_sounds() {
+    let mut audio = Audio::new(false);
+
+    audio.play(Sound::Lock);
+
+    assert_eq!(audio.play_count(), 1);
+
+    audio.set_muted(true);
+    audio.play(Sound::LineClear);
+
+    assert_eq!(audio.play_count(), 1);
+
+    audio.set_muted(false);
+    audio.play(Sound::Hold);
+
+    assert_eq!(audio.play_count(), 2);
+  }
+
+  #[test]
+  fn every_sound_has_a_clip() {
+    let audio = Audio::new(true);
+
+    for sound in Sound::ALL {
+      let clip = audio.clip(sound).unwrap();
+
+      assert!(!clip.is_empty());
+      assert!(clip.iter().all(|sample| sample.abs() <= 1.0));
+    }
+  }
+}
@@ -0,0 +1,146 @@
+use crate::general_data::timer::Timer;
+use crate::renderer::color::Color;
+use crate::renderer::Renderer;
+use std::time::Duration;
+
+/// Visual effects drawn over the rendered frame, which never affect gameplay.
+///
+/// Each effect runs off its own [`Timer`](crate::general_data::timer::Timer), so starting one again restarts it.
+#[derive(Debug)]
+pub struct Effects {
+  shake: Timer,
+  fade: Timer,
+}
+
+impl Effects {
+  /// How long a screen shake lasts.
+  pub const SHAKE_DURATION: Duration = Duration::from_millis(300);
+  /// How far in pixels the screen is moved when a shake starts.
+  pub const SHAKE_MAGNITUDE: u32 = 4;
+  /// How long it takes to fade in from black.
+  pub const FADE_DURATION: Duration = Duration::from_millis(250);
+  /// The directions the screen is moved in while shaking, cycling every [`SHAKE_STEP`](Effects::SHAKE_STEP).
+  const SHAKE_PATTERN: [(i32, i32); 6] = [(1, 0), (-1, 1), (0, -1), (-1, 0), (1, -1), (0, 1)];
+  /// How long the screen stays in each direction of the [`SHAKE_PATTERN`](Effects::SHAKE_PATTERN).
+  const SHAKE_STEP: Duration = Duration::from_millis(20);
+
+  pub fn new() -> Self {
+    Self {
+      shake: Timer::new(Self::SHAKE_DURATION),
+      fade: Timer::new(Self::FADE_DURATION),
+    }
+  }
+
+  /// Starts shaking the screen, with the shake weakening until it stops after [`SHAKE_DURATION`](Effects::SHAKE_DURATION).
+  pub fn shake(&self) {
+    self.shake.start();
+  }
+
+  /// Starts fading the screen in from black over [`FADE_DURATION`](Effects::FADE_DURATION).
+  pub fn fade_in(&self) {
+    self.fade.start();
+  }
+
+  /// Returns how far in pixels the screen is currently moved by the shake.
+  ///
+  /// The distance shrinks as the shake goes on, reaching zero once it's over.
+  pub fn shake_offset(&self) -> (i32, i32) {
+    let (Some(elapsed), Some(remaining)) = (self.shake.elapsed(), self.shake.remaining()) else {
+      return (0, 0);
+    };
+
+    let strength =
+      (Self::SHAKE_MAGNITUDE as f32 * (1.0 - self.shake.elapsed_fraction())).ceil() as i32;
+
+    if remaining.is_zero() || strength == 0 {
+      return (0, 0);
+    }
+
+    let step = (elapsed.as_millis() / Self::SHAKE_STEP.as_millis()) as usize;
+    let (direction_x, direction_y) = Self::SHAKE_PATTERN[step % Self::SHAKE_PATTERN.len()];
+
+    (direction_x * strength, direction_y * strength)
+  }
+
+  /// Returns the alpha of the black drawn over the screen by the fade, with 0 once the fade is over.
+  pub fn fade_alpha(&self) -> u8 {
+    if !self.fade.running() {
+      return 0;
+    }
+
+    ((1.0 - self.fade.elapsed_fraction()) * 255.0).round() as u8
+  }
+
+  /// Applies the current shake and fade to everything drawn in the frame so far.
+  pub fn apply(&self, renderer: &mut Renderer) -> anyhow::Result<()> {
+    let (offset_x, offset_y) = self.shake_offset();
+
+    if (offset_x, offset_y) != (0, 0) {
+      renderer.offset_frame(offset_x, offset_y);
+    }
+
+    let fade_alpha = self.fade_alpha();
+
+    if fade_alpha > 0 {
+      renderer.apply_color(Color::BLACK.with_alpha(fade_alpha))?;
+    }
+
+    Ok(())
+  }
+}
+
+impl Default for Effects {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::general_data::timer::clock;
+  use winit::dpi::LogicalSize;
+
+  #[test]
+  fn shake_offset_decays_to_zero() {
+    let effects = Effects::new();
+
+    assert_eq!(effects.shake_offset(), (0, 0));
+
+    effects.shake();
+    let (start_x, start_y) = effects.shake_offset();
+
+    assert_eq!(
+      start_x.abs().max(start_y.abs()),
+      Effects::SHAKE_MAGNITUDE as i32
+    );
+
+    clock::advance(Effects::SHAKE_DURATION * 3 / 4);
+    let (late_x, late_y) = effects.shake_offset();
+
+    assert!(late_x.abs().max(late_y.abs()) < Effects::SHAKE_MAGNITUDE as i32);
+
+    clock::advance(Effects::SHAKE_DURATION);
+
+    assert_eq!(effects.shake_offset(), (0, 0));
+  }
+
+  #[test]
+  fn mid_fade_frame_is_partially_black() {
+    let mut renderer = Renderer::new_in_memory(LogicalSize::new(2, 2));
+    renderer.set_color([255, 255, 255]).unwrap();
+    let effects = Effects::new();
+
+    effects.fade_in();
+    clock::advance(Effects::FADE_DURATION / 2);
+    effects.apply(&mut renderer).unwrap();
+
+    let red = renderer.frame()[0];
+
+    assert!(red > 0 && red < 255, "Red channel was {}", red);
+
+    clock::advance(Effects::FADE_DURATION);
+
+    assert_eq!(effects.fade_alpha(), 0);
+  }
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// The rules deciding when a game ends, other than topping out.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameMode {
+  /// Endless play, only ending once the board tops out.
+  #[default]
+  Marathon,
+  /// Ends once [`SPRINT_LINES`](GameMode::SPRINT_LINES) lines have been cleared, showing how long it took.
+  Sprint,
+  /// Ends once [`ULTRA_DURATION`](GameMode::ULTRA_DURATION) has passed, showing the score.
+  Ultra,
+}
+
+impl GameMode {
+  /// How many lines have to be cleared to finish a sprint.
+  pub const SPRINT_LINES: u32 = 40;
+  /// How long an ultra game lasts.
+  pub const ULTRA_DURATION: Duration = Duration::from_secs(120);
+
+  /// Returns how many lines have to be cleared to finish the game, if there's a goal.
+  pub fn line_goal(&self) -> Option<u32> {
+    match self {
+      GameMode::Sprint => Some(Self::SPRINT_LINES),
+      _ => None,
+    }
+  }
+
+  /// Returns how long the game lasts, if it has a time limit.
+  pub fn time_limit(&self) -> Option<Duration> {
+    match self {
+      GameMode::Ultra => Some(Self::ULTRA_DURATION),
+      _ => None,
+    }
+  }
+}
@@ -1,21 +1,177 @@
-// use std::collections::HashMap;
+use super::actions::{GameAction, MenuAction};
+use super::palette::Palette;
+use super::resolution::RenderResolution;
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use winit::keyboard::KeyCode;
+
+/// How finished frames are shown on the window.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PresentMode {
+  /// Frames wait for the display to refresh, pacing drawing to the monitor's refresh rate.
+  #[default]
+  Vsync,
+  /// Frames are shown as soon as they're drawn, with drawing limited to the set fps by sleeping.
+  Immediate,
+}
 
 // This will contain things like controls, ui scaling, textures, and more.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct GameSettings {
   /// The current set fps.
   fps: u32,
+  /// Whether the window covers the entire monitor.
+  fullscreen: bool,
+  /// How finished frames are shown on the window.
+  present_mode: PresentMode,
   controls: Controls,
+  /// How many pieces the player gets to place after topping out to clear back under the top of the board.
+  top_out_grace_placements: u32,
+  /// Whether a line is drawn along the top of the visible part of the board.
+  show_visible_boundary: bool,
+  /// Whether lines are drawn between the cells of the board, with a border around it.
+  show_grid: bool,
+  /// Whether the active piece's ghost is drawn where it would land.
+  ghost_enabled: bool,
+  /// The alpha the ghost piece is drawn with.
+  ghost_alpha: u8,
+  /// The color of the lines drawn between the cells of the board.
+  grid_color: [u8; 4],
+  /// The color of the border drawn around the board.
+  border_color: [u8; 4],
+  /// The colors pieces are drawn with.
+  palette: Palette,
+  /// The internal resolution the game is drawn at, which is applied the next time the game starts.
+  render_resolution: RenderResolution,
+  /// How long in milliseconds a piece can rest on the stack before it locks in place.
+  lock_delay_ms: u64,
+  /// How many times moving or rotating a resting piece can restart its lock delay.
+  lock_reset_cap: u32,
+  /// How long in milliseconds left or right has to be held before the piece starts moving on its own.
+  das_ms: u64,
+  /// How long in milliseconds between each repeated move once left or right has been held past the DAS delay.
+  arr_ms: u64,
+  /// How long in milliseconds up or down has to be held in a menu before the cursor starts moving on its own.
+  menu_das_ms: u64,
+  /// How long in milliseconds between each repeated cursor move once up or down has been held past the menu DAS delay.
+  menu_arr_ms: u64,
+  /// Whether going back from the main menu closes the game.
+  back_exits_main_menu: bool,
+  /// How many of the upcoming pieces are shown next to the board.
+  preview_count: usize,
+  /// Whether sound effects are silenced.
+  muted: bool,
+  /// How long in milliseconds the countdown before each game lasts.
+  countdown_ms: u64,
+  /// The most line clear particles that can be on screen at once. 0 disables them.
+  max_particles: usize,
 }
 
-struct Controls {
-  // inner: HashMap<String,
+/// The keys bound to each action, both in game and in menus.
+///
+/// A key can only ever be bound to a single action in each context.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Controls {
+  game: HashMap<KeyCode, GameAction>,
+  menu: HashMap<KeyCode, MenuAction>,
+  /// Switches the window between fullscreen and windowed in every context.
+  fullscreen_key: KeyCode,
+  /// Shows or hides the debug overlay in every context.
+  debug_overlay_key: KeyCode,
 }
 
 impl GameSettings {
+  /// The most upcoming pieces that can be shown next to the board.
+  pub const MAX_PREVIEW_COUNT: usize = 7;
+
+  /// The name of the file settings are saved to, within the [`settings directory`](GameSettings::settings_path).
+  pub const SETTINGS_FILE_NAME: &'static str = "rustris_settings.toml";
+
   pub fn initialize() -> anyhow::Result<Self> {
-    let controls = Controls::initialize()?;
+    Ok(Self::load_from_disk())
+  }
+
+  /// Returns the path settings are saved to, inside of the platform's config directory.
+  ///
+  /// None is returned if the platform has no config directory.
+  pub fn settings_path() -> Option<PathBuf> {
+    Some(
+      dirs::config_dir()?
+        .join("rustris")
+        .join(Self::SETTINGS_FILE_NAME),
+    )
+  }
+
+  /// Loads the settings saved to the [`settings path`](GameSettings::settings_path).
+  ///
+  /// The default settings are used if the file is missing or malformed.
+  pub fn load_from_disk() -> Self {
+    let Some(settings_path) = Self::settings_path() else {
+      log::warn!("There is no config directory, using the default settings.");
+
+      return Self::default();
+    };
+
+    Self::load_from_path(&settings_path)
+  }
+
+  /// Loads the settings from the toml file at the path.
+  ///
+  /// The default settings are used if the file is missing or malformed.
+  /// Any setting missing from the file uses its default value.
+  pub fn load_from_path(path: &Path) -> Self {
+    let settings_file = match std::fs::read_to_string(path) {
+      Ok(settings_file) => settings_file,
+      Err(error) => {
+        log::info!(
+          "Failed to read the settings at {:?}, using the default settings. `{:?}`",
+          path,
+          error
+        );
 
-    Ok(Self { fps: 144, controls })
+        return Self::default();
+      }
+    };
+
+    match toml::from_str(&settings_file) {
+      Ok(settings) => settings,
+      Err(error) => {
+        log::warn!(
+          "The settings at {:?} are malformed, using the default settings. `{}`",
+          path,
+          error
+        );
+
+        Self::default()
+      }
+    }
+  }
+
+  /// Saves the settings to the [`settings path`](GameSettings::settings_path).
+  pub fn save_to_disk(&self) -> anyhow::Result<()> {
+    let Some(settings_path) = Self::settings_path() else {
+      return Err(anyhow!(
+        "Failed to save the settings, there is no config directory."
+      ));
+    };
+
+    self.save_to_path(&settings_path)
+  }
+
+  /// Saves the settings as toml to the path, creating any missing directories.
+  pub fn save_to_path(&self, path: &Path) -> anyhow::Result<()> {
+    if let Some(parent_directory) = path.parent() {
+      std::fs::create_dir_all(parent_directory)?;
+    }
+
+    std::fs::write(path, toml::to_string(self)?)?;
+
+    Ok(())
   }
 
   /// The current set fps.
@@ -24,10 +180,649 @@ impl GameSettings {
   pub fn fps(&self) -> u32 {
     self.fps.clamp(20, 144)
   }
+
+  pub fn set_fps(&mut self, fps: u32) {
+    self.fps = fps.clamp(20, 144);
+  }
+
+  /// Whether the window covers the entire monitor.
+  pub fn fullscreen(&self) -> bool {
+    self.fullscreen
+  }
+
+  pub fn set_fullscreen(&mut self, fullscreen: bool) {
+    self.fullscreen = fullscreen;
+  }
+
+  /// How finished frames are shown on the window.
+  pub fn present_mode(&self) -> PresentMode {
+    self.present_mode
+  }
+
+  pub fn set_present_mode(&mut self, present_mode: PresentMode) {
+    self.present_mode = present_mode;
+  }
+
+  /// Switches between fullscreen and windowed, returning true if the window is now fullscreen.
+  pub fn toggle_fullscreen(&mut self) -> bool {
+    self.fullscreen = !self.fullscreen;
+
+    self.fullscreen
+  }
+
+  pub fn controls(&self) -> &Controls {
+    &self.controls
+  }
+
+  pub fn controls_mut(&mut self) -> &mut Controls {
+    &mut self.controls
+  }
+
+  /// How many pieces the player gets to place after topping out to clear back under the top of the board.
+  ///
+  /// 0 means topping out immediately ends the game.
+  pub fn top_out_grace_placements(&self) -> u32 {
+    self.top_out_grace_placements
+  }
+
+  pub fn set_top_out_grace_placements(&mut self, placements: u32) {
+    self.top_out_grace_placements = placements;
+  }
+
+  /// Whether a line is drawn along the top of the visible part of the board.
+  ///
+  /// Mostly useful for seeing where pieces cross into the hidden rows.
+  pub fn show_visible_boundary(&self) -> bool {
+    self.show_visible_boundary
+  }
+
+  pub fn set_show_visible_boundary(&mut self, show: bool) {
+    self.show_visible_boundary = show;
+  }
+
+  /// Whether lines are drawn between the cells of the board, with a border around it.
+  pub fn show_grid(&self) -> bool {
+    self.show_grid
+  }
+
+  pub fn set_show_grid(&mut self, show: bool) {
+    self.show_grid = show;
+  }
+
+  /// Whether the active piece's ghost is drawn where it would land.
+  pub fn ghost_enabled(&self) -> bool {
+    self.ghost_enabled
+  }
+
+  pub fn set_ghost_enabled(&mut self, enabled: bool) {
+    self.ghost_enabled = enabled;
+  }
+
+  /// The alpha the ghost piece is drawn with.
+  pub fn ghost_alpha(&self) -> u8 {
+    self.ghost_alpha
+  }
+
+  pub fn set_ghost_alpha(&mut self, alpha: u8) {
+    self.ghost_alpha = alpha;
+  }
+
+  /// The color of the lines drawn between the cells of the board.
+  pub fn grid_color(&self) -> [u8; 4] {
+    self.grid_color
+  }
+
+  pub fn set_grid_color(&mut self, color: [u8; 4]) {
+    self.grid_color = color;
+  }
+
+  /// The color of the border drawn around the board.
+  pub fn border_color(&self) -> [u8; 4] {
+    self.border_color
+  }
+
+  pub fn set_border_color(&mut self, color: [u8; 4]) {
+    self.border_color = color;
+  }
+
+  /// The colors pieces are drawn with.
+  pub fn palette(&self) -> Palette {
+    self.palette
+  }
+
+  pub fn set_palette(&mut self, palette: Palette) {
+    self.palette = palette;
+  }
+
+  /// The internal resolution the game is drawn at, which is applied the next time the game starts.
+  pub fn render_resolution(&self) -> RenderResolution {
+    self.render_resolution
+  }
+
+  pub fn set_render_resolution(&mut self, render_resolution: RenderResolution) {
+    self.render_resolution = render_resolution;
+  }
+
+  /// How long in milliseconds a piece can rest on the stack before it locks in place.
+  pub fn lock_delay_ms(&self) -> u64 {
+    self.lock_delay_ms
+  }
+
+  pub fn set_lock_delay_ms(&mut self, lock_delay_ms: u64) {
+    self.lock_delay_ms = lock_delay_ms;
+  }
+
+  /// How many times moving or rotating a resting piece can restart its lock delay.
+  ///
+  /// Once exceeded, the piece locks as soon as it's resting on the stack.
+  pub fn lock_reset_cap(&self) -> u32 {
+    self.lock_reset_cap
+  }
+
+  pub fn set_lock_reset_cap(&mut self, lock_reset_cap: u32) {
+    self.lock_reset_cap = lock_reset_cap;
+  }
+
+  /// How long in milliseconds left or right has to be held before the piece starts moving on its own.
+  ///
+  /// This is the Delayed Auto Shift, or DAS.
+  pub fn das_ms(&self) -> u64 {
+    self.das_ms
+  }
+
+  pub fn set_das_ms(&mut self, das_ms: u64) {
+    self.das_ms = das_ms;
+  }
+
+  /// How long in milliseconds between each repeated move once left or right has been held past the DAS delay.
+  ///
+  /// This is the Auto Repeat Rate, or ARR. At 0 the piece moves straight to the wall.
+  pub fn arr_ms(&self) -> u64 {
+    self.arr_ms
+  }
+
+  pub fn set_arr_ms(&mut self, arr_ms: u64) {
+    self.arr_ms = arr_ms;
+  }
+
+  /// How long in milliseconds up or down has to be held in a menu before the cursor starts moving on its own.
+  pub fn menu_das_ms(&self) -> u64 {
+    self.menu_das_ms
+  }
+
+  pub fn set_menu_das_ms(&mut self, menu_das_ms: u64) {
+    self.menu_das_ms = menu_das_ms;
+  }
+
+  /// How long in milliseconds between each repeated cursor move once up or down has been held past the menu DAS delay.
+  ///
+  /// Unlike the [`ARR`](GameSettings::arr_ms) in game, the cursor still moves one option at a time at 0.
+  pub fn menu_arr_ms(&self) -> u64 {
+    self.menu_arr_ms
+  }
+
+  pub fn set_menu_arr_ms(&mut self, menu_arr_ms: u64) {
+    self.menu_arr_ms = menu_arr_ms;
+  }
+
+  /// Whether going back from the main menu closes the game.
+  ///
+  /// When false, going back from the main menu does nothing.
+  pub fn back_exits_main_menu(&self) -> bool {
+    self.back_exits_main_menu
+  }
+
+  pub fn set_back_exits_main_menu(&mut self, back_exits_main_menu: bool) {
+    self.back_exits_main_menu = back_exits_main_menu;
+  }
+
+  /// How many of the upcoming pieces are shown next to the board.
+  ///
+  /// Clamped to [`MAX_PREVIEW_COUNT`](GameSettings::MAX_PREVIEW_COUNT).
+  pub fn preview_count(&self) -> usize {
+    self.preview_count.min(Self::MAX_PREVIEW_COUNT)
+  }
+
+  pub fn set_preview_count(&mut self, preview_count: usize) {
+    self.preview_count = preview_count;
+  }
+
+  /// Whether sound effects are silenced.
+  pub fn muted(&self) -> bool {
+    self.muted
+  }
+
+  pub fn set_muted(&mut self, muted: bool) {
+    self.muted = muted;
+  }
+
+  /// How long in milliseconds the countdown before each game lasts.
+  ///
+  /// With no countdown, games start as soon as they're picked.
+  pub fn countdown_ms(&self) -> u64 {
+    self.countdown_ms
+  }
+
+  pub fn set_countdown_ms(&mut self, countdown_ms: u64) {
+    self.countdown_ms = countdown_ms;
+  }
+
+  /// The most particles thrown out by line clears that can be on screen at once.
+  ///
+  /// Particles are disabled with a limit of 0.
+  pub fn max_particles(&self) -> usize {
+    self.max_particles
+  }
+
+  pub fn set_max_particles(&mut self, max_particles: usize) {
+    self.max_particles = max_particles;
+  }
+}
+
+impl Default for GameSettings {
+  fn default() -> Self {
+    Self {
+      fps: 144,
+      fullscreen: false,
+      present_mode: PresentMode::default(),
+      controls: Controls::default(),
+      top_out_grace_placements: 1,
+      show_visible_boundary: false,
+      show_grid: true,
+      ghost_enabled: true,
+      ghost_alpha: 0x55,
+      grid_color: [255, 255, 255, 0x18],
+      border_color: [255, 255, 255, 0x80],
+      palette: Palette::default(),
+      render_resolution: RenderResolution::default(),
+      lock_delay_ms: 500,
+      lock_reset_cap: 15,
+      das_ms: 167,
+      arr_ms: 33,
+      menu_das_ms: 300,
+      menu_arr_ms: 60,
+      back_exits_main_menu: false,
+      preview_count: 5,
+      muted: false,
+      countdown_ms: 3000,
+      max_particles: 256,
+    }
+  }
 }
 
 impl Controls {
-  fn initialize() -> anyhow::Result<Self> {
-    Ok(Self {})
+  pub const DEFAULT_FULLSCREEN_KEY: KeyCode = KeyCode::F11;
+  pub const DEFAULT_DEBUG_OVERLAY_KEY: KeyCode = KeyCode::F3;
+
+  /// Creates the controls from a list of key bindings for each context.
+  ///
+  /// If a key is bound more than once in the same context, only the first binding is kept
+  /// and a warning is logged for each binding that was dropped.
+  pub fn from_bindings(
+    game_bindings: &[(KeyCode, GameAction)],
+    menu_bindings: &[(KeyCode, MenuAction)],
+  ) -> Self {
+    let (game, dropped_game_bindings) = resolve_bindings(game_bindings);
+    let (menu, dropped_menu_bindings) = resolve_bindings(menu_bindings);
+
+    for (key, action) in dropped_game_bindings {
+      log::warn!(
+        "Dropped the game binding {:?} -> {:?}, the key was already bound to {:?}.",
+        key,
+        action,
+        game.get(&key)
+      );
+    }
+
+    for (key, action) in dropped_menu_bindings {
+      log::warn!(
+        "Dropped the menu binding {:?} -> {:?}, the key was already bound to {:?}.",
+        key,
+        action,
+        menu.get(&key)
+      );
+    }
+
+    Self {
+      game,
+      menu,
+      fullscreen_key: Self::DEFAULT_FULLSCREEN_KEY,
+      debug_overlay_key: Self::DEFAULT_DEBUG_OVERLAY_KEY,
+    }
+  }
+
+  /// Returns the game action bound to the key.
+  ///
+  /// [`GameAction::Unknown`](GameAction) is returned if the key isn't bound.
+  pub fn game_action(&self, key: &KeyCode) -> GameAction {
+    self.game.get(key).cloned().unwrap_or(GameAction::Unknown)
+  }
+
+  /// Returns the menu action bound to the key.
+  ///
+  /// [`MenuAction::Unknown`](MenuAction) is returned if the key isn't bound.
+  pub fn menu_action(&self, key: &KeyCode) -> MenuAction {
+    self.menu.get(key).cloned().unwrap_or(MenuAction::Unknown)
+  }
+
+  /// Binds the game action to the key, replacing every key the action was bound to before.
+  ///
+  /// An error is returned if the key is already bound to a different game action.
+  pub fn rebind_game(&mut self, action: GameAction, key: KeyCode) -> anyhow::Result<()> {
+    rebind(&mut self.game, action, key)
+  }
+
+  /// Binds the menu action to the key, replacing every key the action was bound to before.
+  ///
+  /// An error is returned if the key is already bound to a different menu action.
+  pub fn rebind_menu(&mut self, action: MenuAction, key: KeyCode) -> anyhow::Result<()> {
+    rebind(&mut self.menu, action, key)
+  }
+
+  /// Returns the key that switches the window between fullscreen and windowed.
+  pub fn fullscreen_key(&self) -> KeyCode {
+    self.fullscreen_key
+  }
+
+  pub fn set_fullscreen_key(&mut self, key: KeyCode) {
+    self.fullscreen_key = key;
+  }
+
+  /// Returns the key that shows or hides the frame rate overlay.
+  pub fn debug_overlay_key(&self) -> KeyCode {
+    self.debug_overlay_key
+  }
+
+  pub fn set_debug_overlay_key(&mut self, key: KeyCode) {
+    self.debug_overlay_key = key;
+  }
+
+  /// Returns every key bound to an action in either context.
+  pub fn bound_keys(&self) -> HashSet<KeyCode> {
+    self.game.keys().chain(self.menu.keys()).copied().collect()
+  }
+
+  pub fn default_game_bindings() -> Vec<(KeyCode, GameAction)> {
+    vec![
+      (KeyCode::ArrowLeft, GameAction::MoveLeft),
+      (KeyCode::KeyA, GameAction::MoveLeft),
+      (KeyCode::ArrowRight, GameAction::MoveRight),
+      (KeyCode::KeyD, GameAction::MoveRight),
+      (KeyCode::ArrowDown, GameAction::SoftDrop),
+      (KeyCode::KeyS, GameAction::SoftDrop),
+      (KeyCode::KeyX, GameAction::RotateCw),
+      (KeyCode::KeyZ, GameAction::RotateCcw),
+      (KeyCode::KeyC, GameAction::Rotate180),
+      (KeyCode::Space, GameAction::HardDrop),
+      (KeyCode::ArrowUp, GameAction::Hold),
+      (KeyCode::Escape, GameAction::Pause),
+      (KeyCode::Backspace, GameAction::QuitToMenu),
+    ]
+  }
+
+  pub fn default_menu_bindings() -> Vec<(KeyCode, MenuAction)> {
+    vec![
+      (KeyCode::ArrowUp, MenuAction::Up),
+      (KeyCode::KeyW, MenuAction::Up),
+      (KeyCode::ArrowDown, MenuAction::Down),
+      (KeyCode::KeyS, MenuAction::Down),
+      (KeyCode::ArrowLeft, MenuAction::Left),
+      (KeyCode::KeyA, MenuAction::Left),
+      (KeyCode::ArrowRight, MenuAction::Right),
+      (KeyCode::KeyD, MenuAction::Right),
+      (KeyCode::Enter, MenuAction::Select),
+      (KeyCode::KeyZ, MenuAction::Select),
+      (KeyCode::Backspace, MenuAction::Back),
+      (KeyCode::KeyX, MenuAction::Back),
+      (KeyCode::Escape, MenuAction::Back),
+    ]
+  }
+}
+
+impl Default for Controls {
+  fn default() -> Self {
+    Self::from_bindings(
+      &Self::default_game_bindings(),
+      &Self::default_menu_bindings(),
+    )
+  }
+}
+
+/// Builds a map of key bindings, keeping only the first action bound to each key.
+///
+/// Returns the map along with every binding that was dropped for reusing a key.
+fn resolve_bindings<A: Clone + Debug>(
+  bindings: &[(KeyCode, A)],
+) -> (HashMap<KeyCode, A>, Vec<(KeyCode, A)>) {
+  let mut resolved_bindings = HashMap::with_capacity(bindings.len());
+  let mut dropped_bindings = vec![];
+
+  for (key, action) in bindings {
+    if resolved_bindings.contains_key(key) {
+      dropped_bindings.push((*key, action.clone()));
+
+      continue;
+    }
+
+    resolved_bindings.insert(*key, action.clone());
+  }
+
+  (resolved_bindings, dropped_bindings)
+}
+
+/// Binds the action to the key, removing any other keys bound to the action.
+///
+/// An error is returned if the key is already bound to a different action, leaving the bindings unchanged.
+fn rebind<A: PartialEq + Debug>(
+  bindings: &mut HashMap<KeyCode, A>,
+  action: A,
+  key: KeyCode,
+) -> anyhow::Result<()> {
+  if let Some(bound_action) = bindings.get(&key) {
+    if bound_action != &action {
+      return Err(anyhow!(
+        "Failed to bind {:?} to {:?}, the key is already bound to {:?}.",
+        action,
+        key,
+        bound_action
+      ));
+    }
+  }
+
+  bindings.retain(|_, bound_action| bound_action != &action);
+  bindings.insert(key, action);
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn duplicate_key_keeps_the_first_binding() {
+    let bindings = [
+      (KeyCode::KeyA, GameAction::MoveLeft),
+      (KeyCode::KeyA, GameAction::HardDrop),
+      (KeyCode::KeyD, GameAction::MoveRight),
+    ];
+
+    let (resolved_bindings, dropped_bindings) = resolve_bindings(&bindings);
+
+    assert_eq!(resolved_bindings.len(), 2);
+    assert_eq!(
+      resolved_bindings.get(&KeyCode::KeyA),
+      Some(&GameAction::MoveLeft)
+    );
+    assert_eq!(
+      dropped_bindings,
+      vec![(KeyCode::KeyA, GameAction::HardDrop)]
+    );
+  }
+
+  #[test]
+  fn controls_resolve_duplicate_bindings() {
+    let controls = Controls::from_bindings(
+      &[
+        (KeyCode::KeyA, GameAction::MoveLeft),
+        (KeyCode::KeyA, GameAction::MoveRight),
+      ],
+      &[(KeyCode::KeyA, MenuAction::Left)],
+    );
+
+    assert_eq!(controls.game_action(&KeyCode::KeyA), GameAction::MoveLeft);
+    assert_eq!(controls.menu_action(&KeyCode::KeyA), MenuAction::Left);
+    assert_eq!(controls.game_action(&KeyCode::KeyJ), GameAction::Unknown);
+  }
+
+  #[test]
+  fn rebinding_replaces_the_previous_keys() {
+    let mut controls = Controls::default();
+
+    controls
+      .rebind_game(GameAction::MoveLeft, KeyCode::KeyJ)
+      .unwrap();
+
+    assert_eq!(controls.game_action(&KeyCode::KeyJ), GameAction::MoveLeft);
+    assert_eq!(controls.game_action(&KeyCode::KeyA), GameAction::Unknown);
+    assert_eq!(
+      controls.game_action(&KeyCode::ArrowLeft),
+      GameAction::Unknown
+    );
+  }
+
+  #[test]
+  fn rebinding_to_a_bound_key_is_a_conflict() {
+    let mut controls = Controls::default();
+
+    assert!(controls
+      .rebind_menu(MenuAction::Select, KeyCode::ArrowUp)
+      .is_err());
+    assert_eq!(controls.menu_action(&KeyCode::ArrowUp), MenuAction::Up);
+    assert_eq!(controls.menu_action(&KeyCode::Enter), MenuAction::Select);
+
+    // Rebinding to a key the action already has isn't a conflict.
+    assert!(controls
+      .rebind_menu(MenuAction::Select, KeyCode::Enter)
+      .is_ok());
+  }
+
+  #[test]
+  fn settings_round_trip_through_toml() {
+    let mut settings = GameSettings {
+      fps: 60,
+      ..Default::default()
+    };
+    settings.set_fullscreen(true);
+    settings
+      .controls_mut()
+      .rebind_game(GameAction::HardDrop, KeyCode::KeyJ)
+      .unwrap();
+
+    let serialized_settings = toml::to_string(&settings).unwrap();
+    let deserialized_settings: GameSettings = toml::from_str(&serialized_settings).unwrap();
+
+    assert_eq!(deserialized_settings, settings);
+  }
+
+  #[test]
+  fn present_mode_is_saved_by_name() {
+    let mut settings = GameSettings::default();
+    settings.set_present_mode(PresentMode::Immediate);
+
+    let serialized_settings = toml::to_string(&settings).unwrap();
+    assert!(serialized_settings.contains("present_mode = \"Immediate\""));
+
+    let deserialized_settings: GameSettings = toml::from_str(&serialized_settings).unwrap();
+    assert_eq!(deserialized_settings.present_mode(), PresentMode::Immediate);
+
+    let settings: GameSettings = toml::from_str("fps = 60").unwrap();
+    assert_eq!(settings.present_mode(), PresentMode::Vsync);
+  }
+
+  #[test]
+  fn fps_is_clamped_to_its_range() {
+    let mut settings = GameSettings::default();
+
+    settings.set_fps(90);
+    assert_eq!(settings.fps(), 90);
+
+    settings.set_fps(5);
+    assert_eq!(settings.fps(), 20);
+
+    settings.set_fps(500);
+    assert_eq!(settings.fps(), 144);
+
+    let settings: GameSettings = toml::from_str("fps = 1000").unwrap();
+    assert_eq!(settings.fps(), 144);
+  }
+
+  #[test]
+  fn missing_settings_use_their_defaults() {
+    let settings: GameSettings = toml::from_str("fps = 60").unwrap();
+
+    assert_eq!(settings.fps(), 60);
+    assert_eq!(settings.controls(), &Controls::default());
+  }
+
+  #[test]
+  fn malformed_or_missing_files_fall_back_to_the_defaults() {
+    let settings_directory =
+      std::env::temp_dir().join(format!("rustris_settings_test_{}", std::process::id()));
+    let settings_path = settings_directory.join(GameSettings::SETTINGS_FILE_NAME);
+
+    assert_eq!(
+      GameSettings::load_from_path(&settings_path),
+      GameSettings::default()
+    );
+
+    std::fs::create_dir_all(&settings_directory).unwrap();
+    std::fs::write(&settings_path, "fps = [not toml").unwrap();
+
+    assert_eq!(
+      GameSettings::load_from_path(&settings_path),
+      GameSettings::default()
+    );
+
+    let mut settings = GameSettings::default();
+    settings.set_fullscreen(true);
+    settings.save_to_path(&settings_path).unwrap();
+
+    assert_eq!(GameSettings::load_from_path(&settings_path), settings);
+
+    std::fs::remove_dir_all(&settings_directory).unwrap();
+  }
+
+  #[test]
+  fn toggled_fullscreen_is_saved() {
+    let settings_directory =
+      std::env::temp_dir().join(format!("rustris_fullscreen_test_{}", std::process::id()));
+    let settings_path = settings_directory.join(GameSettings::SETTINGS_FILE_NAME);
+    let mut settings = GameSettings::default();
+
+    assert!(settings.toggle_fullscreen());
+
+    settings.controls_mut().set_fullscreen_key(KeyCode::F10);
+    settings.save_to_path(&settings_path).unwrap();
+    let loaded_settings = GameSettings::load_from_path(&settings_path);
+
+    assert!(loaded_settings.fullscreen());
+    assert_eq!(loaded_settings.controls().fullscreen_key(), KeyCode::F10);
+
+    settings.toggle_fullscreen();
+    settings.save_to_path(&settings_path).unwrap();
+
+    assert!(!GameSettings::load_from_path(&settings_path).fullscreen());
+
+    std::fs::remove_dir_all(&settings_directory).unwrap();
+  }
+
+  #[test]
+  fn default_bindings_have_no_duplicates() {
+    let (_, dropped_game_bindings) = resolve_bindings(&Controls::default_game_bindings());
+    let (_, dropped_menu_bindings) = resolve_bindings(&Controls::default_menu_bindings());
+
+    assert!(dropped_game_bindings.is_empty());
+    assert!(dropped_menu_bindings.is_empty());
   }
 }
@@ -0,0 +1,60 @@
+use super::game_mode::GameMode;
+use super::minos::{ActivePiece, MinoType};
+use super::scoring::Scoring;
+use super::seven_bag::SevenBag;
+use super::stats::Stats;
+use super::world_state::WorldState;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// The state of a game in progress, which can be saved to disk and restored to continue the game later.
+///
+/// Menus and timers aren't part of the snapshot. A restored game starts with every timer stopped.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameSnapshot {
+  pub current_state: WorldState,
+  pub game_mode: GameMode,
+  pub mode_completed: bool,
+  pub board: Vec<Option<MinoType>>,
+  pub held: Option<MinoType>,
+  pub hold_locked: bool,
+  pub active_piece: Option<ActivePiece>,
+  pub bag: SevenBag,
+  pub seed: u64,
+  pub scoring: Scoring,
+  /// Missing from saves made before stats were tracked.
+  #[serde(default)]
+  pub stats: Stats,
+  pub game_over: bool,
+  pub top_out_grace: Option<u32>,
+  pub lock_resets: u32,
+}
+
+impl GameSnapshot {
+  pub const SAVE_FILE_NAME: &'static str = "rustris_save.json";
+
+  /// Returns the path games are saved to, inside of the platform's data directory.
+  ///
+  /// None is returned if the platform has no data directory.
+  pub fn save_path() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join("rustris").join(Self::SAVE_FILE_NAME))
+  }
+
+  /// Loads the snapshot from the json file at the path.
+  pub fn load_from_path(path: &Path) -> anyhow::Result<Self> {
+    let snapshot_file = std::fs::read_to_string(path)?;
+
+    Ok(serde_json::from_str(&snapshot_file)?)
+  }
+
+  /// Saves the snapshot as json to the path, creating any missing directories.
+  pub fn save_to_path(&self, path: &Path) -> anyhow::Result<()> {
+    if let Some(parent_directory) = path.parent() {
+      std::fs::create_dir_all(parent_directory)?;
+    }
+
+    std::fs::write(path, serde_json::to_string(self)?)?;
+
+    Ok(())
+  }
+}
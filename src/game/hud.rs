@@ -0,0 +1,166 @@
+use super::scoring::Scoring;
+use crate::renderer::color::Color;
+use crate::renderer::fonts::TextBox;
+use crate::renderer::Renderer;
+use std::collections::HashMap;
+use winit::dpi::*;
+
+/// The text shown next to the board during a game, displaying the player's score, level, and lines cleared.
+///
+/// Text boxes are only rasterized again when the value they display changes.
+#[derive(Debug, Default)]
+pub struct Hud {
+  /// The label drawn above each displayed value.
+  labels: HashMap<&'static str, TextBox>,
+  /// The last value shown for each stat, and the text box displaying it.
+  values: HashMap<&'static str, (u32, TextBox)>,
+}
+
+impl Hud {
+  pub const TEXT_SIZE: f32 = 8.0;
+  /// The distance in pixels between the top of each line of text.
+  pub const LINE_HEIGHT: u32 = 10;
+  const TEXT_COLOR: Color = Color::WHITE;
+
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Updates the displayed stats to match the given scoring, with the first label's top left at the given position.
+  ///
+  /// Requires a font to be loaded in the renderer.
+  pub fn update(
+    &mut self,
+    renderer: &Renderer,
+    scoring: &Scoring,
+    position: &LogicalPosition<u32>,
+  ) {
+    let stats = [
+      ("score", "SCORE", scoring.score()),
+      ("level", "LEVEL", scoring.level()),
+      ("lines", "LINES", scoring.lines()),
+    ];
+
+    for (index, (name, label, value)) in stats.into_iter().enumerate() {
+      let label_position = LogicalPosition::new(
+        position.x,
+        position.y + (index as u32 * Self::LINE_HEIGHT * 2),
+      );
+      let value_position = LogicalPosition::new(position.x, label_position.y + Self::LINE_HEIGHT);
+
+      self
+        .labels
+        .entry(name)
+        .or_insert_with(|| TextBox::new(renderer, 0, label, &label_position, Self::TEXT_SIZE));
+
+      match self.values.get_mut(name) {
+        Some((shown_value, _)) if *shown_value == value => (),
+        Some((shown_value, text_box)) => {
+          text_box.update_text(
+            renderer,
+            &value.to_string(),
+            Self::TEXT_SIZE,
+            &value_position,
+          );
+          *shown_value = value;
+        }
+        None => {
+          let text_box = TextBox::new(
+            renderer,
+            0,
+            &value.to_string(),
+            &value_position,
+            Self::TEXT_SIZE,
+          );
+
+          self.values.insert(name, (value, text_box));
+        }
+      }
+    }
+  }
+
+  /// Draws every label and value last set with [`update()`](Hud::update).
+  pub fn render(&self, renderer: &mut Renderer) -> anyhow::Result<()> {
+    let buffer_dimensions = renderer.buffer_dimensions();
+    let text_boxes = self
+      .labels
+      .values()
+      .chain(self.values.values().map(|(_, text_box)| text_box));
+
+    for text_box in text_boxes {
+      renderer.render_text_box(text_box, Self::TEXT_COLOR, &buffer_dimensions)?;
+    }
+
+    Ok(())
+  }
+
+  /// Returns the text box displaying the value of the given stat.
+  ///
+  /// The stats are `score`, `level`, and `lines`.
+  /// None is returned if the HUD hasn't been updated yet.
+  pub fn value_text_box(&self, name: &str) -> Option<&TextBox> {
+    self.values.get(name).map(|(_, text_box)| text_box)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::asset_loader::Assets;
+
+  mod hud_logic {
+    use super::*;
+
+    const POSITION: LogicalPosition<u32> = LogicalPosition::new(0, 0);
+
+    fn renderer_with_font() -> Renderer {
+      let assets = Assets::load_assets();
+      let mut renderer = Renderer::new_in_memory(LogicalSize::new(100, 100));
+      renderer
+        .load_font_from_bytes(assets.get_font("gadugi").unwrap(), "gadugi")
+        .unwrap();
+
+      renderer
+    }
+
+    #[test]
+    fn values_are_shown_after_the_first_update() {
+      let renderer = renderer_with_font();
+      let mut hud = Hud::new();
+
+      assert!(hud.value_text_box("score").is_none());
+
+      hud.update(&renderer, &Scoring::new(), &POSITION);
+
+      assert_eq!(hud.value_text_box("score").unwrap().text(), "0");
+      assert_eq!(hud.value_text_box("level").unwrap().text(), "1");
+      assert_eq!(hud.value_text_box("lines").unwrap().text(), "0");
+    }
+
+    #[test]
+    fn changed_score_updates_its_text_box() {
+      let renderer = renderer_with_font();
+      let mut hud = Hud::new();
+      let mut scoring = Scoring::new();
+
+      hud.update(&renderer, &scoring, &POSITION);
+
+      scoring.award_hard_drop(10);
+      hud.update(&renderer, &scoring, &POSITION);
+
+      assert_eq!(hud.value_text_box("score").unwrap().text(), "20");
+      assert_eq!(hud.value_text_box("level").unwrap().text(), "1");
+    }
+
+    #[test]
+    fn hud_renders_text() {
+      let mut renderer = renderer_with_font();
+      let mut hud = Hud::new();
+
+      hud.update(&renderer, &Scoring::new(), &POSITION);
+      hud.render(&mut renderer).unwrap();
+
+      assert!(renderer.frame().iter().any(|channel| *channel != 0));
+    }
+  }
+}
@@ -0,0 +1,67 @@
+//! The wall kick offsets used by the Super Rotation System.
+//!
+//! Each table lists the (x, y) offsets to try in order when rotating from one orientation to another.
+//! The first offset that doesn't collide is applied to the piece.
+//!
+//! The offsets are already converted to board coordinates, where y increases going down.
+//! This means every y value is the negation of the one found in most SRS references.
+
+use super::minos::{MinoType, Rotation};
+
+/// The amount of offsets tested during a rotation.
+pub const KICK_COUNT: usize = 5;
+
+/// The five offsets tested during a rotation.
+pub type KickOffsets = [(i32, i32); KICK_COUNT];
+
+pub const JLSTZ_SPAWN_TO_RIGHT: KickOffsets = [(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)];
+pub const JLSTZ_RIGHT_TO_SPAWN: KickOffsets = [(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)];
+pub const JLSTZ_RIGHT_TO_FLIPPED: KickOffsets = [(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)];
+pub const JLSTZ_FLIPPED_TO_RIGHT: KickOffsets = [(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)];
+pub const JLSTZ_FLIPPED_TO_LEFT: KickOffsets = [(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)];
+pub const JLSTZ_LEFT_TO_FLIPPED: KickOffsets = [(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)];
+pub const JLSTZ_LEFT_TO_SPAWN: KickOffsets = [(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)];
+pub const JLSTZ_SPAWN_TO_LEFT: KickOffsets = [(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)];
+
+pub const I_SPAWN_TO_RIGHT: KickOffsets = [(0, 0), (-2, 0), (1, 0), (-2, 1), (1, -2)];
+pub const I_RIGHT_TO_SPAWN: KickOffsets = [(0, 0), (2, 0), (-1, 0), (2, -1), (-1, 2)];
+pub const I_RIGHT_TO_FLIPPED: KickOffsets = [(0, 0), (-1, 0), (2, 0), (-1, -2), (2, 1)];
+pub const I_FLIPPED_TO_RIGHT: KickOffsets = [(0, 0), (1, 0), (-2, 0), (1, 2), (-2, -1)];
+pub const I_FLIPPED_TO_LEFT: KickOffsets = [(0, 0), (2, 0), (-1, 0), (2, -1), (-1, 2)];
+pub const I_LEFT_TO_FLIPPED: KickOffsets = [(0, 0), (-2, 0), (1, 0), (-2, 1), (1, -2)];
+pub const I_LEFT_TO_SPAWN: KickOffsets = [(0, 0), (1, 0), (-2, 0), (1, 2), (-2, -1)];
+pub const I_SPAWN_TO_LEFT: KickOffsets = [(0, 0), (-1, 0), (2, 0), (-1, -2), (2, 1)];
+
+/// The O piece looks the same in every orientation, so it never needs to be kicked.
+pub const O_KICKS: KickOffsets = [(0, 0); 5];
+
+/// Returns the kick offsets to test when rotating the given piece between two orientations.
+///
+/// Any transition that isn't a single 90 degree turn has no kicks, returning only the unmoved offset.
+pub fn kick_offsets(kind: MinoType, from: Rotation, to: Rotation) -> KickOffsets {
+  use Rotation::*;
+
+  match (kind, from, to) {
+    (MinoType::O, _, _) => O_KICKS,
+
+    (MinoType::I, Spawn, Right) => I_SPAWN_TO_RIGHT,
+    (MinoType::I, Right, Spawn) => I_RIGHT_TO_SPAWN,
+    (MinoType::I, Right, Flipped) => I_RIGHT_TO_FLIPPED,
+    (MinoType::I, Flipped, Right) => I_FLIPPED_TO_RIGHT,
+    (MinoType::I, Flipped, Left) => I_FLIPPED_TO_LEFT,
+    (MinoType::I, Left, Flipped) => I_LEFT_TO_FLIPPED,
+    (MinoType::I, Left, Spawn) => I_LEFT_TO_SPAWN,
+    (MinoType::I, Spawn, Left) => I_SPAWN_TO_LEFT,
+
+    (_, Spawn, Right) => JLSTZ_SPAWN_TO_RIGHT,
+    (_, Right, Spawn) => JLSTZ_RIGHT_TO_SPAWN,
+    (_, Right, Flipped) => JLSTZ_RIGHT_TO_FLIPPED,
+    (_, Flipped, Right) => JLSTZ_FLIPPED_TO_RIGHT,
+    (_, Flipped, Left) => JLSTZ_FLIPPED_TO_LEFT,
+    (_, Left, Flipped) => JLSTZ_LEFT_TO_FLIPPED,
+    (_, Left, Spawn) => JLSTZ_LEFT_TO_SPAWN,
+    (_, Spawn, Left) => JLSTZ_SPAWN_TO_LEFT,
+
+    _ => O_KICKS,
+  }
+}
@@ -0,0 +1,325 @@
+use super::game_mode::GameMode;
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::path::{Path, PathBuf};
+
+/// The highest scores reached, kept across runs of the game.
+///
+/// Entries are kept in rank order, highest score first, and only the best
+/// [`MAX_ENTRIES`](Leaderboard::MAX_ENTRIES) are kept.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Leaderboard {
+  entries: Vec<LeaderboardEntry>,
+}
+
+/// A single score on the [`Leaderboard`](Leaderboard).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+  pub name: String,
+  pub score: u32,
+  pub game_mode: GameMode,
+  /// The day the score was reached, as year-month-day.
+  pub date: String,
+}
+
+impl Leaderboard {
+  pub const FILE_NAME: &'static str = "rustris_leaderboard.json";
+  /// How many scores are kept.
+  pub const MAX_ENTRIES: usize = 10;
+  /// The most characters a name can have.
+  pub const MAX_NAME_LENGTH: usize = 8;
+  /// The name given to a score when none was entered.
+  pub const ANONYMOUS_NAME: &'static str = "???";
+
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Returns the path the leaderboard is saved to, inside of the platform's data directory.
+  ///
+  /// None is returned if the platform has no data directory.
+  pub fn save_path() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join("rustris").join(Self::FILE_NAME))
+  }
+
+  /// Loads the leaderboard saved to the [`save path`](Leaderboard::save_path).
+  ///
+  /// An empty leaderboard is used if the file is missing or malformed.
+  pub fn load_from_disk() -> Self {
+    let Some(save_path) = Self::save_path() else {
+      log::warn!("There is no data directory, starting with an empty leaderboard.");
+
+      return Self::new();
+    };
+
+    Self::load_from_path(&save_path)
+  }
+
+  /// Loads the leaderboard from the json file at the path.
+  ///
+  /// An empty leaderboard is used if the file is missing or malformed.
+  pub fn load_from_path(path: &Path) -> Self {
+    let leaderboard_file = match std::fs::read_to_string(path) {
+      Ok(leaderboard_file) => leaderboard_file,
+      Err(error) => {
+        log::info!(
+          "Failed to read the leaderboard at {:?}, starting with an empty leaderboard. `{:?}`",
+          path,
+          error
+        );
+
+        return Self::new();
+      }
+    };
+
+    match serde_json::from_str::<Self>(&leaderboard_file) {
+      Ok(mut leaderboard) => {
+        leaderboard
+          .entries
+          .sort_by_key(|entry| Reverse(entry.score));
+        leaderboard.entries.truncate(Self::MAX_ENTRIES);
+
+        leaderboard
+      }
+      Err(error) => {
+        log::warn!(
+          "The leaderboard at {:?} is malformed, starting with an empty leaderboard. `{}`",
+          path,
+          error
+        );
+
+        Self::new()
+      }
+    }
+  }
+
+  /// Saves the leaderboard to the [`save path`](Leaderboard::save_path).
+  pub fn save_to_disk(&self) -> anyhow::Result<()> {
+    let Some(save_path) = Self::save_path() else {
+      return Err(anyhow!(
+        "Failed to save the leaderboard, there is no data directory."
+      ));
+    };
+
+    self.save_to_path(&save_path)
+  }
+
+  /// Saves the leaderboard as json to the path, creating any missing directories.
+  pub fn save_to_path(&self, path: &Path) -> anyhow::Result<()> {
+    if let Some(parent_directory) = path.parent() {
+      std::fs::create_dir_all(parent_directory)?;
+    }
+
+    std::fs::write(path, serde_json::to_string(self)?)?;
+
+    Ok(())
+  }
+
+  /// The scores on the leaderboard, highest first.
+  pub fn entries(&self) -> &[LeaderboardEntry] {
+    &self.entries
+  }
+
+  /// Returns true if the score is high enough to be placed on the leaderboard.
+  ///
+  /// A score of 0 never qualifies.
+  pub fn qualifies(&self, score: u32) -> bool {
+    if score == 0 {
+      return false;
+    }
+
+    self.entries.len() < Self::MAX_ENTRIES
+      || self
+        .entries
+        .last()
+        .is_some_and(|lowest| score > lowest.score)
+  }
+
+  /// Places the entry in rank order, dropping the lowest score if the leaderboard was full.
+  ///
+  /// Entries tied with an existing score are placed after it.
+  /// Returns the index the entry was placed at, or None if it didn't qualify.
+  pub fn insert(&mut self, entry: LeaderboardEntry) -> Option<usize> {
+    if !self.qualifies(entry.score) {
+      return None;
+    }
+
+    let rank = self
+      .entries
+      .partition_point(|existing| existing.score >= entry.score);
+
+    self.entries.insert(rank, entry);
+    self.entries.truncate(Self::MAX_ENTRIES);
+
+    Some(rank)
+  }
+}
+
+impl LeaderboardEntry {
+  /// Creates an entry dated today.
+  ///
+  /// Names are cut down to [`MAX_NAME_LENGTH`](Leaderboard::MAX_NAME_LENGTH) characters,
+  /// and an empty name is replaced with [`ANONYMOUS_NAME`](Leaderboard::ANONYMOUS_NAME).
+  pub fn new(name: &str, score: u32, game_mode: GameMode) -> Self {
+    let name: String = name
+      .trim()
+      .chars()
+      .take(Leaderboard::MAX_NAME_LENGTH)
+      .collect();
+    let name = if name.is_empty() {
+      Leaderboard::ANONYMOUS_NAME.to_string()
+    } else {
+      name
+    };
+
+    Self {
+      name,
+      score,
+      game_mode,
+      date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn entry(score: u32) -> LeaderboardEntry {
+    LeaderboardEntry::new("test", score, GameMode::Marathon)
+  }
+
+  fn full_leaderboard() -> Leaderboard {
+    let mut leaderboard = Leaderboard::new();
+
+    for score in 1..=Leaderboard::MAX_ENTRIES as u32 {
+      leaderboard.insert(entry(score * 100));
+    }
+
+    leaderboard
+  }
+
+  fn scores(leaderboard: &Leaderboard) -> Vec<u32> {
+    leaderboard
+      .entries()
+      .iter()
+      .map(|entry| entry.score)
+      .collect()
+  }
+
+  mod insert_logic {
+    use super::*;
+
+    #[test]
+    fn inserted_scores_are_kept_in_rank_order() {
+      let mut leaderboard = Leaderboard::new();
+
+      assert_eq!(leaderboard.insert(entry(300)), Some(0));
+      assert_eq!(leaderboard.insert(entry(500)), Some(0));
+      assert_eq!(leaderboard.insert(entry(400)), Some(1));
+      assert_eq!(leaderboard.insert(entry(100)), Some(3));
+
+      assert_eq!(scores(&leaderboard), [500, 400, 300, 100]);
+    }
+
+    #[test]
+    fn full_leaderboard_drops_the_lowest_score() {
+      let mut leaderboard = full_leaderboard();
+
+      assert_eq!(leaderboard.insert(entry(550)), Some(5));
+
+      assert_eq!(leaderboard.entries().len(), Leaderboard::MAX_ENTRIES);
+      assert_eq!(leaderboard.entries()[5].score, 550);
+      assert_eq!(leaderboard.entries().last().unwrap().score, 200);
+      assert!(scores(&leaderboard)
+        .windows(2)
+        .all(|pair| pair[0] >= pair[1]));
+    }
+
+    #[test]
+    fn scores_too_low_for_a_full_leaderboard_are_rejected() {
+      let mut leaderboard = full_leaderboard();
+
+      assert!(!leaderboard.qualifies(100));
+      assert_eq!(leaderboard.insert(entry(50)), None);
+      assert_eq!(leaderboard, full_leaderboard());
+    }
+
+    #[test]
+    fn ties_are_placed_after_the_existing_score() {
+      let mut leaderboard = Leaderboard::new();
+      leaderboard.insert(LeaderboardEntry::new("first", 100, GameMode::Marathon));
+
+      let rank = leaderboard.insert(LeaderboardEntry::new("second", 100, GameMode::Ultra));
+
+      assert_eq!(rank, Some(1));
+      assert_eq!(leaderboard.entries()[0].name, "first");
+    }
+
+    #[test]
+    fn zero_never_qualifies() {
+      assert!(!Leaderboard::new().qualifies(0));
+    }
+  }
+
+  mod entry_logic {
+    use super::*;
+
+    #[test]
+    fn long_names_are_shortened() {
+      let entry = LeaderboardEntry::new("a very long name", 100, GameMode::Sprint);
+
+      assert_eq!(entry.name.chars().count(), Leaderboard::MAX_NAME_LENGTH);
+    }
+
+    #[test]
+    fn empty_names_are_anonymous() {
+      let entry = LeaderboardEntry::new("  ", 100, GameMode::Sprint);
+
+      assert_eq!(entry.name, Leaderboard::ANONYMOUS_NAME);
+    }
+  }
+
+  mod save_logic {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+      std::env::temp_dir().join(format!(
+        "rustris_leaderboard_{}_{}.json",
+        name,
+        std::process::id()
+      ))
+    }
+
+    #[test]
+    fn missing_file_loads_an_empty_leaderboard() {
+      let leaderboard = Leaderboard::load_from_path(&temp_path("missing"));
+
+      assert!(leaderboard.entries().is_empty());
+    }
+
+    #[test]
+    fn malformed_file_loads_an_empty_leaderboard() {
+      let path = temp_path("malformed");
+      std::fs::write(&path, "not a leaderboard").unwrap();
+
+      let leaderboard = Leaderboard::load_from_path(&path);
+      std::fs::remove_file(&path).unwrap();
+
+      assert!(leaderboard.entries().is_empty());
+    }
+
+    #[test]
+    fn leaderboard_round_trips_through_the_file() {
+      let path = temp_path("round_trip");
+      let leaderboard = full_leaderboard();
+
+      leaderboard.save_to_path(&path).unwrap();
+      let loaded = Leaderboard::load_from_path(&path);
+      std::fs::remove_file(&path).unwrap();
+
+      assert_eq!(loaded, leaderboard);
+    }
+  }
+}
@@ -1,4 +1,6 @@
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MinoType {
   I,
   L,
@@ -7,25 +9,233 @@ pub enum MinoType {
   T,
   S,
   Z,
+  /// A cell of a row pushed onto the board by [`add_garbage()`](crate::game::world_data::WorldData::add_garbage).
+  ///
+  /// Garbage is never a piece, so it isn't in [`ALL`](MinoType::ALL) and has no cells.
+  Garbage,
+}
+
+/// The four orientations a piece can be in.
+///
+/// Follows the SRS naming, where [`Spawn`](Rotation::Spawn) is the orientation a piece spawns in,
+/// and every other orientation is named after the direction it was rotated towards.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Rotation {
+  Spawn,
+  Right,
+  Flipped,
+  Left,
+}
+
+/// The piece currently being controlled by the player.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivePiece {
+  pub kind: MinoType,
+  pub rotation: Rotation,
+  /// The top left of the piece's bounding box in board coordinates, with y going down.
+  pub origin: (i32, i32),
+}
+
+impl ActivePiece {
+  pub fn new(kind: MinoType, origin: (i32, i32)) -> Self {
+    Self {
+      kind,
+      rotation: Rotation::Spawn,
+      origin,
+    }
+  }
+
+  /// Returns the board coordinates of the four cells of this piece.
+  pub fn cells(&self) -> [(i32, i32); 4] {
+    self
+      .kind
+      .cells(self.rotation)
+      .map(|(x_offset, y_offset)| (self.origin.0 + x_offset, self.origin.1 + y_offset))
+  }
+}
+
+impl Rotation {
+  /// Returns the orientation after a 90 degree clockwise turn.
+  pub fn clockwise(&self) -> Self {
+    match self {
+      Rotation::Spawn => Rotation::Right,
+      Rotation::Right => Rotation::Flipped,
+      Rotation::Flipped => Rotation::Left,
+      Rotation::Left => Rotation::Spawn,
+    }
+  }
+
+  /// Returns the orientation after a 90 degree counterclockwise turn.
+  pub fn counter_clockwise(&self) -> Self {
+    match self {
+      Rotation::Spawn => Rotation::Left,
+      Rotation::Right => Rotation::Spawn,
+      Rotation::Flipped => Rotation::Right,
+      Rotation::Left => Rotation::Flipped,
+    }
+  }
+
+  /// Returns the orientation after a 180 degree turn.
+  pub fn flipped(&self) -> Self {
+    self.clockwise().clockwise()
+  }
 }
 
 impl MinoType {
-  #[inline]
-  pub fn color(&self) -> [u8; 3] {
-    self.into()
+  /// Every piece type, in a stable order matching their [`index`](MinoType::to_index).
+  pub const ALL: [MinoType; 7] = [
+    MinoType::I,
+    MinoType::L,
+    MinoType::J,
+    MinoType::O,
+    MinoType::T,
+    MinoType::S,
+    MinoType::Z,
+  ];
+
+  /// Returns an iterator over every piece type in the order of [`ALL`](MinoType::ALL).
+  pub fn iter() -> impl Iterator<Item = MinoType> {
+    Self::ALL.into_iter()
+  }
+
+  /// Returns the piece type at the given index in [`ALL`](MinoType::ALL).
+  ///
+  /// None is returned if the index is out of range.
+  pub fn from_index(index: u8) -> Option<Self> {
+    Self::ALL.get(index as usize).copied()
+  }
+
+  /// Returns the index of this piece type in [`ALL`](MinoType::ALL).
+  ///
+  /// [`Garbage`](MinoType::Garbage) comes after every piece type.
+  pub fn to_index(&self) -> u8 {
+    match self {
+      MinoType::I => 0,
+      MinoType::L => 1,
+      MinoType::J => 2,
+      MinoType::O => 3,
+      MinoType::T => 4,
+      MinoType::S => 5,
+      MinoType::Z => 6,
+      MinoType::Garbage => 7,
+    }
+  }
+
+  /// Returns the piece type named by the given letter, ignoring case.
+  ///
+  /// None is returned if the letter doesn't name a piece type.
+  pub fn from_letter(letter: char) -> Option<Self> {
+    Self::iter()
+      .chain([MinoType::Garbage])
+      .find(|mino_type| mino_type.to_letter() == letter.to_ascii_uppercase())
+  }
+
+  /// Returns the uppercase letter this piece type is named after.
+  pub fn to_letter(&self) -> char {
+    match self {
+      MinoType::I => 'I',
+      MinoType::L => 'L',
+      MinoType::J => 'J',
+      MinoType::O => 'O',
+      MinoType::T => 'T',
+      MinoType::S => 'S',
+      MinoType::Z => 'Z',
+      MinoType::Garbage => 'G',
+    }
+  }
+
+  /// Returns the (x, y) offsets of the four cells of this piece for the given rotation.
+  ///
+  /// Offsets are relative to the top left of the piece's bounding box, with y going down.
+  /// The I piece uses a 4x4 bounding box, every other piece uses a 3x3 one.
+  pub fn cells(&self, rotation: Rotation) -> [(i32, i32); 4] {
+    match (self, rotation) {
+      (MinoType::I, Rotation::Spawn) => [(0, 1), (1, 1), (2, 1), (3, 1)],
+      (MinoType::I, Rotation::Right) => [(2, 0), (2, 1), (2, 2), (2, 3)],
+      (MinoType::I, Rotation::Flipped) => [(0, 2), (1, 2), (2, 2), (3, 2)],
+      (MinoType::I, Rotation::Left) => [(1, 0), (1, 1), (1, 2), (1, 3)],
+
+      (MinoType::J, Rotation::Spawn) => [(0, 0), (0, 1), (1, 1), (2, 1)],
+      (MinoType::J, Rotation::Right) => [(1, 0), (2, 0), (1, 1), (1, 2)],
+      (MinoType::J, Rotation::Flipped) => [(0, 1), (1, 1), (2, 1), (2, 2)],
+      (MinoType::J, Rotation::Left) => [(1, 0), (1, 1), (0, 2), (1, 2)],
+
+      (MinoType::L, Rotation::Spawn) => [(2, 0), (0, 1), (1, 1), (2, 1)],
+      (MinoType::L, Rotation::Right) => [(1, 0), (1, 1), (1, 2), (2, 2)],
+      (MinoType::L, Rotation::Flipped) => [(0, 1), (1, 1), (2, 1), (0, 2)],
+      (MinoType::L, Rotation::Left) => [(0, 0), (1, 0), (1, 1), (1, 2)],
+
+      (MinoType::O, _) => [(1, 0), (2, 0), (1, 1), (2, 1)],
+
+      (MinoType::S, Rotation::Spawn) => [(1, 0), (2, 0), (0, 1), (1, 1)],
+      (MinoType::S, Rotation::Right) => [(1, 0), (1, 1), (2, 1), (2, 2)],
+      (MinoType::S, Rotation::Flipped) => [(1, 1), (2, 1), (0, 2), (1, 2)],
+      (MinoType::S, Rotation::Left) => [(0, 0), (0, 1), (1, 1), (1, 2)],
+
+      (MinoType::T, Rotation::Spawn) => [(1, 0), (0, 1), (1, 1), (2, 1)],
+      (MinoType::T, Rotation::Right) => [(1, 0), (1, 1), (2, 1), (1, 2)],
+      (MinoType::T, Rotation::Flipped) => [(0, 1), (1, 1), (2, 1), (1, 2)],
+      (MinoType::T, Rotation::Left) => [(1, 0), (0, 1), (1, 1), (1, 2)],
+
+      (MinoType::Z, Rotation::Spawn) => [(0, 0), (1, 0), (1, 1), (2, 1)],
+      (MinoType::Z, Rotation::Right) => [(2, 0), (1, 1), (2, 1), (1, 2)],
+      (MinoType::Z, Rotation::Flipped) => [(0, 1), (1, 1), (1, 2), (2, 2)],
+      (MinoType::Z, Rotation::Left) => [(1, 0), (0, 1), (1, 1), (0, 2)],
+
+      (MinoType::Garbage, _) => unreachable!("Garbage is never placed as a piece."),
+    }
   }
 }
 
-impl From<&MinoType> for [u8; 3] {
-  fn from(mino: &MinoType) -> [u8; 3] {
-    match mino {
-      MinoType::I => [0x32, 0xC5, 0xF5],
-      MinoType::L => [0xFA, 0xA2, 0x47],
-      MinoType::J => [0x00, 0x7A, 0xBF],
-      MinoType::O => [0xFE, 0xD7, 0x1E],
-      MinoType::T => [0xA0, 0x51, 0x9F],
-      MinoType::S => [0x7B, 0xBE, 0x44],
-      MinoType::Z => [0xEF, 0x4B, 0x39],
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  mod index_logic {
+    use super::*;
+
+    #[test]
+    fn every_piece_type_is_listed_once() {
+      assert_eq!(MinoType::ALL.len(), 7);
+      assert_eq!(MinoType::iter().collect::<Vec<_>>(), MinoType::ALL);
+    }
+
+    #[test]
+    fn indices_round_trip() {
+      for (index, mino_type) in MinoType::iter().enumerate() {
+        assert_eq!(mino_type.to_index(), index as u8);
+        assert_eq!(MinoType::from_index(mino_type.to_index()), Some(mino_type));
+      }
+    }
+
+    #[test]
+    fn garbage_is_not_a_piece_type() {
+      assert!(!MinoType::ALL.contains(&MinoType::Garbage));
+      assert_eq!(MinoType::from_index(MinoType::Garbage.to_index()), None);
+    }
+
+    #[test]
+    fn out_of_range_index_is_none() {
+      assert_eq!(MinoType::from_index(7), None);
+      assert_eq!(MinoType::from_index(u8::MAX), None);
+    }
+
+    #[test]
+    fn letters_round_trip() {
+      for mino_type in MinoType::iter() {
+        assert_eq!(
+          MinoType::from_letter(mino_type.to_letter()),
+          Some(mino_type)
+        );
+        assert_eq!(
+          MinoType::from_letter(mino_type.to_letter().to_ascii_lowercase()),
+          Some(mino_type)
+        );
+      }
+
+      assert_eq!(MinoType::from_letter('G'), Some(MinoType::Garbage));
+      assert_eq!(MinoType::from_letter('.'), None);
+      assert_eq!(MinoType::from_letter('X'), None);
     }
   }
 }
@@ -0,0 +1,110 @@
+use super::minos::MinoType;
+use serde::{Deserialize, Serialize};
+
+/// The sets of colors pieces can be drawn with.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Palette {
+  /// The standard guideline colors.
+  #[default]
+  Default,
+  /// Colors from the Okabe-Ito palette, which stay distinct with red-green color blindness.
+  Deuteranopia,
+  /// Fully saturated colors for telling pieces apart at a glance.
+  HighContrast,
+}
+
+impl Palette {
+  pub const ALL: [Palette; 3] = [
+    Palette::Default,
+    Palette::Deuteranopia,
+    Palette::HighContrast,
+  ];
+
+  /// The color of garbage, which is the same in every palette.
+  pub const GARBAGE_COLOR: [u8; 3] = [0x80, 0x80, 0x80];
+
+  /// Returns the color of the piece in this palette.
+  pub fn color(&self, mino: MinoType) -> [u8; 3] {
+    match mino {
+      MinoType::Garbage => Self::GARBAGE_COLOR,
+      _ => self.colors()[mino.to_index() as usize],
+    }
+  }
+
+  /// Returns the palette after this one, wrapping around to the first.
+  pub fn next(&self) -> Self {
+    let index = Self::ALL
+      .iter()
+      .position(|palette| palette == self)
+      .unwrap_or(0);
+
+    Self::ALL[(index + 1) % Self::ALL.len()]
+  }
+
+  /// The color of every piece, in the order of [`MinoType::ALL`](MinoType::ALL).
+  fn colors(&self) -> [[u8; 3]; 7] {
+    match self {
+      Palette::Default => [
+        [0x32, 0xC5, 0xF5],
+        [0xFA, 0xA2, 0x47],
+        [0x00, 0x7A, 0xBF],
+        [0xFE, 0xD7, 0x1E],
+        [0xA0, 0x51, 0x9F],
+        [0x7B, 0xBE, 0x44],
+        [0xEF, 0x4B, 0x39],
+      ],
+      Palette::Deuteranopia => [
+        [0x56, 0xB4, 0xE9],
+        [0xE6, 0x9F, 0x00],
+        [0x00, 0x72, 0xB2],
+        [0xF0, 0xE4, 0x42],
+        [0xCC, 0x79, 0xA7],
+        [0x00, 0x9E, 0x73],
+        [0xD5, 0x5E, 0x00],
+      ],
+      Palette::HighContrast => [
+        [0x00, 0xFF, 0xFF],
+        [0xFF, 0x80, 0x00],
+        [0x00, 0x40, 0xFF],
+        [0xFF, 0xFF, 0x00],
+        [0xFF, 0x00, 0xFF],
+        [0x00, 0xFF, 0x00],
+        [0xFF, 0x00, 0x00],
+      ],
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn every_palette_has_a_distinct_color_for_each_piece() {
+    for palette in Palette::ALL {
+      let mut colors: Vec<[u8; 3]> = MinoType::iter()
+        .chain([MinoType::Garbage])
+        .map(|mino| palette.color(mino))
+        .collect();
+      colors.sort_unstable();
+      colors.dedup();
+
+      assert_eq!(colors.len(), MinoType::ALL.len() + 1, "{:?}", palette);
+    }
+  }
+
+  #[test]
+  fn next_cycles_through_every_palette() {
+    let mut palette = Palette::default();
+
+    for expected_palette in [
+      Palette::Deuteranopia,
+      Palette::HighContrast,
+      Palette::Default,
+    ] {
+      palette = palette.next();
+
+      assert_eq!(palette, expected_palette);
+    }
+  }
+}
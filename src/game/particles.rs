@@ -0,0 +1,207 @@
+use crate::general_data::rng::Rng;
+use crate::general_data::timer::Timer;
+use crate::renderer::Renderer;
+use std::time::Duration;
+use winit::dpi::*;
+
+/// A single particle, with its position and velocity measured in board cells.
+#[derive(Debug)]
+struct Particle {
+  position: (f32, f32),
+  /// How far the particle moves each tick.
+  velocity: (f32, f32),
+  color: [u8; 3],
+  /// Started when the particle is spawned, the particle is removed once this runs out.
+  lifetime: Timer,
+}
+
+/// The particles thrown out of cleared rows.
+///
+/// Particles are purely visual and never touch the board.
+/// Positions are measured in cells from the top left of the visible board, so they're
+/// independent of the size the board is drawn at.
+#[derive(Debug)]
+pub struct Particles {
+  particles: Vec<Particle>,
+  /// The most particles that can exist at once. Anything spawned past this is dropped.
+  max_particles: usize,
+  rng: Rng,
+}
+
+impl Particles {
+  /// How many particles are spawned out of each cell of a cleared row.
+  pub const PARTICLES_PER_CELL: usize = 2;
+  /// How long each particle lasts, fading out until it's removed.
+  pub const LIFETIME: Duration = Duration::from_millis(600);
+  /// How much the downwards velocity of a particle increases each tick.
+  const GRAVITY: f32 = 0.02;
+  /// The largest sideways speed a particle can spawn with, in cells per tick.
+  const MAX_HORIZONTAL_SPEED: f32 = 0.15;
+  /// The range of upwards speeds a particle can spawn with, in cells per tick.
+  const UPWARDS_SPEED: (f32, f32) = (0.1, 0.3);
+
+  pub fn new(max_particles: usize, seed: u64) -> Self {
+    Self {
+      particles: Vec::with_capacity(max_particles),
+      max_particles,
+      rng: Rng::from_seed(seed),
+    }
+  }
+
+  /// Throws out particles from each cell of a cleared row.
+  ///
+  /// Cells are given as their position on the visible board and the color they were drawn with.
+  pub fn spawn_burst(&mut self, cells: &[((i32, i32), [u8; 3])]) {
+    for &((x, y), color) in cells {
+      for _ in 0..Self::PARTICLES_PER_CELL {
+        if self.particles.len() >= self.max_particles {
+          return;
+        }
+
+        let horizontal_speed = (self.random_fraction() * 2.0 - 1.0) * Self::MAX_HORIZONTAL_SPEED;
+        let (min_upwards_speed, max_upwards_speed) = Self::UPWARDS_SPEED;
+        let upwards_speed =
+          min_upwards_speed + self.random_fraction() * (max_upwards_speed - min_upwards_speed);
+        let lifetime = Timer::new(Self::LIFETIME);
+        lifetime.start();
+
+        self.particles.push(Particle {
+          position: (x as f32 + 0.5, y as f32 + 0.5),
+          velocity: (horizontal_speed, -upwards_speed),
+          color,
+          lifetime,
+        });
+      }
+    }
+  }
+
+  /// Moves every particle by its velocity and applies gravity, removing particles whose lifetime ran out.
+  pub fn update(&mut self) {
+    self
+      .particles
+      .retain(|particle| particle.lifetime.remaining() != Some(Duration::ZERO));
+
+    for particle in &mut self.particles {
+      particle.velocity.1 += Self::GRAVITY;
+      particle.position.0 += particle.velocity.0;
+      particle.position.1 += particle.velocity.1;
+    }
+  }
+
+  /// Draws each particle as a single pixel, fading out over its lifetime.
+  ///
+  /// The origin is the pixel the top left of the visible board is drawn at, with each cell being `cell_size` pixels wide.
+  pub fn render(
+    &self,
+    renderer: &mut Renderer,
+    origin: &LogicalPosition<u32>,
+    cell_size: u32,
+  ) -> anyhow::Result<()> {
+    let LogicalSize { width, height } = renderer.buffer_dimensions();
+
+    for particle in &self.particles {
+      let x = origin.x as f32 + particle.position.0 * cell_size as f32;
+      let y = origin.y as f32 + particle.position.1 * cell_size as f32;
+
+      if x < 0.0 || y < 0.0 || x >= width as f32 || y >= height as f32 {
+        continue;
+      }
+
+      let [red, green, blue] = particle.color;
+      let alpha = ((1.0 - particle.lifetime.elapsed_fraction()) * 255.0).round() as u8;
+      let pixel_index = x as usize + (y as usize * width as usize);
+
+      Renderer::draw_at_pixel_with_rgba(
+        renderer.frame_mut(),
+        pixel_index,
+        &[red, green, blue, alpha],
+      )?;
+    }
+
+    Ok(())
+  }
+
+  /// Returns how many particles currently exist.
+  pub fn len(&self) -> usize {
+    self.particles.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.particles.is_empty()
+  }
+
+  pub fn clear(&mut self) {
+    self.particles.clear();
+  }
+
+  pub fn max_particles(&self) -> usize {
+    self.max_particles
+  }
+
+  /// Sets the most particles that can exist at once, removing the oldest particles past the new limit.
+  pub fn set_max_particles(&mut self, max_particles: usize) {
+    let excess_count = self.particles.len().saturating_sub(max_particles);
+
+    self.particles.drain(..excess_count);
+    self.max_particles = max_particles;
+  }
+
+  /// Returns a number in the range `0.0..1.0`.
+  fn random_fraction(&mut self) -> f32 {
+    self.rng.next_below(1 << 16) as f32 / (1 << 16) as f32
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::general_data::timer::clock;
+
+  /// A full row of red cells along the top of the board.
+  fn row() -> Vec<((i32, i32), [u8; 3])> {
+    (0..10).map(|x| ((x, 0), [255, 0, 0])).collect()
+  }
+
+  #[test]
+  fn particles_are_removed_once_their_lifetime_expires() {
+    let mut particles = Particles::new(100, 0);
+
+    particles.spawn_burst(&row());
+    particles.update();
+
+    assert_eq!(particles.len(), row().len() * Particles::PARTICLES_PER_CELL);
+
+    clock::advance(Particles::LIFETIME);
+    particles.update();
+
+    assert!(particles.is_empty());
+  }
+
+  #[test]
+  fn particle_count_is_bounded() {
+    let mut particles = Particles::new(5, 0);
+
+    particles.spawn_burst(&row());
+
+    assert_eq!(particles.len(), 5);
+
+    particles.set_max_particles(0);
+    particles.spawn_burst(&row());
+
+    assert!(particles.is_empty());
+  }
+
+  #[test]
+  fn particles_fall_over_time() {
+    let mut particles = Particles::new(1, 0);
+
+    particles.spawn_burst(&row()[..1]);
+    let starting_velocity = particles.particles[0].velocity.1;
+
+    for _ in 0..100 {
+      particles.update();
+    }
+
+    assert!(particles.particles[0].velocity.1 > starting_velocity.max(0.0));
+  }
+}
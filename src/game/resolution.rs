@@ -0,0 +1,55 @@
+use crate::rustris_config::RENDERED_WINDOW_DIMENSIONS;
+use serde::{Deserialize, Serialize};
+use winit::dpi::LogicalSize;
+
+/// The internal resolutions the game can be drawn at, before being scaled up to fill the window.
+///
+/// Every preset keeps the same aspect ratio, so the layout only changes in size.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RenderResolution {
+  /// 250x400, the resolution the game was designed at.
+  #[default]
+  Standard,
+  /// 375x600.
+  Large,
+  /// 500x800.
+  ExtraLarge,
+}
+
+impl RenderResolution {
+  pub const ALL: [RenderResolution; 3] = [
+    RenderResolution::Standard,
+    RenderResolution::Large,
+    RenderResolution::ExtraLarge,
+  ];
+
+  /// Returns the width and height in pixels of the buffer drawn to at this resolution.
+  pub fn dimensions(&self) -> LogicalSize<u32> {
+    match self {
+      RenderResolution::Standard => RENDERED_WINDOW_DIMENSIONS,
+      RenderResolution::Large => LogicalSize::new(375, 600),
+      RenderResolution::ExtraLarge => LogicalSize::new(500, 800),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn every_resolution_keeps_the_standard_aspect_ratio() {
+    let standard = RenderResolution::Standard.dimensions();
+
+    for resolution in RenderResolution::ALL {
+      let dimensions = resolution.dimensions();
+
+      assert_eq!(
+        dimensions.width * standard.height,
+        dimensions.height * standard.width,
+        "{:?}",
+        resolution
+      );
+    }
+  }
+}
@@ -0,0 +1,216 @@
+use serde::{Deserialize, Serialize};
+
+/// The kinds of T-spin a T piece can be locked with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TSpin {
+  /// The T piece was rotated into a spot with only one of the corners it's pointing towards filled.
+  Mini,
+  Full,
+}
+
+/// Keeps track of the player's score, level, and total lines cleared.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Scoring {
+  score: u32,
+  level: u32,
+  lines: u32,
+  /// How many drops in a row have cleared lines, minus one.
+  ///
+  /// -1 when the last drop cleared no lines.
+  combo: i32,
+  /// Set when the last line clear was a tetris or a T-spin, until a clear that's neither.
+  back_to_back: bool,
+}
+
+impl Scoring {
+  /// Points awarded for every cell a piece is soft dropped.
+  pub const SOFT_DROP_POINTS: u32 = 1;
+  /// Points awarded for every cell a piece is hard dropped.
+  pub const HARD_DROP_POINTS: u32 = 2;
+  /// The amount of lines that need to be cleared to advance a level.
+  pub const LINES_PER_LEVEL: u32 = 10;
+  /// Points awarded for each step of a combo, past the first clear.
+  pub const COMBO_POINTS: u32 = 50;
+
+  #[allow(clippy::new_without_default)]
+  pub fn new() -> Self {
+    Self {
+      score: 0,
+      level: 1,
+      lines: 0,
+      combo: -1,
+      back_to_back: false,
+    }
+  }
+
+  pub fn score(&self) -> u32 {
+    self.score
+  }
+
+  pub fn level(&self) -> u32 {
+    self.level
+  }
+
+  pub fn lines(&self) -> u32 {
+    self.lines
+  }
+
+  pub fn combo(&self) -> i32 {
+    self.combo
+  }
+
+  pub fn back_to_back(&self) -> bool {
+    self.back_to_back
+  }
+
+  /// Awards points for the amount of cells a piece was soft dropped.
+  pub fn award_soft_drop(&mut self, cells: u32) {
+    self.score += cells * Self::SOFT_DROP_POINTS;
+  }
+
+  /// Awards points for the amount of cells a piece was hard dropped.
+  pub fn award_hard_drop(&mut self, cells: u32) {
+    self.score += cells * Self::HARD_DROP_POINTS;
+  }
+
+  /// Awards points for clearing the given amount of lines at once, returning the points awarded.
+  ///
+  /// T-spins award bonus points, even when no lines are cleared.
+  /// A tetris or T-spin clear following another one is worth 1.5 times as much,
+  /// and every clear after the first in an unbroken chain of clearing drops awards a combo bonus.
+  /// Points are multiplied by the level the lines were cleared on.
+  /// The level increases every [`LINES_PER_LEVEL`](Scoring::LINES_PER_LEVEL) lines.
+  pub fn award_clear(&mut self, lines_cleared: usize, t_spin: Option<TSpin>) -> u32 {
+    let base_points = match (t_spin, lines_cleared) {
+      (None, 0) => 0,
+      (None, 1) => 100,
+      (None, 2) => 300,
+      (None, 3) => 500,
+      (None, _) => 800,
+
+      (Some(TSpin::Mini), 0) => 100,
+      (Some(TSpin::Mini), 1) => 200,
+      (Some(TSpin::Mini), _) => 400,
+
+      (Some(TSpin::Full), 0) => 400,
+      (Some(TSpin::Full), 1) => 800,
+      (Some(TSpin::Full), 2) => 1200,
+      (Some(TSpin::Full), _) => 1600,
+    };
+    let mut base_points = base_points;
+
+    if lines_cleared == 0 {
+      self.combo = -1;
+    } else {
+      let difficult_clear = lines_cleared >= 4 || t_spin.is_some();
+
+      if difficult_clear && self.back_to_back {
+        base_points = base_points * 3 / 2;
+      }
+
+      self.combo += 1;
+      self.back_to_back = difficult_clear;
+    }
+
+    let combo_points = Self::COMBO_POINTS * self.combo.max(0) as u32;
+    let points = (base_points + combo_points) * self.level;
+
+    self.score += points;
+    self.lines += lines_cleared as u32;
+    self.level = (self.lines / Self::LINES_PER_LEVEL) + 1;
+
+    points
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn drops_award_points_per_cell() {
+    let mut scoring = Scoring::new();
+
+    scoring.award_soft_drop(3);
+    scoring.award_hard_drop(10);
+
+    assert_eq!(scoring.score(), 3 + 20);
+  }
+
+  #[test]
+  fn clears_award_points_scaled_by_level() {
+    let mut scoring = Scoring::new();
+
+    // Drops that clear nothing are placed between each clear to avoid any combos.
+    assert_eq!(scoring.award_clear(4, None), 800);
+    scoring.award_clear(0, None);
+    assert_eq!(scoring.award_clear(3, None), 500);
+    scoring.award_clear(0, None);
+    assert_eq!(scoring.award_clear(3, None), 500);
+    scoring.award_clear(0, None);
+    // 10 lines have been cleared, moving to level 2.
+    assert_eq!(scoring.level(), 2);
+    assert_eq!(scoring.award_clear(1, None), 200);
+
+    assert_eq!(scoring.lines(), 11);
+    assert_eq!(scoring.score(), 800 + 500 + 500 + 200);
+  }
+
+  #[test]
+  fn t_spins_award_bonus_points() {
+    let mut scoring = Scoring::new();
+
+    assert_eq!(scoring.award_clear(0, Some(TSpin::Mini)), 100);
+    assert_eq!(scoring.award_clear(0, Some(TSpin::Full)), 400);
+    assert_eq!(scoring.award_clear(1, Some(TSpin::Mini)), 200);
+    scoring.award_clear(0, None);
+    // Back to back with the T-spin mini.
+    assert_eq!(scoring.award_clear(3, Some(TSpin::Full)), 2400);
+
+    assert_eq!(scoring.lines(), 4);
+  }
+
+  #[test]
+  fn consecutive_clears_build_a_combo() {
+    let mut scoring = Scoring::new();
+
+    assert_eq!(scoring.award_clear(1, None), 100);
+    assert_eq!(scoring.award_clear(1, None), 100 + 50);
+    assert_eq!(scoring.award_clear(2, None), 300 + 100);
+    assert_eq!(scoring.combo(), 2);
+
+    scoring.award_clear(0, None);
+
+    assert_eq!(scoring.combo(), -1);
+    assert_eq!(scoring.award_clear(1, None), 100);
+  }
+
+  #[test]
+  fn consecutive_tetrises_award_the_back_to_back_bonus() {
+    let mut scoring = Scoring::new();
+
+    assert_eq!(scoring.award_clear(4, None), 800);
+    assert!(scoring.back_to_back());
+
+    scoring.award_clear(0, None);
+
+    // Drops that clear nothing don't break the chain.
+    assert!(scoring.back_to_back());
+    assert_eq!(scoring.award_clear(4, None), 1200);
+
+    scoring.award_clear(0, None);
+    scoring.award_clear(1, None);
+
+    assert!(!scoring.back_to_back());
+    scoring.award_clear(0, None);
+    assert_eq!(scoring.award_clear(4, None), 800);
+  }
+
+  #[test]
+  fn no_lines_awards_nothing() {
+    let mut scoring = Scoring::new();
+
+    assert_eq!(scoring.award_clear(0, None), 0);
+    assert_eq!(scoring, Scoring::new());
+  }
+}
@@ -0,0 +1,114 @@
+use super::minos::MinoType;
+use crate::general_data::rng::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Hands out pieces using the 7-bag system.
+///
+/// Every piece type is placed into a bag and shuffled, then handed out one at a time.
+/// Once the bag is empty, a new one is filled and shuffled.
+/// This guarantees every piece shows up once every 7 pieces.
+///
+/// Bags are shuffled with a seeded [`Rng`](crate::general_data::rng::Rng), so two bags with the same seed
+/// hand out the exact same sequence of pieces.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SevenBag {
+  queue: VecDeque<MinoType>,
+  rng: Rng,
+}
+
+impl SevenBag {
+  pub fn new(seed: u64) -> Self {
+    Self {
+      queue: VecDeque::with_capacity(MinoType::ALL.len() * 2),
+      rng: Rng::from_seed(seed),
+    }
+  }
+
+  /// Takes the next piece out of the bag, refilling it if it was empty.
+  pub fn next_piece(&mut self) -> MinoType {
+    if self.queue.is_empty() {
+      self.refill();
+    }
+
+    // The queue was just refilled, so it can't be empty.
+    self.queue.pop_front().unwrap_or(MinoType::I)
+  }
+
+  /// Returns the next pieces that will be taken out of the bag, in order, without taking them.
+  ///
+  /// Pieces past the current bag are the ones the following bags will be filled with.
+  pub fn peek(&self, count: usize) -> Vec<MinoType> {
+    let mut pieces: Vec<MinoType> = self.queue.iter().take(count).copied().collect();
+    let mut rng = self.rng.clone();
+
+    while pieces.len() < count {
+      let remaining_count = count - pieces.len();
+
+      pieces.extend(
+        Self::shuffled_pieces(&mut rng)
+          .into_iter()
+          .take(remaining_count),
+      );
+    }
+
+    pieces
+  }
+
+  /// Adds a shuffled set of every piece to the back of the queue.
+  fn refill(&mut self) {
+    let pieces = Self::shuffled_pieces(&mut self.rng);
+
+    self.queue.extend(pieces);
+  }
+
+  fn shuffled_pieces(rng: &mut Rng) -> [MinoType; 7] {
+    let mut pieces = MinoType::ALL;
+
+    rng.shuffle(&mut pieces);
+
+    pieces
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn every_bag_contains_each_piece_once() {
+    let mut bag = SevenBag::new(0);
+
+    for _ in 0..3 {
+      let mut pieces: Vec<MinoType> = (0..7).map(|_| bag.next_piece()).collect();
+      pieces.sort_by_key(|piece| *piece as u8);
+
+      assert_eq!(pieces, MinoType::ALL);
+    }
+  }
+
+  #[test]
+  fn peek_matches_the_pieces_taken_afterwards() {
+    let mut bag = SevenBag::new(7);
+    // Take a few pieces so the peek has to look past the current bag.
+    (0..4).for_each(|_| {
+      bag.next_piece();
+    });
+
+    let peeked_pieces = bag.peek(10);
+    let taken_pieces: Vec<MinoType> = (0..10).map(|_| bag.next_piece()).collect();
+
+    assert_eq!(peeked_pieces, taken_pieces);
+  }
+
+  #[test]
+  fn same_seed_yields_the_same_pieces() {
+    let mut first_bag = SevenBag::new(42);
+    let mut second_bag = SevenBag::new(42);
+
+    let first_pieces: Vec<MinoType> = (0..21).map(|_| first_bag.next_piece()).collect();
+    let second_pieces: Vec<MinoType> = (0..21).map(|_| second_bag.next_piece()).collect();
+
+    assert_eq!(first_pieces, second_pieces);
+  }
+}
@@ -0,0 +1,135 @@
+use super::minos::MinoType;
+use super::scoring::TSpin;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Statistics kept across every game played in a session.
+///
+/// Unlike [`Scoring`](super::scoring::Scoring), the stats aren't reset when a new game starts.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+  /// How many of each piece type have been locked, in the order of [`MinoType::ALL`](MinoType::ALL).
+  pieces_placed: [u32; 7],
+  /// How many singles, doubles, triples, and tetrises have been cleared, in that order.
+  clears: [u32; 4],
+  /// How many pieces were locked with a T-spin, whether or not they cleared any lines.
+  t_spins: u32,
+  /// The longest chain of clearing drops, past the first clear.
+  max_combo: u32,
+  /// How long every finished game was played for in total.
+  play_time: Duration,
+}
+
+impl Stats {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Records a locked piece of the given type.
+  pub fn record_placement(&mut self, kind: MinoType) {
+    if let Some(pieces_placed) = self.pieces_placed.get_mut(kind.to_index() as usize) {
+      *pieces_placed += 1;
+    }
+  }
+
+  /// Records the lines cleared by a locked piece, along with the T-spin it was locked with and the combo it resulted in.
+  ///
+  /// Clearing more than four lines at once counts as a tetris.
+  pub fn record_clear(&mut self, lines_cleared: usize, t_spin: Option<TSpin>, combo: i32) {
+    if t_spin.is_some() {
+      self.t_spins += 1;
+    }
+
+    if lines_cleared > 0 {
+      self.clears[lines_cleared.min(self.clears.len()) - 1] += 1;
+    }
+
+    self.max_combo = self.max_combo.max(combo.max(0) as u32);
+  }
+
+  /// Adds the length of a finished game to the total play time.
+  pub fn add_play_time(&mut self, game_time: Duration) {
+    self.play_time += game_time;
+  }
+
+  /// Returns how many pieces of the given type have been locked.
+  pub fn pieces_placed(&self, kind: MinoType) -> u32 {
+    self
+      .pieces_placed
+      .get(kind.to_index() as usize)
+      .copied()
+      .unwrap_or_default()
+  }
+
+  pub fn total_pieces_placed(&self) -> u32 {
+    self.pieces_placed.iter().sum()
+  }
+
+  /// Returns how many times the given amount of lines were cleared at once, from 1 for singles up to 4 for tetrises.
+  ///
+  /// 0 is returned for any other amount of lines.
+  pub fn clears(&self, lines_cleared: usize) -> u32 {
+    lines_cleared
+      .checked_sub(1)
+      .and_then(|index| self.clears.get(index))
+      .copied()
+      .unwrap_or_default()
+  }
+
+  pub fn total_lines_cleared(&self) -> u32 {
+    (1..=self.clears.len())
+      .map(|lines_cleared| self.clears(lines_cleared) * lines_cleared as u32)
+      .sum()
+  }
+
+  pub fn t_spins(&self) -> u32 {
+    self.t_spins
+  }
+
+  pub fn max_combo(&self) -> u32 {
+    self.max_combo
+  }
+
+  pub fn play_time(&self) -> Duration {
+    self.play_time
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn placements_are_counted_per_piece_type() {
+    let mut stats = Stats::new();
+
+    stats.record_placement(MinoType::T);
+    stats.record_placement(MinoType::T);
+    stats.record_placement(MinoType::I);
+
+    assert_eq!(stats.pieces_placed(MinoType::T), 2);
+    assert_eq!(stats.pieces_placed(MinoType::I), 1);
+    assert_eq!(stats.pieces_placed(MinoType::O), 0);
+    assert_eq!(stats.total_pieces_placed(), 3);
+  }
+
+  #[test]
+  fn clears_are_counted_by_type() {
+    let mut stats = Stats::new();
+
+    stats.record_clear(0, None, -1);
+    stats.record_clear(1, None, 0);
+    stats.record_clear(4, None, 1);
+    stats.record_clear(2, Some(TSpin::Full), 2);
+    stats.record_clear(0, Some(TSpin::Mini), -1);
+
+    assert_eq!(stats.clears(1), 1);
+    assert_eq!(stats.clears(2), 1);
+    assert_eq!(stats.clears(3), 0);
+    assert_eq!(stats.clears(4), 1);
+    assert_eq!(stats.clears(0), 0);
+    assert_eq!(stats.total_lines_cleared(), 7);
+    assert_eq!(stats.t_spins(), 2);
+    assert_eq!(stats.max_combo(), 2);
+  }
+}
@@ -1,28 +1,93 @@
-use super::actions::{MenuAction, PlayerAction};
-use super::minos::MinoType;
+use super::actions::{GameAction, MenuAction, PlayerAction};
+use super::audio::{Audio, Sound};
+use super::effects::Effects;
+use super::game_mode::GameMode;
+use super::game_settings::{GameSettings, PresentMode};
+use super::game_snapshot::GameSnapshot;
+use super::hud::Hud;
+use super::kicks;
+use super::leaderboard::{Leaderboard, LeaderboardEntry};
+use super::minos::{ActivePiece, MinoType, Rotation};
+use super::particles::Particles;
+use super::scoring::{Scoring, TSpin};
+use super::seven_bag::SevenBag;
+use super::stats::Stats;
 use crate::asset_loader::Assets;
 use crate::game::world_state::*;
+use crate::general_data::rng::Rng;
+use crate::general_data::timer::Timer;
 use crate::menus::menu_data::*;
+use crate::menus::templates::game_mode::GameModeMenu;
+use crate::menus::templates::game_over::GameOverMenu;
 use crate::menus::templates::game_settings::Settings;
 use crate::menus::templates::main_menu::*;
+use crate::menus::templates::pause_menu::PauseMenu;
+use crate::menus::theme::Theme;
+use crate::renderer::color::Color;
+use crate::renderer::fonts::TextBox;
 use crate::renderer::Renderer;
-use crate::rustris_config::RENDERED_WINDOW_DIMENSIONS;
 use anyhow::anyhow;
 use maplit::hashmap;
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
 use winit::dpi::*;
 
 #[allow(unused)]
 #[derive(Debug)]
 pub struct WorldData {
   current_state: WorldState,
+  game_mode: GameMode,
+  /// Set when the game ended by reaching the goal of its mode, rather than topping out.
+  mode_completed: bool,
 
   held: Option<MinoType>,
+  /// Set once the active piece has been swapped with the held piece, until the next piece locks.
+  hold_locked: bool,
+  active_piece: Option<ActivePiece>,
+  bag: SevenBag,
+  /// The seed the bag of the current game was created with.
+  seed: u64,
+  scoring: Scoring,
+  /// Kept across every game, never reset.
+  stats: Stats,
+  leaderboard: Leaderboard,
+  /// The name typed so far for a score that made it onto the leaderboard.
+  ///
+  /// Some while the game over screen is asking for a name.
+  name_entry: Option<String>,
+  /// Kept behind a RefCell so its text boxes can be updated while rendering.
+  hud: RefCell<Hud>,
+  /// Set when the stack reaches the top of the board.
+  game_over: bool,
+  /// The amount of placements left to clear back under the top of the board before the game ends.
+  ///
+  /// None when the board isn't topped out.
+  top_out_grace: Option<u32>,
+  /// How many times the lock delay has been restarted for the active piece.
+  lock_resets: u32,
+  /// The horizontal direction currently held, -1 for left and 1 for right.
+  held_direction: Option<i32>,
+  /// Either [`MenuAction::Up`](MenuAction) or [`MenuAction::Down`](MenuAction) while it's held in a menu.
+  held_menu_direction: Option<MenuAction>,
+  /// The T-spin the last rotation of the active piece resulted in.
+  ///
+  /// Cleared whenever the active piece moves.
+  t_spin: Option<TSpin>,
   /// Contains the list of filled squares and the piece that occupies them.
   board: Vec<Option<MinoType>>,
 
   current_menu: Option<&'static str>,
+  /// The menus that were open before the current one, with the most recent last.
+  menu_stack: Vec<&'static str>,
   menus: HashMap<&'static str, Menu>,
+
+  timers: HashMap<&'static str, Timer>,
+  settings: GameSettings,
+  audio: Audio,
+  effects: Effects,
+  particles: Particles,
 }
 
 impl WorldData {
@@ -30,32 +95,105 @@ impl WorldData {
   pub const LOGICAL_BOARD_HEIGHT: u32 = 40;
   pub const VISIBLE_BOARD_WIDTH: u32 = 10;
   pub const VISIBLE_BOARD_HEIGHT: u32 = 20;
+  /// The rows above the visible part of the board.
+  pub const HIDDEN_BOARD_HEIGHT: u32 = Self::LOGICAL_BOARD_HEIGHT - Self::VISIBLE_BOARD_HEIGHT;
+  /// How far the game is blurred behind the pause menu, in pixels.
+  const PAUSE_BLUR_RADIUS: u32 = 2;
+  /// The text shown over the board during the countdown before a game, in order.
+  const COUNTDOWN_STEPS: [&'static str; 4] = ["3", "2", "1", "Go!"];
+  /// The color drawn over the board once the game is over.
+  const GAME_OVER_DIM_COLOR: [u8; 4] = [0, 0, 0, 0xAA];
+  /// The color behind the empty cells of the visible board.
+  const PLAYFIELD_COLOR: [u8; 4] = [0x10, 0x10, 0x18, 255];
+  /// The gap in pixels between the edge of the next queue box and the pieces within it.
+  const NEXT_QUEUE_PADDING: u32 = 1;
+  /// The color a held piece is drawn with when it can't be swapped back in yet.
+  const HOLD_LOCKED_COLOR: [u8; 4] = [0x80, 0x80, 0x80, 255];
+  /// The size in pixels of the label at the top of the hold box.
+  const HOLD_LABEL_SIZE: u32 = 8;
+  /// The size of the session stats on the game over screen, relative to the theme's text size.
+  const STATS_TEXT_SCALE: f32 = 0.6;
+  /// The color of the line drawn along the top of the visible board.
+  const VISIBLE_BOUNDARY_COLOR: [u8; 4] = [255, 255, 255, 0x44];
+  /// Where new pieces are placed, putting them in the two rows right above the visible part of the board.
+  pub const SPAWN_ORIGIN: (i32, i32) = (3, Self::HIDDEN_BOARD_HEIGHT as i32 - 2);
+  /// The time in milliseconds it takes a piece to fall a single row on each level, starting at level 1.
+  ///
+  /// Levels past the end of the table use the last entry.
+  const GRAVITY_MS_PER_LEVEL: [u64; 15] = [
+    1000, 793, 618, 473, 355, 262, 190, 135, 94, 64, 43, 28, 18, 11, 7,
+  ];
+
+  /// Creates the world, with the pieces of the first game handed out in an order decided by the seed.
+  pub fn new(settings: GameSettings, seed: u64) -> Self {
+    let mut general_settings_menu = Settings::general_settings_menu();
+    general_settings_menu.set_slider_value("fps", settings.fps());
+    general_settings_menu.set_toggle_value("grid", settings.show_grid());
+    general_settings_menu.set_toggle_value("mute", settings.muted());
+    general_settings_menu.set_toggle_value("fullscreen", settings.fullscreen());
+    general_settings_menu.set_toggle_value("vsync", settings.present_mode() == PresentMode::Vsync);
+    general_settings_menu.set_toggle_value("ghost", settings.ghost_enabled());
+    general_settings_menu.set_slider_value("ghost_alpha", settings.ghost_alpha() as u32);
 
-  #[allow(clippy::new_without_default)]
-  pub fn new() -> Self {
     let menus = hashmap! {
       MainMenu::MENU_NAME => MainMenu::new_menu(),
-      Settings::GENERAL_SETTINGS_NAME => Settings::general_settings_menu(),
+      Settings::GENERAL_SETTINGS_NAME => general_settings_menu,
       Settings::GAME_CONTROLS_NAME => Settings::game_controls_menu(),
       Settings::MENU_CONTROLS_NAME => Settings::menu_controls_menu(),
+      GameOverMenu::MENU_NAME => GameOverMenu::new_menu(),
+      GameModeMenu::MENU_NAME => GameModeMenu::new_menu(),
+      PauseMenu::MENU_NAME => PauseMenu::new_menu(),
     };
 
     Self {
       current_state: WorldState::Menu,
+      game_mode: GameMode::default(),
+      mode_completed: false,
 
       held: None,
+      hold_locked: false,
+      active_piece: None,
+      bag: SevenBag::new(seed),
+      seed,
+      scoring: Scoring::new(),
+      stats: Stats::new(),
+      leaderboard: Leaderboard::new(),
+      name_entry: None,
+      hud: RefCell::new(Hud::new()),
+      game_over: false,
+      top_out_grace: None,
+      lock_resets: 0,
+      held_direction: None,
+      held_menu_direction: None,
+      t_spin: None,
       board: vec![None; Self::LOGICAL_BOARD_WIDTH as usize * Self::LOGICAL_BOARD_HEIGHT as usize],
 
       current_menu: Some(MainMenu::MENU_NAME),
+      menu_stack: Vec::new(),
       menus,
+
+      timers: HashMap::new(),
+      audio: Audio::new(settings.muted()),
+      effects: Effects::new(),
+      particles: Particles::new(settings.max_particles(), seed),
+      settings,
     }
   }
 
   /// True is returned when a request to close the program was made.
   pub fn update_world(&mut self, player_action: Option<PlayerAction>) -> anyhow::Result<bool> {
+    let player_action = match (self.current_state, player_action) {
+      (WorldState::Menu | WorldState::GameOver, None) => {
+        self.menu_repeat_action().map(PlayerAction::MenuAction)
+      }
+      (_, player_action) => player_action,
+    };
+
     match self.current_state {
       WorldState::Menu => return self.update_menu(player_action),
+      WorldState::Countdown => self.update_countdown(player_action)?,
       WorldState::Game => self.update_game(player_action)?,
+      WorldState::GameOver => self.update_game_over(player_action)?,
     };
 
     Ok(false)
@@ -69,157 +207,4521 @@ impl WorldData {
 
     let current_menu = self.current_menu_mut()?;
 
+    if player_action == MenuAction::Back {
+      if current_menu.name() == PauseMenu::MENU_NAME {
+        self.resume_game()?;
+
+        return Ok(false);
+      }
+
+      return Ok(self.pop_menu());
+    }
+
     match current_menu.name() {
-      "main_menu" => match player_action {
+      MainMenu::MENU_NAME => match player_action {
+        MenuAction::Up => current_menu.previous(),
+        MenuAction::Down => current_menu.next(),
+        MenuAction::Select => match Self::selected_option_name(current_menu)? {
+          "start" => self.push_menu(GameModeMenu::MENU_NAME),
+          "options" => self.push_menu(Settings::GENERAL_SETTINGS_NAME),
+          "exit" => return Ok(true),
+          _ => (),
+        },
+        _ => (),
+      },
+
+      GameModeMenu::MENU_NAME => match player_action {
         MenuAction::Up => current_menu.previous(),
         MenuAction::Down => current_menu.next(),
         MenuAction::Select => {
-          let Some(current_option) = current_menu.current_option() else {
-            return Err(anyhow!(
-              "The current menu, `{}`, has no options.",
-              current_menu.name()
-            ));
+          let game_mode = match Self::selected_option_name(current_menu)? {
+            "sprint" => GameMode::Sprint,
+            "ultra" => GameMode::Ultra,
+            _ => GameMode::Marathon,
           };
 
-          match current_option.name() {
-            "start" => self.update_state(WorldState::Game),
-            "options" => self.current_menu = Some("options_menu"),
-            "exit" => return Ok(true),
-            _ => (),
+          self.game_mode = game_mode;
+          self.pop_menu();
+          self.start_countdown()?;
+        }
+        _ => (),
+      },
+
+      Settings::GENERAL_SETTINGS_NAME => match player_action {
+        MenuAction::Up => current_menu.previous(),
+        MenuAction::Down => current_menu.next(),
+        MenuAction::Left | MenuAction::Right => {
+          if let Some(value) = current_menu.adjust_slider(player_action == MenuAction::Right) {
+            match Self::selected_option_name(current_menu)? {
+              "fps" => self.settings.set_fps(value),
+              "ghost_alpha" => self
+                .settings
+                .set_ghost_alpha(value.min(u8::MAX as u32) as u8),
+              _ => (),
+            }
+          } else if let Some(value) = current_menu.flip_toggle() {
+            let option_name = Self::selected_option_name(current_menu)?;
+
+            self.apply_toggled_setting(option_name, value);
           }
         }
+        MenuAction::Select => match Self::selected_option_name(current_menu)? {
+          option_name @ ("grid" | "mute" | "fullscreen" | "vsync" | "ghost") => {
+            if let Some(value) = current_menu.flip_toggle() {
+              self.apply_toggled_setting(option_name, value);
+            }
+          }
+          "palette" => {
+            let palette = self.settings.palette().next();
+
+            self.settings.set_palette(palette);
+          }
+          "game_controls" => self.push_menu(Settings::GAME_CONTROLS_NAME),
+          "menu_controls" => self.push_menu(Settings::MENU_CONTROLS_NAME),
+          _ => (),
+        },
         _ => (),
       },
 
-      "options_menu" => {
-        todo!()
-      }
+      Settings::GAME_CONTROLS_NAME | Settings::MENU_CONTROLS_NAME => match player_action {
+        MenuAction::Up => current_menu.previous(),
+        MenuAction::Down => current_menu.next(),
+        _ => (),
+      },
 
-      "pause_menu" => {
-        todo!()
-      }
+      PauseMenu::MENU_NAME => match player_action {
+        MenuAction::Up => current_menu.previous(),
+        MenuAction::Down => current_menu.next(),
+        MenuAction::Select => match Self::selected_option_name(current_menu)? {
+          "resume" => self.resume_game()?,
+          "quit" => {
+            self
+              .stats
+              .add_play_time(self.game_time().unwrap_or_default());
+
+            self.quit_to_menu()?;
+          }
+          _ => (),
+        },
+        _ => (),
+      },
       _ => (),
     }
 
     Ok(false)
   }
 
-  fn update_game(&mut self, _player_action: Option<PlayerAction>) -> anyhow::Result<()> {
-    Ok(())
+  /// Switches the fullscreen setting, keeping its toggle in the settings menu in sync.
+  ///
+  /// Returns true if the window should now be fullscreen.
+  pub fn toggle_fullscreen(&mut self) -> bool {
+    let fullscreen = self.settings.toggle_fullscreen();
+
+    if let Some(settings_menu) = self.menus.get_mut(Settings::GENERAL_SETTINGS_NAME) {
+      settings_menu.set_toggle_value("fullscreen", fullscreen);
+    }
+
+    fullscreen
   }
 
-  pub fn render(&self, assets: &Assets, renderer: &mut Renderer) -> anyhow::Result<()> {
-    match self.current_state {
-      WorldState::Menu => {
-        let current_menu_name = self.current_menu.unwrap_or("main_menu");
+  /// Writes the new value of a toggle in the settings menu through to the setting it switches.
+  fn apply_toggled_setting(&mut self, option_name: &str, value: bool) {
+    match option_name {
+      "grid" => self.settings.set_show_grid(value),
+      "mute" => self.set_muted(value),
+      "fullscreen" => self.settings.set_fullscreen(value),
+      "vsync" => self.settings.set_present_mode(if value {
+        PresentMode::Vsync
+      } else {
+        PresentMode::Immediate
+      }),
+      "ghost" => self.settings.set_ghost_enabled(value),
+      _ => (),
+    }
+  }
 
-        match current_menu_name {
-          "main_menu" => self.render_main_menu(assets, renderer)?,
-          "options" => self.render_options(renderer)?,
-          "pause_menu" => {
-            self.render_game(renderer)?;
+  /// Opens the given menu, remembering the current one so it can be returned to with [`pop_menu`](WorldData::pop_menu).
+  fn push_menu(&mut self, menu_name: &'static str) {
+    if let Some(current_menu) = self.current_menu.replace(menu_name) {
+      self.menu_stack.push(current_menu);
+    }
+  }
 
-            renderer.apply_color([0, 0, 0, 0x77])?;
+  /// Returns to the menu that was open before the current one.
+  ///
+  /// With no previous menu, nothing happens unless [`back_exits_main_menu`](GameSettings::back_exits_main_menu)
+  /// is set, in which case true is returned to request closing the program.
+  fn pop_menu(&mut self) -> bool {
+    match self.menu_stack.pop() {
+      Some(previous_menu) => {
+        self.current_menu = Some(previous_menu);
 
-            self.render_pause_screen(renderer)?;
-          }
-          _ => return Err(anyhow!("Unknown menu.")),
+        false
+      }
+      None => self.settings.back_exits_main_menu(),
+    }
+  }
+
+  /// Returns the name of the option currently selected in the menu.
+  fn selected_option_name(menu: &Menu) -> anyhow::Result<&'static str> {
+    let Some(current_option) = menu.current_option() else {
+      return Err(anyhow!(
+        "The current menu, `{}`, has no options.",
+        menu.name()
+      ));
+    };
+
+    Ok(current_option.name())
+  }
+
+  fn update_game(&mut self, player_action: Option<PlayerAction>) -> anyhow::Result<()> {
+    if self.game_over {
+      return self.enter_game_over();
+    }
+
+    let mut game_actions = match player_action {
+      Some(PlayerAction::GameAction(game_actions)) => game_actions,
+      _ => Vec::new(),
+    };
+    game_actions.extend(self.auto_shift_actions());
+    game_actions.sort_by_key(GameAction::resolution_order);
+
+    for game_action in game_actions {
+      match game_action {
+        GameAction::RotateCw => {
+          self.try_rotate(true);
+        }
+        GameAction::RotateCcw => {
+          self.try_rotate(false);
         }
+        GameAction::Rotate180 => {
+          self.try_rotate_180();
+        }
+        GameAction::MoveLeft => self.move_horizontally(-1),
+        GameAction::MoveRight => self.move_horizontally(1),
+        GameAction::SoftDrop => self.soft_drop(),
+        GameAction::HardDrop => self.hard_drop(),
+        GameAction::Hold => self.hold_piece(),
+        GameAction::Pause => return self.pause_game(),
+        GameAction::QuitToMenu => {
+          self
+            .stats
+            .add_play_time(self.game_time().unwrap_or_default());
+
+          return self.quit_to_menu();
+        }
+        _ => (),
       }
+    }
 
-      WorldState::Game => self.render_game(renderer)?,
+    self.apply_gravity();
+    self.particles.update();
+
+    if self.mode_goal_reached() {
+      log::info!("Finished the {:?} game.", self.game_mode);
+
+      self.game_over = true;
+      self.mode_completed = true;
+      self.game_clock().pause();
+
+      return self.enter_game_over();
     }
 
     Ok(())
   }
 
-  fn render_game(&self, _renderer: &mut Renderer) -> anyhow::Result<()> {
-    todo!()
+  /// Freezes the game behind the pause menu, with its first option selected.
+  ///
+  /// Every running timer is paused, so no time passes in the game until it's resumed.
+  fn pause_game(&mut self) -> anyhow::Result<()> {
+    self.transition(WorldState::Menu)?;
+    self.timers.values().for_each(Timer::pause);
+
+    self
+      .menus
+      .insert(PauseMenu::MENU_NAME, PauseMenu::new_menu());
+    self.push_menu(PauseMenu::MENU_NAME);
+
+    Ok(())
+  }
+
+  /// Closes the pause menu and continues the game from where it was paused.
+  fn resume_game(&mut self) -> anyhow::Result<()> {
+    self.pop_menu();
+    self.transition(WorldState::Game)?;
+    self.timers.values().for_each(Timer::resume);
+
+    Ok(())
   }
 
-  #[allow(unused_labels)]
-  fn render_main_menu(&self, assets: &Assets, renderer: &mut Renderer) -> anyhow::Result<()> {
-    // 'draw_background: {
-    let pixel_buffer = renderer.frame_mut();
-    let buffer_dimensions = RENDERED_WINDOW_DIMENSIONS;
-    let pixel_count = buffer_dimensions.width * buffer_dimensions.height;
+  /// Spawns the first piece and starts counting down to the start of the game.
+  ///
+  /// With no countdown set, the game starts right away.
+  fn start_countdown(&mut self) -> anyhow::Result<()> {
+    self.spawn_next_piece();
 
-    for index in 0..pixel_count {
-      let (x, y) = (
-        index % buffer_dimensions.width,
-        index / buffer_dimensions.height,
-      );
+    if self.settings.countdown_ms() == 0 {
+      return self.transition(WorldState::Game);
+    }
 
-      let x_percentage = x as f64 / buffer_dimensions.width as f64;
-      let y_percentage = y as f64 / buffer_dimensions.height as f64;
+    self.transition(WorldState::Countdown)
+  }
 
-      let red = (255.0 * y_percentage).cast::<u8>();
-      let blue = (255.0 * x_percentage).cast::<u8>();
+  /// Waits for the countdown to finish, ignoring any input until then.
+  ///
+  /// The countdown starts on the first update, and the game starts on the same update the countdown finishes,
+  /// so input from that update is used.
+  fn update_countdown(&mut self, player_action: Option<PlayerAction>) -> anyhow::Result<()> {
+    let countdown_timer = self.countdown_timer();
 
-      Renderer::draw_at_pixel_with_rgb(pixel_buffer, index as usize, &[red, 0, blue])?;
+    if !countdown_timer.running() {
+      countdown_timer.start();
     }
-    // }
 
-    let menu_position = LogicalPosition {
-      x: 0,
-      y: (RENDERED_WINDOW_DIMENSIONS.height as f32 * 0.25).cast::<i32>(),
-    };
-    let option_spacing = 20; // pixels.
+    if !countdown_timer.is_finished() {
+      return Ok(());
+    }
 
-    let current_menu = self.current_menu()?;
+    countdown_timer.stop();
+    self.transition(WorldState::Game)?;
 
-    current_menu.render(assets, &menu_position, renderer, option_spacing)
+    self.update_game(player_action)
   }
 
-  fn render_options(&self, _renderer: &mut Renderer) -> anyhow::Result<()> {
-    todo!()
+  /// Returns the timer counting down to the start of the game, with its duration matching the current settings.
+  fn countdown_timer(&mut self) -> &Timer {
+    let countdown = Duration::from_millis(self.settings.countdown_ms());
+    let countdown_timer = self.get_or_init_timer("countdown", countdown);
+    countdown_timer.set_duration(countdown);
+
+    countdown_timer
   }
 
-  fn render_pause_screen(&self, _renderer: &mut Renderer) -> anyhow::Result<()> {
-    todo!()
+  /// Returns true once the game has reached the goal of its mode,
+  /// starting the game clock if it isn't running yet.
+  fn mode_goal_reached(&mut self) -> bool {
+    let game_clock = self.game_clock();
+
+    if !game_clock.running() {
+      game_clock.start();
+    }
+
+    let out_of_time = game_clock.remaining() == Some(Duration::ZERO);
+    let line_goal_reached = self
+      .game_mode
+      .line_goal()
+      .is_some_and(|line_goal| self.scoring.lines() >= line_goal);
+
+    out_of_time || line_goal_reached
   }
 
-  pub fn world_state(&self) -> WorldState {
-    self.current_state
+  /// Returns the timer counting how long the current game has been played for.
+  ///
+  /// The timer finishes when the mode's time limit runs out, and never finishes in modes without one.
+  fn game_clock(&mut self) -> &Timer {
+    let time_limit = self.game_mode.time_limit().unwrap_or(Duration::MAX);
+
+    self.get_or_init_timer("game_clock", time_limit)
   }
 
-  fn update_state(&mut self, new_state: WorldState) {
-    self.current_state = new_state;
+  /// Returns how long the current game has been played for, or None if it hasn't started.
+  pub fn game_time(&self) -> Option<Duration> {
+    self.timers.get("game_clock")?.elapsed()
   }
 
-  /// Returns a reference to the currently selected menu.
+  /// Updates which of left or right is being held down, restarting the DAS delay when it changes.
   ///
-  /// # Errors
+  /// Holding both directions at once is treated as holding neither.
+  pub fn update_held_actions(&mut self, held_actions: &[GameAction]) {
+    let held_direction = match (
+      held_actions.contains(&GameAction::MoveLeft),
+      held_actions.contains(&GameAction::MoveRight),
+    ) {
+      (true, false) => Some(-1),
+      (false, true) => Some(1),
+      _ => None,
+    };
+
+    if held_direction == self.held_direction {
+      return;
+    }
+
+    self.held_direction = held_direction;
+    self.arr_timer().stop();
+
+    if held_direction.is_some() {
+      self.das_timer().start();
+    } else {
+      self.das_timer().stop();
+    }
+  }
+
+  /// Returns the moves produced by holding left or right.
   ///
-  /// - When there is no selected menu.
-  /// - When the selected menu doesn't exist in the list of menus.
-  fn current_menu(&self) -> anyhow::Result<&Menu> {
-    let Some(current_menu_name) = self.current_menu else {
-      return Err(anyhow!(
-        "Attempted to get the current menu when there wasn't one."
-      ));
+  /// A single move is produced once the DAS delay passes, then another every time the ARR interval passes.
+  fn auto_shift_actions(&mut self) -> Vec<GameAction> {
+    let Some(held_direction) = self.held_direction else {
+      return Vec::new();
     };
-    match self.menus.get(current_menu_name) {
-      Some(menu) => Ok(menu),
-      None => Err(anyhow!("Currently selected menu does not exist.")),
+    let move_action = if held_direction < 0 {
+      GameAction::MoveLeft
+    } else {
+      GameAction::MoveRight
+    };
+
+    if self.das_timer().is_finished() {
+      self.das_timer().stop();
+      self.arr_timer().start();
+
+      return vec![move_action];
+    }
+
+    if !self.arr_timer().is_finished() {
+      return Vec::new();
+    }
+
+    if self.settings.arr_ms() == 0 {
+      return vec![move_action; Self::LOGICAL_BOARD_WIDTH as usize];
     }
+
+    vec![move_action]
   }
 
-  /// Returns a mutable reference to the currently selected menu.
+  /// Updates which of up or down is being held down in a menu, restarting the menu DAS delay when it changes.
   ///
-  /// # Errors
+  /// Holding both directions at once is treated as holding neither.
+  pub fn update_held_menu_actions(&mut self, held_actions: &[MenuAction]) {
+    let held_menu_direction = match (
+      held_actions.contains(&MenuAction::Up),
+      held_actions.contains(&MenuAction::Down),
+    ) {
+      (true, false) => Some(MenuAction::Up),
+      (false, true) => Some(MenuAction::Down),
+      _ => None,
+    };
+
+    if held_menu_direction == self.held_menu_direction {
+      return;
+    }
+
+    self.held_menu_direction = held_menu_direction;
+    self.menu_arr_timer().stop();
+
+    if self.held_menu_direction.is_some() {
+      self.menu_das_timer().start();
+    } else {
+      self.menu_das_timer().stop();
+    }
+  }
+
+  /// Returns the cursor move produced by holding up or down in a menu.
   ///
-  /// - When there is no selected menu.
-  /// - When the selected menu doesn't exist in the list of menus.
-  fn current_menu_mut(&mut self) -> anyhow::Result<&mut Menu> {
-    let Some(current_menu_name) = self.current_menu else {
-      return Err(anyhow!(
-        "Attempted to get the current menu when there wasn't one."
-      ));
+  /// A single move is produced once the menu DAS delay passes, then another every time the menu ARR interval passes.
+  fn menu_repeat_action(&mut self) -> Option<MenuAction> {
+    let held_menu_direction = self.held_menu_direction.clone()?;
+
+    if self.menu_das_timer().is_finished() {
+      self.menu_das_timer().stop();
+      self.menu_arr_timer().start();
+
+      return Some(held_menu_direction);
+    }
+
+    self
+      .menu_arr_timer()
+      .is_finished()
+      .then_some(held_menu_direction)
+  }
+
+  /// Returns the menu DAS timer, with its duration matching the current settings.
+  fn menu_das_timer(&mut self) -> &Timer {
+    let menu_das = Duration::from_millis(self.settings.menu_das_ms());
+    let menu_das_timer = self.get_or_init_timer("menu_das", menu_das);
+    menu_das_timer.set_duration(menu_das);
+
+    menu_das_timer
+  }
+
+  /// Returns the menu ARR timer, with its duration matching the current settings.
+  fn menu_arr_timer(&mut self) -> &Timer {
+    let menu_arr = Duration::from_millis(self.settings.menu_arr_ms());
+    let menu_arr_timer = self.get_or_init_timer("menu_arr", menu_arr);
+    menu_arr_timer.set_duration(menu_arr);
+
+    menu_arr_timer
+  }
+
+  /// Returns the DAS timer, with its duration matching the current settings.
+  fn das_timer(&mut self) -> &Timer {
+    let das = Duration::from_millis(self.settings.das_ms());
+    let das_timer = self.get_or_init_timer("das", das);
+    das_timer.set_duration(das);
+
+    das_timer
+  }
+
+  /// Returns the ARR timer, with its duration matching the current settings.
+  fn arr_timer(&mut self) -> &Timer {
+    let arr = Duration::from_millis(self.settings.arr_ms());
+    let arr_timer = self.get_or_init_timer("arr", arr);
+    arr_timer.set_duration(arr);
+
+    arr_timer
+  }
+
+  /// Moves to the game over screen, with its first option selected.
+  ///
+  /// If the score made it onto the leaderboard, a name is asked for before the options can be used.
+  fn enter_game_over(&mut self) -> anyhow::Result<()> {
+    self.transition(WorldState::GameOver)?;
+    self
+      .stats
+      .add_play_time(self.game_time().unwrap_or_default());
+    self.audio.play(Sound::GameOver);
+
+    if self.leaderboard.qualifies(self.scoring.score()) {
+      self.name_entry = Some(String::new());
+    }
+
+    self
+      .menus
+      .insert(GameOverMenu::MENU_NAME, GameOverMenu::new_menu());
+    self.push_menu(GameOverMenu::MENU_NAME);
+
+    Ok(())
+  }
+
+  /// Handles the options on the game over screen, either restarting the game or quitting to the main menu.
+  ///
+  /// Menu actions are ignored while a name is being entered, since their keys are typed into the name.
+  fn update_game_over(&mut self, player_action: Option<PlayerAction>) -> anyhow::Result<()> {
+    let Some(PlayerAction::MenuAction(player_action)) = player_action else {
+      return Ok(());
     };
-    match self.menus.get_mut(current_menu_name) {
-      Some(menu) => Ok(menu),
-      None => Err(anyhow!("Currently selected menu does not exist.")),
+
+    if self.is_entering_name() {
+      return Ok(());
+    }
+
+    let current_menu = self.current_menu_mut()?;
+
+    match player_action {
+      MenuAction::Up => current_menu.previous(),
+      MenuAction::Down => current_menu.next(),
+      MenuAction::Select => match Self::selected_option_name(current_menu)? {
+        "restart" => {
+          self.pop_menu();
+          self.reset_game();
+          self.start_countdown()?;
+        }
+        "quit" => self.quit_to_menu()?,
+        _ => (),
+      },
+      _ => (),
+    }
+
+    Ok(())
+  }
+
+  /// Returns true while the game over screen is asking for a name for the leaderboard.
+  pub fn is_entering_name(&self) -> bool {
+    self.name_entry.is_some()
+  }
+
+  /// Adds the character to the end of the name being entered.
+  ///
+  /// Only letters, digits, and punctuation are kept, up to [`MAX_NAME_LENGTH`](Leaderboard::MAX_NAME_LENGTH) of them.
+  pub fn type_name_character(&mut self, character: char) {
+    let Some(name) = &mut self.name_entry else {
+      return;
+    };
+
+    if character.is_ascii_graphic() && name.len() < Leaderboard::MAX_NAME_LENGTH {
+      name.push(character);
+    }
+  }
+
+  /// Removes the last character of the name being entered.
+  pub fn erase_name_character(&mut self) {
+    if let Some(name) = &mut self.name_entry {
+      name.pop();
+    }
+  }
+
+  /// Places the score of the finished game on the leaderboard under the entered name.
+  ///
+  /// Returns the index the score was placed at, or None if no name was being entered.
+  pub fn submit_name(&mut self) -> Option<usize> {
+    let name = self.name_entry.take()?;
+    let entry = LeaderboardEntry::new(&name, self.scoring.score(), self.game_mode);
+
+    self.leaderboard.insert(entry)
+  }
+
+  /// Clears the game and returns to the main menu, ready for a new game to be started from it.
+  ///
+  /// The game is already in the menu state when quitting from the pause menu.
+  fn quit_to_menu(&mut self) -> anyhow::Result<()> {
+    self.reset_game();
+
+    if self.current_state != WorldState::Menu {
+      self.transition(WorldState::Menu)?;
+    }
+
+    self.menu_stack.clear();
+    self.current_menu = Some(MainMenu::MENU_NAME);
+
+    Ok(())
+  }
+
+  /// Clears everything from the last game, leaving an empty board with a new bag and no active piece.
+  ///
+  /// The new bag is created with a seed derived from the last one,
+  /// so every game of a session started from the same seed hands out the same pieces.
+  fn reset_game(&mut self) {
+    self.board.fill(None);
+    self.held = None;
+    self.hold_locked = false;
+    self.active_piece = None;
+    self.seed = Rng::from_seed(self.seed).next_u64();
+    self.bag = SevenBag::new(self.seed);
+    self.scoring = Scoring::new();
+    self.game_over = false;
+    self.mode_completed = false;
+    self.top_out_grace = None;
+    self.lock_resets = 0;
+    self.held_direction = None;
+    self.t_spin = None;
+    self.timers.clear();
+    self.particles.clear();
+  }
+
+  /// Moves the active piece down a row each time the gravity timer finishes.
+  ///
+  /// Once the piece can't move down any further the lock delay starts, locking the piece when it finishes
+  /// if the piece is still resting on the stack.
+  fn apply_gravity(&mut self) {
+    if self.active_piece.is_none() {
+      return;
+    }
+
+    let gravity_duration = Self::gravity_duration(self.scoring.level());
+    let gravity_timer = self.get_or_init_timer("gravity", gravity_duration);
+    gravity_timer.set_duration(gravity_duration);
+
+    if !gravity_timer.running() {
+      gravity_timer.start();
+    }
+
+    if gravity_timer.is_finished() && !self.shift_active_piece(0, 1) {
+      if self.lock_resets > self.settings.lock_reset_cap() {
+        self.lock_active_piece();
+
+        return;
+      }
+
+      let lock_delay_timer = self.lock_delay_timer();
+
+      if !lock_delay_timer.running() {
+        lock_delay_timer.start();
+      }
+    }
+
+    let lock_delay_timer = self.lock_delay_timer();
+
+    if lock_delay_timer.is_finished() {
+      lock_delay_timer.stop();
+
+      if self.is_grounded() {
+        self.lock_active_piece();
+      }
+    }
+  }
+
+  /// Restarts the lock delay after the player moves or rotates a piece resting on the stack.
+  ///
+  /// Once the lock delay has been restarted more times than the [`lock reset cap`](GameSettings::lock_reset_cap)
+  /// allows, the piece locks if it's still resting on the stack instead.
+  fn reset_lock_delay(&mut self) {
+    if !self.lock_delay_timer().running() {
+      return;
+    }
+
+    self.lock_resets += 1;
+
+    if self.lock_resets <= self.settings.lock_reset_cap() {
+      self.lock_delay_timer().start();
+    } else if self.is_grounded() {
+      self.lock_active_piece();
+    } else {
+      self.lock_delay_timer().stop();
+    }
+  }
+
+  fn stop_lock_delay(&self) {
+    if let Some(lock_delay_timer) = self.timers.get("lock_delay") {
+      lock_delay_timer.stop();
+    }
+  }
+
+  /// Returns the lock delay timer, with its duration matching the current settings.
+  fn lock_delay_timer(&mut self) -> &Timer {
+    let lock_delay = Duration::from_millis(self.settings.lock_delay_ms());
+    let lock_delay_timer = self.get_or_init_timer("lock_delay", lock_delay);
+    lock_delay_timer.set_duration(lock_delay);
+
+    lock_delay_timer
+  }
+
+  /// Returns how long it takes a piece to fall a single row on the given level.
+  fn gravity_duration(level: u32) -> Duration {
+    let level_index = (level.max(1) as usize - 1).min(Self::GRAVITY_MS_PER_LEVEL.len() - 1);
+
+    Duration::from_millis(Self::GRAVITY_MS_PER_LEVEL[level_index])
+  }
+
+  /// Returns true if the active piece can't move down any further.
+  fn is_grounded(&self) -> bool {
+    let Some(active_piece) = self.active_piece else {
+      return false;
+    };
+    let (x, y) = active_piece.origin;
+
+    self.collides(active_piece.kind, active_piece.rotation, (x, y + 1))
+  }
+
+  /// Returns the timer with the given name, creating it with the given duration if it doesn't exist yet.
+  ///
+  /// Newly created timers aren't running.
+  pub fn get_or_init_timer(&mut self, name: &'static str, duration: Duration) -> &Timer {
+    self
+      .timers
+      .entry(name)
+      .or_insert_with(|| Timer::new(duration))
+  }
+
+  /// Moves the active piece sideways, restarting the lock delay if it moved.
+  fn move_horizontally(&mut self, x_offset: i32) {
+    if self.shift_active_piece(x_offset, 0) {
+      self.reset_lock_delay();
+    }
+  }
+
+  /// Moves the active piece down a single row, awarding points if it moved.
+  fn soft_drop(&mut self) {
+    if self.shift_active_piece(0, 1) {
+      self.scoring.award_soft_drop(1);
+    }
+  }
+
+  /// Drops the active piece straight down as far as it can go, then locks it in place.
+  ///
+  /// Nothing happens if there is no active piece.
+  pub fn hard_drop(&mut self) {
+    let Some(ghost_origin) = self.ghost_origin() else {
+      return;
+    };
+
+    if let Some(active_piece) = self.active_piece.as_mut() {
+      let dropped_cells = (ghost_origin.1 - active_piece.origin.1) as u32;
+
+      active_piece.origin = ghost_origin;
+      self.scoring.award_hard_drop(dropped_cells);
+    }
+
+    self.lock_active_piece();
+  }
+
+  /// Returns the origin the active piece would land on if it was hard dropped.
+  ///
+  /// None is returned if there is no active piece.
+  pub fn ghost_origin(&self) -> Option<(i32, i32)> {
+    let active_piece = self.active_piece?;
+    let mut origin = active_piece.origin;
+
+    while !self.collides(
+      active_piece.kind,
+      active_piece.rotation,
+      (origin.0, origin.1 + 1),
+    ) {
+      origin.1 += 1;
+    }
+
+    Some(origin)
+  }
+
+  /// Writes the active piece into the board, clears any completed lines, then spawns the next piece.
+  ///
+  /// If the piece locked entirely above the visible part of the board, the board is topped out.
+  /// The player then gets a set amount of placements to clear back under the top of the board,
+  /// with the game ending if the board is still topped out after them.
+  fn lock_active_piece(&mut self) {
+    let Some(active_piece) = self.active_piece.take() else {
+      return;
+    };
+
+    self.hold_locked = false;
+    self.stop_lock_delay();
+
+    let board_width = Self::LOGICAL_BOARD_WIDTH as i32;
+    let hidden_board_height = Self::HIDDEN_BOARD_HEIGHT as i32;
+
+    for (x, y) in active_piece.cells() {
+      if y < 0 {
+        continue;
+      }
+
+      self.board[(x + (y * board_width)) as usize] = Some(active_piece.kind);
+    }
+
+    let locked_out = active_piece
+      .cells()
+      .iter()
+      .all(|(_, y)| *y < hidden_board_height);
+
+    let lines_cleared = self.clear_lines();
+    let t_spin = self.t_spin.take();
+    self.scoring.award_clear(lines_cleared, t_spin);
+    self.stats.record_placement(active_piece.kind);
+    self
+      .stats
+      .record_clear(lines_cleared, t_spin, self.scoring.combo());
+
+    if lines_cleared >= 4 || (lines_cleared > 0 && t_spin.is_some()) {
+      self.effects.shake();
+    }
+
+    if lines_cleared > 0 {
+      self.audio.play(Sound::LineClear);
+    } else {
+      self.audio.play(Sound::Lock);
+    }
+
+    if (locked_out || self.top_out_grace.is_some()) && self.is_topped_out() {
+      let remaining_placements = self
+        .top_out_grace
+        .unwrap_or(self.settings.top_out_grace_placements());
+
+      if remaining_placements == 0 {
+        log::info!("Game over, the board is topped out.");
+
+        self.game_over = true;
+
+        return;
+      }
+
+      log::info!(
+        "The board topped out, {} placements left to recover.",
+        remaining_placements
+      );
+
+      self.top_out_grace = Some(remaining_placements - 1);
+    } else if self.top_out_grace.take().is_some() {
+      log::info!("Recovered from topping out.");
+    }
+
+    self.spawn_next_piece();
+  }
+
+  /// Returns true if any cell above the visible part of the board is filled.
+  fn is_topped_out(&self) -> bool {
+    let hidden_cell_count = (Self::HIDDEN_BOARD_HEIGHT * Self::LOGICAL_BOARD_WIDTH) as usize;
+
+    self.board[..hidden_cell_count].iter().any(Option::is_some)
+  }
+
+  /// Takes the next piece from the bag and places it at the spawn position.
+  fn spawn_next_piece(&mut self) {
+    let next_kind = self.bag.next_piece();
+
+    self.spawn_piece(next_kind);
+  }
+
+  /// Places a new piece of the given kind at the spawn position as the active piece.
+  ///
+  /// If the spawn position is blocked, the piece is moved up until it fits.
+  /// The game is over if the piece can't fit anywhere above the spawn position.
+  fn spawn_piece(&mut self, kind: MinoType) {
+    let (spawn_x, spawn_y) = Self::SPAWN_ORIGIN;
+
+    let spawn_origin = (0..=spawn_y)
+      .rev()
+      .map(|y| (spawn_x, y))
+      .find(|origin| !self.collides(kind, Rotation::Spawn, *origin));
+
+    let Some(spawn_origin) = spawn_origin else {
+      log::info!("Game over, the next piece collided when spawning.");
+
+      self.game_over = true;
+
+      return;
+    };
+
+    self.active_piece = Some(ActivePiece::new(kind, spawn_origin));
+    self.lock_resets = 0;
+  }
+
+  /// Swaps the active piece with the held piece, taking the next piece from the bag if nothing was held.
+  ///
+  /// The swapped in piece starts back at the spawn position.
+  /// Only one swap is allowed until the next piece locks.
+  fn hold_piece(&mut self) {
+    if self.hold_locked {
+      return;
+    }
+
+    let Some(active_piece) = self.active_piece.take() else {
+      return;
+    };
+
+    self.stop_lock_delay();
+    self.t_spin = None;
+    self.hold_locked = true;
+    self.audio.play(Sound::Hold);
+
+    match self.held.replace(active_piece.kind) {
+      Some(held_kind) => self.spawn_piece(held_kind),
+      None => self.spawn_next_piece(),
+    }
+  }
+
+  /// Moves the active piece by the given offset, as long as it wouldn't collide in its new position.
+  ///
+  /// Returns true if the piece was moved.
+  /// Nothing happens if there is no active piece.
+  fn shift_active_piece(&mut self, x_offset: i32, y_offset: i32) -> bool {
+    let Some(active_piece) = self.active_piece else {
+      return false;
+    };
+    let new_origin = (
+      active_piece.origin.0 + x_offset,
+      active_piece.origin.1 + y_offset,
+    );
+
+    if self.collides(active_piece.kind, active_piece.rotation, new_origin) {
+      return false;
+    }
+
+    if let Some(active_piece) = self.active_piece.as_mut() {
+      active_piece.origin = new_origin;
+    }
+
+    self.t_spin = None;
+
+    true
+  }
+
+  /// Removes every full row from the board, shifting the rows above them down to fill the gap.
+  ///
+  /// Returns the amount of rows that were cleared.
+  pub fn clear_lines(&mut self) -> usize {
+    let board_width = Self::LOGICAL_BOARD_WIDTH as usize;
+
+    let remaining_cells: Vec<Option<MinoType>> = self
+      .board
+      .chunks_exact(board_width)
+      .filter(|row| !row.iter().all(Option::is_some))
+      .flatten()
+      .copied()
+      .collect();
+    let cleared_cell_count = self.board.len() - remaining_cells.len();
+
+    if cleared_cell_count == 0 {
+      return 0;
+    }
+
+    self.spawn_clear_particles();
+
+    let mut new_board = vec![None; cleared_cell_count];
+    new_board.extend(remaining_cells);
+    self.board = new_board;
+
+    cleared_cell_count / board_width
+  }
+
+  /// Pushes the stack up by the given amount of rows, filling the bottom of the board with garbage.
+  ///
+  /// Every garbage row is solid except for the cell at the hole column.
+  /// The active piece is moved up with the stack, and the game is over if any of the stack is pushed off the top of the board.
+  pub fn add_garbage(&mut self, rows: usize, hole_column: usize) -> anyhow::Result<()> {
+    let board_width = Self::LOGICAL_BOARD_WIDTH as usize;
+
+    if hole_column >= board_width {
+      return Err(anyhow!(
+        "The garbage hole column {} is outside of the board.",
+        hole_column
+      ));
+    }
+
+    let rows = rows.min(Self::LOGICAL_BOARD_HEIGHT as usize);
+    let pushed_cell_count = rows * board_width;
+
+    if self.board[..pushed_cell_count].iter().any(Option::is_some) {
+      log::info!("Game over, garbage pushed the stack off the top of the board.");
+
+      self.game_over = true;
+    }
+
+    let mut garbage_row = vec![Some(MinoType::Garbage); board_width];
+    garbage_row[hole_column] = None;
+
+    self.board.drain(..pushed_cell_count);
+    self.board.extend(garbage_row.repeat(rows));
+
+    if let Some(active_piece) = self.active_piece.as_mut() {
+      active_piece.origin.1 -= rows as i32;
+    }
+
+    Ok(())
+  }
+
+  /// Throws particles out of every full row, colored like the cells they came from.
+  fn spawn_clear_particles(&mut self) {
+    if self.particles.max_particles() == 0 {
+      return;
+    }
+
+    let board_width = Self::LOGICAL_BOARD_WIDTH as usize;
+    let hidden_board_height = Self::HIDDEN_BOARD_HEIGHT as i32;
+    let palette = self.settings.palette();
+    let cells: Vec<((i32, i32), [u8; 3])> = self
+      .board
+      .chunks_exact(board_width)
+      .enumerate()
+      .filter(|(_, row)| row.iter().all(Option::is_some))
+      .flat_map(|(y, row)| {
+        row.iter().enumerate().filter_map(move |(x, cell)| {
+          let position = (x as i32, y as i32 - hidden_board_height);
+
+          cell.map(|kind| (position, palette.color(kind)))
+        })
+      })
+      .collect();
+
+    self.particles.spawn_burst(&cells);
+  }
+
+  /// Rotates the active piece 90 degrees, applying the first SRS wall kick that doesn't collide.
+  ///
+  /// Returns true if the piece was rotated.
+  /// Nothing happens if there is no active piece.
+  pub fn try_rotate(&mut self, clockwise: bool) -> bool {
+    let Some(active_piece) = self.active_piece else {
+      return false;
+    };
+    let new_rotation = if clockwise {
+      active_piece.rotation.clockwise()
+    } else {
+      active_piece.rotation.counter_clockwise()
+    };
+
+    let Some((kick_index, new_origin)) = self.find_rotation_kick(&active_piece, new_rotation)
+    else {
+      return false;
+    };
+
+    self.apply_rotation(new_rotation, new_origin, kick_index);
+
+    true
+  }
+
+  /// Rotates the active piece 180 degrees.
+  ///
+  /// SRS has no kicks for a 180 degree turn, so the piece is first flipped in place.
+  /// If that collides, it's turned clockwise twice with the kicks of each 90 degree turn,
+  /// only rotating if both turns fit.
+  ///
+  /// Returns true if the piece was rotated.
+  /// Nothing happens if there is no active piece.
+  pub fn try_rotate_180(&mut self) -> bool {
+    let Some(active_piece) = self.active_piece else {
+      return false;
+    };
+    let halfway_rotation = active_piece.rotation.clockwise();
+    let new_rotation = active_piece.rotation.flipped();
+
+    let kick = self
+      .find_rotation_kick(&active_piece, new_rotation)
+      .or_else(|| {
+        let (_, halfway_origin) = self.find_rotation_kick(&active_piece, halfway_rotation)?;
+        let halfway_piece = ActivePiece {
+          rotation: halfway_rotation,
+          origin: halfway_origin,
+          ..active_piece
+        };
+
+        self.find_rotation_kick(&halfway_piece, new_rotation)
+      });
+    let Some((kick_index, new_origin)) = kick else {
+      return false;
+    };
+
+    self.apply_rotation(new_rotation, new_origin, kick_index);
+
+    true
+  }
+
+  /// Moves the active piece into a rotation found with the given kick, restarting the lock delay.
+  fn apply_rotation(&mut self, new_rotation: Rotation, new_origin: (i32, i32), kick_index: usize) {
+    if let Some(active_piece) = self.active_piece.as_mut() {
+      active_piece.rotation = new_rotation;
+      active_piece.origin = new_origin;
+    }
+
+    self.t_spin = self.detect_t_spin(kick_index);
+    self.reset_lock_delay();
+    self.audio.play(Sound::Rotate);
+  }
+
+  /// Classifies the T-spin the active piece is in after being rotated with the given kick.
+  ///
+  /// A T piece that used a kick and can't move down is a T-spin if at least three of the four
+  /// corners around its center are filled or outside the board.
+  /// If either of the two corners the T is pointing towards is empty, it's only a mini T-spin,
+  /// unless the rotation used the last kick.
+  fn detect_t_spin(&self, kick_index: usize) -> Option<TSpin> {
+    let active_piece = self.active_piece?;
+
+    if active_piece.kind != MinoType::T || kick_index == 0 || !self.is_grounded() {
+      return None;
+    }
+
+    let (x, y) = active_piece.origin;
+    // The T piece's center is always in the middle of its 3x3 bounding box.
+    let corner_filled =
+      |(x_offset, y_offset): (i32, i32)| self.cell_filled(x + x_offset, y + y_offset);
+
+    let filled_corners = [(0, 0), (2, 0), (0, 2), (2, 2)]
+      .into_iter()
+      .filter(|corner| corner_filled(*corner))
+      .count();
+
+    if filled_corners < 3 {
+      return None;
+    }
+
+    let front_corners = match active_piece.rotation {
+      Rotation::Spawn => [(0, 0), (2, 0)],
+      Rotation::Right => [(2, 0), (2, 2)],
+      Rotation::Flipped => [(0, 2), (2, 2)],
+      Rotation::Left => [(0, 0), (0, 2)],
+    };
+
+    if front_corners.into_iter().all(corner_filled) || kick_index == kicks::KICK_COUNT - 1 {
+      Some(TSpin::Full)
+    } else {
+      Some(TSpin::Mini)
+    }
+  }
+
+  /// Returns true if the cell at the given position is filled or outside the sides or bottom of the board.
+  fn cell_filled(&self, x: i32, y: i32) -> bool {
+    let board_width = Self::LOGICAL_BOARD_WIDTH as i32;
+
+    if !(0..board_width).contains(&x) || y >= Self::LOGICAL_BOARD_HEIGHT as i32 {
+      return true;
+    }
+
+    y >= 0 && self.board[(x + (y * board_width)) as usize].is_some()
+  }
+
+  /// Returns the index of the first kick offset that allows the piece to rotate, along with the origin it would move to.
+  ///
+  /// None is returned if every kick offset collides.
+  fn find_rotation_kick(
+    &self,
+    piece: &ActivePiece,
+    new_rotation: Rotation,
+  ) -> Option<(usize, (i32, i32))> {
+    kicks::kick_offsets(piece.kind, piece.rotation, new_rotation)
+      .into_iter()
+      .map(|(x_offset, y_offset)| (piece.origin.0 + x_offset, piece.origin.1 + y_offset))
+      .enumerate()
+      .find(|(_, origin)| !self.collides(piece.kind, new_rotation, *origin))
+  }
+
+  /// Returns true if the given piece would overlap an occupied cell or leave the board when placed at `origin`.
+  ///
+  /// The origin is the top left of the piece's bounding box in board coordinates, with y going down.
+  /// Cells above the top of the board (negative y) are allowed, as pieces can spawn partially out of view.
+  pub fn collides(&self, kind: MinoType, rotation: Rotation, origin: (i32, i32)) -> bool {
+    let board_width = Self::LOGICAL_BOARD_WIDTH as i32;
+    let board_height = Self::LOGICAL_BOARD_HEIGHT as i32;
+
+    kind.cells(rotation).iter().any(|(x_offset, y_offset)| {
+      let (x, y) = (origin.0 + x_offset, origin.1 + y_offset);
+
+      if !(0..board_width).contains(&x) || y >= board_height {
+        return true;
+      }
+
+      if y < 0 {
+        return false;
+      }
+
+      self.board[(x + (y * board_width)) as usize].is_some()
+    })
+  }
+
+  pub fn render(&self, assets: &Assets, renderer: &mut Renderer) -> anyhow::Result<()> {
+    match self.current_state {
+      WorldState::Menu => {
+        let current_menu_name = self.current_menu.unwrap_or(MainMenu::MENU_NAME);
+
+        match current_menu_name {
+          MainMenu::MENU_NAME => self.render_main_menu(assets, renderer)?,
+          Settings::GENERAL_SETTINGS_NAME
+          | Settings::GAME_CONTROLS_NAME
+          | Settings::MENU_CONTROLS_NAME
+          | GameModeMenu::MENU_NAME => self.render_options(assets, renderer)?,
+          PauseMenu::MENU_NAME => {
+            self.render_game(renderer)?;
+
+            renderer.box_blur_region(
+              &LogicalPosition::new(0, 0),
+              &renderer.buffer_dimensions(),
+              Self::PAUSE_BLUR_RADIUS,
+            );
+            renderer.apply_color([0, 0, 0, 0x77])?;
+
+            self.render_pause_screen(assets, renderer)?;
+          }
+          _ => return Err(anyhow!("Unknown menu.")),
+        }
+      }
+
+      WorldState::Countdown => self.render_countdown(renderer)?,
+      WorldState::Game => self.render_game(renderer)?,
+      WorldState::GameOver => self.render_game_over(assets, renderer)?,
+    }
+
+    self.effects.apply(renderer)
+  }
+
+  /// Draws the final board dimmed, with the final score and the game over options over it.
+  ///
+  /// The text is only drawn once a font has been loaded.
+  fn render_game_over(&self, assets: &Assets, renderer: &mut Renderer) -> anyhow::Result<()> {
+    self.render_game(renderer)?;
+    renderer.apply_color(Self::GAME_OVER_DIM_COLOR)?;
+
+    if renderer.fonts().is_empty() {
+      return Ok(());
+    }
+
+    let (title, result_text) = self.results_text();
+    let theme = Theme::default();
+    let title_y = (renderer.buffer_dimensions().height as f32 * 0.25) as u32;
+    let title_height = Self::render_centered_text(renderer, title, title_y, theme.text_size * 1.5)?;
+
+    let score_y = title_y + title_height + theme.option_spacing;
+    let score_height =
+      Self::render_centered_text(renderer, &result_text, score_y, theme.text_size)?;
+
+    let mut stats_y = score_y + score_height + theme.option_spacing;
+
+    for stats_line in self.stats_text() {
+      stats_y += Self::render_centered_text(
+        renderer,
+        &stats_line,
+        stats_y,
+        theme.text_size * Self::STATS_TEXT_SCALE,
+      )?;
+    }
+
+    if let Some(name) = &self.name_entry {
+      let prompt_y = stats_y + theme.option_spacing;
+      let prompt_height =
+        Self::render_centered_text(renderer, "New High Score!", prompt_y, theme.text_size)?;
+      let name_y = prompt_y + prompt_height + theme.option_spacing;
+
+      Self::render_centered_text(
+        renderer,
+        &format!("Name: {}_", name),
+        name_y,
+        theme.text_size,
+      )?;
+
+      return Ok(());
+    }
+
+    let menu_position = LogicalPosition::new(0, stats_y as i32);
+    let current_menu = self.current_menu()?;
+
+    current_menu.render_with_theme(assets, &menu_position, renderer, &theme)
+  }
+
+  /// Draws the board with the current step of the countdown over it.
+  ///
+  /// The countdown is split evenly between each of [`COUNTDOWN_STEPS`](WorldData::COUNTDOWN_STEPS).
+  /// The text is only drawn once a font has been loaded.
+  fn render_countdown(&self, renderer: &mut Renderer) -> anyhow::Result<()> {
+    self.render_game(renderer)?;
+
+    if renderer.fonts().is_empty() {
+      return Ok(());
+    }
+
+    let elapsed_fraction = self
+      .timers
+      .get("countdown")
+      .map(Timer::elapsed_fraction)
+      .unwrap_or_default();
+    let step_index = ((elapsed_fraction * Self::COUNTDOWN_STEPS.len() as f32) as usize)
+      .min(Self::COUNTDOWN_STEPS.len() - 1);
+
+    let theme = Theme::default();
+    let text_y = (renderer.buffer_dimensions().height as f32 * 0.4) as u32;
+
+    Self::render_centered_text(
+      renderer,
+      Self::COUNTDOWN_STEPS[step_index],
+      text_y,
+      theme.text_size * 2.0,
+    )?;
+
+    Ok(())
+  }
+
+  /// Returns the title and result shown once the game ends.
+  ///
+  /// A finished sprint shows the time it took, every other game shows the final score.
+  fn results_text(&self) -> (&'static str, String) {
+    let score_text = format!("Score: {}", self.scoring.score());
+
+    match (self.mode_completed, self.game_mode) {
+      (true, GameMode::Sprint) => {
+        let game_time = self.game_time().unwrap_or_default();
+
+        (
+          "Sprint Complete",
+          format!("Time: {}", Self::format_time(game_time)),
+        )
+      }
+      (true, _) => ("Time's Up", score_text),
+      (false, _) => ("Game Over", score_text),
+    }
+  }
+
+  /// Returns the lines of session stats shown under the result once the game ends.
+  fn stats_text(&self) -> [String; 3] {
+    let stats = &self.stats;
+
+    [
+      format!(
+        "Pieces {}  Lines {}",
+        stats.total_pieces_placed(),
+        stats.total_lines_cleared()
+      ),
+      format!("Tetrises {}  T-Spins {}", stats.clears(4), stats.t_spins()),
+      format!(
+        "Max Combo {}  Time {}",
+        stats.max_combo(),
+        Self::format_time(stats.play_time())
+      ),
+    ]
+  }
+
+  /// Formats the duration as minutes, seconds, and hundredths of a second, such as `1:05.42`.
+  fn format_time(duration: Duration) -> String {
+    let hundredths = duration.as_millis() / 10;
+
+    format!(
+      "{}:{:02}.{:02}",
+      hundredths / 6000,
+      (hundredths / 100) % 60,
+      hundredths % 100
+    )
+  }
+
+  /// Draws white text horizontally centered in the buffer, returning the height of the drawn text.
+  fn render_centered_text(
+    renderer: &mut Renderer,
+    text: &str,
+    y: u32,
+    text_size: f32,
+  ) -> anyhow::Result<u32> {
+    let buffer_dimensions = renderer.buffer_dimensions();
+    let text_dimensions = TextBox::measure(renderer, 0, text, text_size)?;
+
+    let x = buffer_dimensions
+      .width
+      .saturating_sub(text_dimensions.width)
+      / 2;
+    let text_box = TextBox::new(renderer, 0, text, &LogicalPosition::new(x, y), text_size);
+
+    renderer.render_text_box(&text_box, Color::WHITE, &buffer_dimensions)?;
+
+    Ok(text_dimensions.height)
+  }
+
+  fn render_game(&self, renderer: &mut Renderer) -> anyhow::Result<()> {
+    let buffer_dimensions = renderer.buffer_dimensions();
+    renderer.draw_background_gradient(self.scoring.level(), &buffer_dimensions)?;
+
+    self.render_playfield(renderer)?;
+    self.render_board(renderer)?;
+    self.render_ghost_piece(renderer)?;
+    self.render_active_piece(renderer)?;
+    self.render_next_queue(renderer)?;
+    self.render_hold_box(renderer)?;
+    self.render_hud(renderer)?;
+    self.render_particles(renderer)?;
+
+    if self.settings.show_visible_boundary() {
+      Self::render_visible_boundary(renderer)?;
+    }
+
+    if self.settings.show_grid() {
+      self.render_playfield_border(renderer)?;
+    }
+
+    Ok(())
+  }
+
+  fn render_particles(&self, renderer: &mut Renderer) -> anyhow::Result<()> {
+    let buffer_dimensions = renderer.buffer_dimensions();
+    let cell_size = Self::cell_size(&buffer_dimensions);
+    let board_position = Self::board_position(&buffer_dimensions);
+
+    self.particles.render(renderer, &board_position, cell_size)
+  }
+
+  /// Draws the empty visible board, with a line between each of its cells.
+  fn render_playfield(&self, renderer: &mut Renderer) -> anyhow::Result<()> {
+    let buffer_dimensions = renderer.buffer_dimensions();
+    let cell_size = Self::cell_size(&buffer_dimensions) as i32;
+    let board_position = Self::board_position(&buffer_dimensions);
+    let board_dimensions = LogicalSize::new(
+      cell_size as u32 * Self::VISIBLE_BOARD_WIDTH,
+      cell_size as u32 * Self::VISIBLE_BOARD_HEIGHT,
+    );
+
+    renderer.filled_rectangle(&board_position, &board_dimensions, Self::PLAYFIELD_COLOR)?;
+
+    if !self.settings.show_grid() {
+      return Ok(());
+    }
+
+    let grid_color = self.settings.grid_color();
+    let (left, top) = (board_position.x as i32, board_position.y as i32);
+    let (right, bottom) = (
+      left + board_dimensions.width as i32 - 1,
+      top + board_dimensions.height as i32 - 1,
+    );
+
+    for column in 1..Self::VISIBLE_BOARD_WIDTH as i32 {
+      let x = left + (column * cell_size);
+
+      renderer.line(
+        &LogicalPosition::new(x, top),
+        &LogicalPosition::new(x, bottom),
+        grid_color,
+      )?;
+    }
+
+    for row in 1..Self::VISIBLE_BOARD_HEIGHT as i32 {
+      let y = top + (row * cell_size);
+
+      renderer.line(
+        &LogicalPosition::new(left, y),
+        &LogicalPosition::new(right, y),
+        grid_color,
+      )?;
+    }
+
+    Ok(())
+  }
+
+  /// Draws a border along the outermost pixels of the visible board.
+  ///
+  /// Drawn over the pieces, so the edge of the board stays visible when the stack reaches it.
+  fn render_playfield_border(&self, renderer: &mut Renderer) -> anyhow::Result<()> {
+    let buffer_dimensions = renderer.buffer_dimensions();
+    let cell_size = Self::cell_size(&buffer_dimensions);
+    let board_position = Self::board_position(&buffer_dimensions);
+
+    let top_left = LogicalPosition::new(board_position.x as i32, board_position.y as i32);
+    let bottom_right = LogicalPosition::new(
+      top_left.x + (cell_size * Self::VISIBLE_BOARD_WIDTH) as i32 - 1,
+      top_left.y + (cell_size * Self::VISIBLE_BOARD_HEIGHT) as i32 - 1,
+    );
+
+    renderer.bounding_rectangle(&top_left, &bottom_right, self.settings.border_color())
+  }
+
+  /// Draws the next pieces in the bag in a box to the right of the board.
+  ///
+  /// Nothing is drawn if the box doesn't fit within the buffer.
+  fn render_next_queue(&self, renderer: &mut Renderer) -> anyhow::Result<()> {
+    let buffer_dimensions = renderer.buffer_dimensions();
+    let preview_count = self.settings.preview_count();
+    let Some((box_position, box_dimensions)) =
+      Self::next_queue_box(&buffer_dimensions, preview_count)
+    else {
+      return Ok(());
+    };
+    let preview_cell_size = Self::preview_cell_size(&buffer_dimensions);
+
+    renderer.filled_rectangle(&box_position, &box_dimensions, Self::PLAYFIELD_COLOR)?;
+
+    for (slot, kind) in self.bag.peek(preview_count).into_iter().enumerate() {
+      let [red, green, blue] = self.settings.palette().color(kind);
+
+      for cell in Self::preview_cells(kind) {
+        let cell_position =
+          Self::next_queue_cell_position(&box_position, preview_cell_size, slot, cell);
+
+        renderer.filled_rectangle(
+          &cell_position,
+          &LogicalSize::new(preview_cell_size, preview_cell_size),
+          [red, green, blue, 255],
+        )?;
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Returns the size in pixels of a single cell of the pieces in the next queue.
+  fn preview_cell_size(buffer_dimensions: &LogicalSize<u32>) -> u32 {
+    (Self::cell_size(buffer_dimensions) / 4).max(1)
+  }
+
+  /// Returns the position and dimensions of the next queue box, centered in the space right of the board.
+  ///
+  /// Each piece gets 3 rows of space, 2 for the piece and 1 to separate it from the next.
+  /// None is returned if there's nothing to show or the box doesn't fit.
+  fn next_queue_box(
+    buffer_dimensions: &LogicalSize<u32>,
+    preview_count: usize,
+  ) -> Option<(LogicalPosition<u32>, LogicalSize<u32>)> {
+    if preview_count == 0 {
+      return None;
+    }
+
+    let preview_cell_size = Self::preview_cell_size(buffer_dimensions);
+    let board_position = Self::board_position(buffer_dimensions);
+    let board_right =
+      board_position.x + (Self::cell_size(buffer_dimensions) * Self::VISIBLE_BOARD_WIDTH);
+
+    let box_dimensions = LogicalSize::new(
+      (preview_cell_size * 4) + (Self::NEXT_QUEUE_PADDING * 2),
+      (preview_cell_size * ((preview_count as u32 * 3) - 1)) + (Self::NEXT_QUEUE_PADDING * 2),
+    );
+    let free_width = buffer_dimensions.width.checked_sub(board_right)?;
+
+    if box_dimensions.width > free_width
+      || board_position.y + box_dimensions.height > buffer_dimensions.height
+    {
+      return None;
+    }
+
+    let box_position = LogicalPosition::new(
+      board_right + ((free_width - box_dimensions.width) / 2),
+      board_position.y,
+    );
+
+    Some((box_position, box_dimensions))
+  }
+
+  /// Returns the pixel position of a cell of the piece in the given slot of the next queue.
+  fn next_queue_cell_position(
+    box_position: &LogicalPosition<u32>,
+    preview_cell_size: u32,
+    slot: usize,
+    (x, y): (u32, u32),
+  ) -> LogicalPosition<u32> {
+    LogicalPosition::new(
+      box_position.x + Self::NEXT_QUEUE_PADDING + (x * preview_cell_size),
+      box_position.y + Self::NEXT_QUEUE_PADDING + (((slot as u32 * 3) + y) * preview_cell_size),
+    )
+  }
+
+  /// Draws the held piece in a labeled box to the left of the board.
+  ///
+  /// The box is empty when nothing is held, and the piece is greyed out while it can't be swapped back in.
+  /// Nothing is drawn if the box doesn't fit within the buffer.
+  fn render_hold_box(&self, renderer: &mut Renderer) -> anyhow::Result<()> {
+    let buffer_dimensions = renderer.buffer_dimensions();
+    let Some((box_position, box_dimensions)) = Self::hold_box(&buffer_dimensions) else {
+      return Ok(());
+    };
+    let preview_cell_size = Self::preview_cell_size(&buffer_dimensions);
+
+    renderer.filled_rectangle(&box_position, &box_dimensions, Self::PLAYFIELD_COLOR)?;
+
+    // The label can only be drawn once a font has been loaded.
+    if !renderer.fonts().is_empty() {
+      let label_position = LogicalPosition::new(
+        box_position.x + Self::NEXT_QUEUE_PADDING,
+        box_position.y + Self::NEXT_QUEUE_PADDING,
+      );
+      let label_bounds = LogicalSize::new(
+        box_dimensions
+          .width
+          .saturating_sub(Self::NEXT_QUEUE_PADDING * 2),
+        Self::HOLD_LABEL_SIZE,
+      );
+      let label = TextBox::new_fit(
+        renderer,
+        0,
+        "HOLD",
+        &label_position,
+        Self::HOLD_LABEL_SIZE as f32,
+        label_bounds,
+      );
+
+      renderer.render_text_box(&label, Color::WHITE, &buffer_dimensions)?;
+    }
+
+    let Some(held_kind) = self.held else {
+      return Ok(());
+    };
+    let color = if self.hold_locked {
+      Self::HOLD_LOCKED_COLOR
+    } else {
+      let [red, green, blue] = self.settings.palette().color(held_kind);
+
+      [red, green, blue, 255]
+    };
+
+    for cell in Self::preview_cells(held_kind) {
+      let cell_position = Self::hold_cell_position(&box_position, preview_cell_size, cell);
+
+      renderer.filled_rectangle(
+        &cell_position,
+        &LogicalSize::new(preview_cell_size, preview_cell_size),
+        color,
+      )?;
+    }
+
+    Ok(())
+  }
+
+  /// Draws the score, level, and lines cleared to the left of the board, below the hold box.
+  ///
+  /// Nothing is drawn until a font has been loaded.
+  fn render_hud(&self, renderer: &mut Renderer) -> anyhow::Result<()> {
+    if renderer.fonts().is_empty() {
+      return Ok(());
+    }
+
+    let position = Self::hud_position(&renderer.buffer_dimensions());
+    let mut hud = self.hud.borrow_mut();
+
+    hud.update(renderer, &self.scoring, &position);
+    hud.render(renderer)
+  }
+
+  /// Returns the top left of the HUD, just below the hold box if there's room for it.
+  fn hud_position(buffer_dimensions: &LogicalSize<u32>) -> LogicalPosition<u32> {
+    let board_position = Self::board_position(buffer_dimensions);
+
+    match Self::hold_box(buffer_dimensions) {
+      Some((box_position, box_dimensions)) => LogicalPosition::new(
+        box_position.x,
+        box_position.y + box_dimensions.height + (Self::NEXT_QUEUE_PADDING * 4),
+      ),
+      None => LogicalPosition::new(0, board_position.y),
+    }
+  }
+
+  /// Returns the position and dimensions of the hold box, centered in the space left of the board.
+  ///
+  /// None is returned if the box doesn't fit.
+  fn hold_box(
+    buffer_dimensions: &LogicalSize<u32>,
+  ) -> Option<(LogicalPosition<u32>, LogicalSize<u32>)> {
+    let preview_cell_size = Self::preview_cell_size(buffer_dimensions);
+    let board_position = Self::board_position(buffer_dimensions);
+
+    let box_dimensions = LogicalSize::new(
+      (preview_cell_size * 4) + (Self::NEXT_QUEUE_PADDING * 2),
+      Self::HOLD_LABEL_SIZE + (preview_cell_size * 2) + (Self::NEXT_QUEUE_PADDING * 3),
+    );
+
+    if box_dimensions.width > board_position.x
+      || board_position.y + box_dimensions.height > buffer_dimensions.height
+    {
+      return None;
+    }
+
+    let box_position = LogicalPosition::new(
+      (board_position.x - box_dimensions.width) / 2,
+      board_position.y,
+    );
+
+    Some((box_position, box_dimensions))
+  }
+
+  /// Returns the pixel position of a cell of the held piece, below the hold box's label.
+  fn hold_cell_position(
+    box_position: &LogicalPosition<u32>,
+    preview_cell_size: u32,
+    (x, y): (u32, u32),
+  ) -> LogicalPosition<u32> {
+    LogicalPosition::new(
+      box_position.x + Self::NEXT_QUEUE_PADDING + (x * preview_cell_size),
+      box_position.y
+        + (Self::NEXT_QUEUE_PADDING * 2)
+        + Self::HOLD_LABEL_SIZE
+        + (y * preview_cell_size),
+    )
+  }
+
+  /// Returns the cells of the piece in its spawn rotation, moved so its topmost and leftmost cells are at 0.
+  fn preview_cells(kind: MinoType) -> [(u32, u32); 4] {
+    let cells = kind.cells(Rotation::Spawn);
+    let min_x = cells.iter().map(|(x, _)| *x).min().unwrap_or(0);
+    let min_y = cells.iter().map(|(_, y)| *y).min().unwrap_or(0);
+
+    cells.map(|(x, y)| ((x - min_x) as u32, (y - min_y) as u32))
+  }
+
+  /// Draws a thin line along the top of the visible part of the board.
+  fn render_visible_boundary(renderer: &mut Renderer) -> anyhow::Result<()> {
+    let buffer_dimensions = renderer.buffer_dimensions();
+    let cell_size = Self::cell_size(&buffer_dimensions);
+    let board_position = Self::board_position(&buffer_dimensions);
+
+    renderer.filled_rectangle(
+      &board_position,
+      &LogicalSize::new(cell_size * Self::VISIBLE_BOARD_WIDTH, 1),
+      Self::VISIBLE_BOUNDARY_COLOR,
+    )
+  }
+
+  /// Draws every filled cell within the visible part of the board.
+  fn render_board(&self, renderer: &mut Renderer) -> anyhow::Result<()> {
+    let board_width = Self::LOGICAL_BOARD_WIDTH as usize;
+
+    self
+      .board
+      .iter()
+      .enumerate()
+      .skip(Self::HIDDEN_BOARD_HEIGHT as usize * board_width)
+      .try_for_each(|(index, cell)| {
+        let Some(kind) = cell else {
+          return Ok(());
+        };
+        let [red, green, blue] = self.settings.palette().color(*kind);
+        let position = ((index % board_width) as i32, (index / board_width) as i32);
+
+        Self::render_cell(renderer, position, [red, green, blue, 255])
+      })
+  }
+
+  /// Draws the piece currently being controlled by the player.
+  ///
+  /// Nothing is drawn if there is no active piece.
+  fn render_active_piece(&self, renderer: &mut Renderer) -> anyhow::Result<()> {
+    let Some(active_piece) = self.active_piece else {
+      return Ok(());
+    };
+    let [red, green, blue] = self.settings.palette().color(active_piece.kind);
+
+    active_piece
+      .cells()
+      .into_iter()
+      .try_for_each(|cell| Self::render_cell(renderer, cell, [red, green, blue, 255]))
+  }
+
+  /// Draws the active piece at the position it would land if hard dropped, at the [`ghost alpha`](GameSettings::ghost_alpha).
+  ///
+  /// Nothing is drawn if there is no active piece, or the ghost is disabled.
+  fn render_ghost_piece(&self, renderer: &mut Renderer) -> anyhow::Result<()> {
+    if !self.settings.ghost_enabled() {
+      return Ok(());
+    }
+
+    let (Some(active_piece), Some(ghost_origin)) = (self.active_piece, self.ghost_origin()) else {
+      return Ok(());
+    };
+    let ghost_piece = ActivePiece {
+      origin: ghost_origin,
+      ..active_piece
+    };
+    let [red, green, blue] = self.settings.palette().color(ghost_piece.kind);
+    let ghost_color = [red, green, blue, self.settings.ghost_alpha()];
+
+    ghost_piece
+      .cells()
+      .into_iter()
+      .try_for_each(|cell| Self::render_cell(renderer, cell, ghost_color))
+  }
+
+  /// Draws a single board cell with the given color.
+  ///
+  /// Cells outside of the visible board, such as those in the hidden rows above it, aren't drawn.
+  fn render_cell(
+    renderer: &mut Renderer,
+    (x, y): (i32, i32),
+    color: [u8; 4],
+  ) -> anyhow::Result<()> {
+    let visible_y = y - Self::HIDDEN_BOARD_HEIGHT as i32;
+
+    if !(0..Self::VISIBLE_BOARD_WIDTH as i32).contains(&x)
+      || !(0..Self::VISIBLE_BOARD_HEIGHT as i32).contains(&visible_y)
+    {
+      return Ok(());
+    }
+
+    let cell_size = Self::cell_size(&renderer.buffer_dimensions());
+    let board_position = Self::board_position(&renderer.buffer_dimensions());
+    let cell_position = LogicalPosition::new(
+      board_position.x + (x as u32 * cell_size),
+      board_position.y + (visible_y as u32 * cell_size),
+    );
+
+    renderer.filled_rectangle(
+      &cell_position,
+      &LogicalSize::new(cell_size, cell_size),
+      color,
+    )
+  }
+
+  /// Returns the size in pixels of a single board cell.
+  ///
+  /// This is the largest size that fits the entire visible board within the buffer.
+  fn cell_size(buffer_dimensions: &LogicalSize<u32>) -> u32 {
+    (buffer_dimensions.width / Self::VISIBLE_BOARD_WIDTH)
+      .min(buffer_dimensions.height / Self::VISIBLE_BOARD_HEIGHT)
+  }
+
+  /// Returns the top left of the visible board, centering it within the buffer.
+  fn board_position(buffer_dimensions: &LogicalSize<u32>) -> LogicalPosition<u32> {
+    let cell_size = Self::cell_size(buffer_dimensions);
+
+    LogicalPosition::new(
+      (buffer_dimensions.width - (cell_size * Self::VISIBLE_BOARD_WIDTH)) / 2,
+      (buffer_dimensions.height - (cell_size * Self::VISIBLE_BOARD_HEIGHT)) / 2,
+    )
+  }
+
+  fn render_main_menu(&self, assets: &Assets, renderer: &mut Renderer) -> anyhow::Result<()> {
+    let buffer_dimensions = renderer.buffer_dimensions();
+    renderer.draw_background_gradient(1, &buffer_dimensions)?;
+
+    let menu_position = LogicalPosition {
+      x: 0,
+      y: (buffer_dimensions.height as f32 * 0.25).cast::<i32>(),
+    };
+    let option_spacing = 20; // pixels.
+
+    let current_menu = self.current_menu()?;
+
+    current_menu.render(assets, &menu_position, renderer, option_spacing)
+  }
+
+  /// Draws the settings menu, or whichever of its submenus is open.
+  fn render_options(&self, assets: &Assets, renderer: &mut Renderer) -> anyhow::Result<()> {
+    let buffer_dimensions = renderer.buffer_dimensions();
+    renderer.draw_background_gradient(1, &buffer_dimensions)?;
+
+    let menu_position = LogicalPosition {
+      x: 0,
+      y: (buffer_dimensions.height as f32 * 0.15).cast::<i32>(),
+    };
+
+    let current_menu = self.current_menu()?;
+
+    current_menu.render_with_theme(assets, &menu_position, renderer, &Theme::default())
+  }
+
+  /// Draws the title and options of the pause menu over the blurred game.
+  ///
+  /// The title is only drawn once a font has been loaded.
+  fn render_pause_screen(&self, assets: &Assets, renderer: &mut Renderer) -> anyhow::Result<()> {
+    let theme = Theme::default();
+    let title_y = (renderer.buffer_dimensions().height as f32 * 0.25) as u32;
+    let mut menu_y = title_y;
+
+    if !renderer.fonts().is_empty() {
+      menu_y += Self::render_centered_text(renderer, "Paused", title_y, theme.text_size * 1.5)?;
+      menu_y += theme.option_spacing;
+    }
+
+    let menu_position = LogicalPosition::new(0, menu_y as i32);
+    let current_menu = self.current_menu()?;
+
+    current_menu.render_with_theme(assets, &menu_position, renderer, &theme)
+  }
+
+  /// Moves the cursor of the open menu to the option drawn at the given position in the buffer.
+  ///
+  /// Returns true if there was an option at the position.
+  /// Nothing happens outside of menus and the game over screen.
+  pub fn hover_menu_option(&mut self, position: &LogicalPosition<u32>) -> bool {
+    if !matches!(self.current_state, WorldState::Menu | WorldState::GameOver) {
+      return false;
+    }
+
+    let Ok(current_menu) = self.current_menu_mut() else {
+      return false;
+    };
+    let Some(option_index) = current_menu.option_index_at(position) else {
+      return false;
+    };
+
+    current_menu.select(option_index);
+
+    true
+  }
+
+  /// Silences or unsilences sound effects, saving the choice in the settings.
+  pub fn set_muted(&mut self, muted: bool) {
+    self.settings.set_muted(muted);
+    self.audio.set_muted(muted);
+  }
+
+  pub fn game_mode(&self) -> GameMode {
+    self.game_mode
+  }
+
+  pub fn world_state(&self) -> WorldState {
+    self.current_state
+  }
+
+  /// Returns the state of the current game, excluding menus and timers.
+  pub fn snapshot(&self) -> GameSnapshot {
+    GameSnapshot {
+      current_state: self.current_state,
+      game_mode: self.game_mode,
+      mode_completed: self.mode_completed,
+      board: self.board.clone(),
+      held: self.held,
+      hold_locked: self.hold_locked,
+      active_piece: self.active_piece,
+      bag: self.bag.clone(),
+      seed: self.seed,
+      scoring: self.scoring.clone(),
+      stats: self.stats.clone(),
+      game_over: self.game_over,
+      top_out_grace: self.top_out_grace,
+      lock_resets: self.lock_resets,
+    }
+  }
+
+  /// Replaces the current game with the one in the snapshot.
+  ///
+  /// Every timer is stopped, and the menus are reset to the main menu,
+  /// opening the game over screen if the snapshot was taken on it.
+  ///
+  /// # Errors
+  ///
+  /// - When the snapshot's board isn't the size of the board.
+  pub fn restore(&mut self, snapshot: GameSnapshot) -> anyhow::Result<()> {
+    if snapshot.board.len() != self.board.len() {
+      return Err(anyhow!(
+        "Attempted to restore a board of {} cells, expected {}.",
+        snapshot.board.len(),
+        self.board.len()
+      ));
+    }
+
+    self.current_state = snapshot.current_state;
+    self.game_mode = snapshot.game_mode;
+    self.mode_completed = snapshot.mode_completed;
+    self.board = snapshot.board;
+    self.held = snapshot.held;
+    self.hold_locked = snapshot.hold_locked;
+    self.active_piece = snapshot.active_piece;
+    self.bag = snapshot.bag;
+    self.seed = snapshot.seed;
+    self.scoring = snapshot.scoring;
+    self.stats = snapshot.stats;
+    self.game_over = snapshot.game_over;
+    self.top_out_grace = snapshot.top_out_grace;
+    self.lock_resets = snapshot.lock_resets;
+    self.t_spin = None;
+    self.timers.clear();
+    self.particles.clear();
+
+    self.menu_stack.clear();
+    self.current_menu = Some(MainMenu::MENU_NAME);
+
+    if self.current_state == WorldState::GameOver {
+      self
+        .menus
+        .insert(GameOverMenu::MENU_NAME, GameOverMenu::new_menu());
+      self.push_menu(GameOverMenu::MENU_NAME);
+    }
+
+    Ok(())
+  }
+
+  /// Saves the current game as json to the path.
+  pub fn save_state(&self, path: &Path) -> anyhow::Result<()> {
+    self.snapshot().save_to_path(path)
+  }
+
+  /// Replaces the current game with the one saved at the path.
+  pub fn load_state(&mut self, path: &Path) -> anyhow::Result<()> {
+    let snapshot = GameSnapshot::load_from_path(path)?;
+
+    self.restore(snapshot)
+  }
+
+  /// Returns every cell of the board, row by row from the top of the hidden rows.
+  pub fn board(&self) -> &[Option<MinoType>] {
+    &self.board
+  }
+
+  /// Parses a board from a grid of text, where `.` is an empty cell and each letter is a cell of that piece type.
+  ///
+  /// Every row is [`LOGICAL_BOARD_WIDTH`](Self::LOGICAL_BOARD_WIDTH) cells wide, and leading whitespace and blank lines are ignored.
+  /// Boards with fewer rows than the [`LOGICAL_BOARD_HEIGHT`](Self::LOGICAL_BOARD_HEIGHT)
+  /// are placed at the bottom, with empty rows above them.
+  ///
+  /// # Panics
+  ///
+  /// When a row is the wrong width, there are too many rows, or a cell is neither `.` nor a piece type.
+  /// This is intended for writing boards by hand, such as in tests.
+  pub fn board_from_str(board: &str) -> Vec<Option<MinoType>> {
+    let board_width = Self::LOGICAL_BOARD_WIDTH as usize;
+    let rows: Vec<&str> = board
+      .lines()
+      .map(str::trim)
+      .filter(|row| !row.is_empty())
+      .collect();
+
+    assert!(
+      rows.len() <= Self::LOGICAL_BOARD_HEIGHT as usize,
+      "The board has {} rows, more than the {} that fit.",
+      rows.len(),
+      Self::LOGICAL_BOARD_HEIGHT
+    );
+
+    let empty_row_count = Self::LOGICAL_BOARD_HEIGHT as usize - rows.len();
+    let mut cells = vec![None; empty_row_count * board_width];
+
+    for row in rows {
+      assert_eq!(
+        row.chars().count(),
+        board_width,
+        "The board row `{row}` isn't {board_width} cells wide."
+      );
+
+      cells.extend(row.chars().map(|cell| {
+        match cell {
+          '.' => None,
+          letter => Some(
+            MinoType::from_letter(letter)
+              .unwrap_or_else(|| panic!("`{letter}` in the board row `{row}` isn't a piece type.")),
+          ),
+        }
+      }));
+    }
+
+    cells
+  }
+
+  /// Writes every row of the board as a line of text, in the format read by [`board_from_str()`](Self::board_from_str).
+  pub fn board_to_str(board: &[Option<MinoType>]) -> String {
+    board
+      .chunks(Self::LOGICAL_BOARD_WIDTH as usize)
+      .map(|row| {
+        row
+          .iter()
+          .map(|cell| cell.map_or('.', |mino_type| mino_type.to_letter()))
+          .collect::<String>()
+      })
+      .collect::<Vec<_>>()
+      .join("\n")
+  }
+
+  pub fn scoring(&self) -> &Scoring {
+    &self.scoring
+  }
+
+  /// Returns the stats kept across every game played since the world was created.
+  pub fn stats(&self) -> &Stats {
+    &self.stats
+  }
+
+  pub fn leaderboard(&self) -> &Leaderboard {
+    &self.leaderboard
+  }
+
+  /// Replaces the leaderboard, such as with the one saved to disk.
+  pub fn set_leaderboard(&mut self, leaderboard: Leaderboard) {
+    self.leaderboard = leaderboard;
+  }
+
+  /// Returns the piece currently being controlled by the player.
+  pub fn active_piece(&self) -> Option<ActivePiece> {
+    self.active_piece
+  }
+
+  /// Returns the seed the pieces of the current game are handed out with.
+  ///
+  /// Starting a game with the same seed hands out the same sequence of pieces.
+  pub fn seed(&self) -> u64 {
+    self.seed
+  }
+
+  pub fn settings(&self) -> &GameSettings {
+    &self.settings
+  }
+
+  pub fn settings_mut(&mut self) -> &mut GameSettings {
+    &mut self.settings
+  }
+
+  /// Moves the world into the given state.
+  ///
+  /// # Errors
+  ///
+  /// - When the current state can't transition into the new state.
+  pub fn transition(&mut self, new_state: WorldState) -> anyhow::Result<()> {
+    if !self.current_state.can_transition_to(new_state) {
+      return Err(anyhow!(
+        "Attempted an illegal world state transition: `{:?}` -> `{:?}`",
+        self.current_state,
+        new_state
+      ));
+    }
+
+    log::debug!(
+      "World state transition: `{:?}` -> `{:?}`",
+      self.current_state,
+      new_state
+    );
+
+    // Fades through black between the menus and the game. The board is already shown during the countdown,
+    // so starting the game from it doesn't fade.
+    let touches_game = self.current_state == WorldState::Game || new_state == WorldState::Game;
+
+    if touches_game && self.current_state != WorldState::Countdown {
+      self.effects.fade_in();
+    }
+
+    self.current_state = new_state;
+
+    Ok(())
+  }
+
+  /// Returns a reference to the currently selected menu.
+  ///
+  /// # Errors
+  ///
+  /// - When there is no selected menu.
+  /// - When the selected menu doesn't exist in the list of menus.
+  fn current_menu(&self) -> anyhow::Result<&Menu> {
+    let Some(current_menu_name) = self.current_menu else {
+      return Err(anyhow!(
+        "Attempted to get the current menu when there wasn't one."
+      ));
+    };
+    match self.menus.get(current_menu_name) {
+      Some(menu) => Ok(menu),
+      None => Err(anyhow!("Currently selected menu does not exist.")),
+    }
+  }
+
+  /// Returns a mutable reference to the currently selected menu.
+  ///
+  /// # Errors
+  ///
+  /// - When there is no selected menu.
+  /// - When the selected menu doesn't exist in the list of menus.
+  fn current_menu_mut(&mut self) -> anyhow::Result<&mut Menu> {
+    let Some(current_menu_name) = self.current_menu else {
+      return Err(anyhow!(
+        "Attempted to get the current menu when there wasn't one."
+      ));
+    };
+    match self.menus.get_mut(current_menu_name) {
+      Some(menu) => Ok(menu),
+      None => Err(anyhow!("Currently selected menu does not exist.")),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::game::palette::Palette;
+  use crate::game::resolution::RenderResolution;
+  use crate::rustris_config::RENDERED_WINDOW_DIMENSIONS;
+
+  const TEST_SEED: u64 = 0x5EED;
+
+  fn game_with_piece(kind: MinoType, origin: (i32, i32)) -> WorldData {
+    let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+
+    world_data.current_state = WorldState::Game;
+    world_data.active_piece = Some(ActivePiece::new(kind, origin));
+
+    world_data
+  }
+
+  mod collision_logic {
+    use super::*;
+
+    const BOTTOM_ROW: i32 = WorldData::LOGICAL_BOARD_HEIGHT as i32 - 1;
+
+    fn fill_cell(world_data: &mut WorldData, x: i32, y: i32) {
+      let index = x + (y * WorldData::LOGICAL_BOARD_WIDTH as i32);
+
+      world_data.board[index as usize] = Some(MinoType::O);
+    }
+
+    #[test]
+    fn piece_in_open_space_does_not_collide() {
+      let world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+
+      assert!(!world_data.collides(MinoType::S, Rotation::Spawn, (3, 10)));
+    }
+
+    #[test]
+    fn piece_past_the_walls_collides() {
+      let world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+
+      // The spawn S piece occupies columns 0-2 of its bounding box.
+      assert!(!world_data.collides(MinoType::S, Rotation::Spawn, (0, 10)));
+      assert!(world_data.collides(MinoType::S, Rotation::Spawn, (-1, 10)));
+      assert!(!world_data.collides(MinoType::S, Rotation::Spawn, (7, 10)));
+      assert!(world_data.collides(MinoType::S, Rotation::Spawn, (8, 10)));
+    }
+
+    #[test]
+    fn piece_past_the_floor_collides() {
+      let world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+
+      assert!(!world_data.collides(MinoType::S, Rotation::Spawn, (3, BOTTOM_ROW - 1)));
+      assert!(world_data.collides(MinoType::S, Rotation::Spawn, (3, BOTTOM_ROW)));
+    }
+
+    #[test]
+    fn piece_above_the_board_does_not_collide() {
+      let world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+
+      assert!(!world_data.collides(MinoType::S, Rotation::Spawn, (3, -1)));
+    }
+
+    #[test]
+    fn piece_overlapping_the_stack_collides() {
+      let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+
+      // A flat stack two cells high along the bottom.
+      for x in 0..WorldData::LOGICAL_BOARD_WIDTH as i32 {
+        fill_cell(&mut world_data, x, BOTTOM_ROW);
+        fill_cell(&mut world_data, x, BOTTOM_ROW - 1);
+      }
+
+      // Resting directly on top of the stack.
+      assert!(!world_data.collides(MinoType::S, Rotation::Spawn, (3, BOTTOM_ROW - 3)));
+      // One row lower, the bottom cells of the S overlap the stack.
+      assert!(world_data.collides(MinoType::S, Rotation::Spawn, (3, BOTTOM_ROW - 2)));
+    }
+
+    #[test]
+    fn piece_fits_into_matching_gap_in_the_stack() {
+      let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+
+      // Leaves the gap a spawn S piece's bottom row occupies: columns 0 and 1.
+      world_data.board = WorldData::board_from_str("..OOOOOOOO");
+
+      assert!(!world_data.collides(MinoType::S, Rotation::Spawn, (0, BOTTOM_ROW - 1)));
+      assert!(world_data.collides(MinoType::S, Rotation::Spawn, (1, BOTTOM_ROW - 1)));
+    }
+  }
+
+  mod board_string_logic {
+    use super::*;
+
+    const STACK: &str = "
+      ..........
+      ....T.....
+      ..S.TTJ..Z
+      IISSTJJOZZ
+    ";
+
+    #[test]
+    fn short_boards_are_placed_at_the_bottom() {
+      let board = WorldData::board_from_str(STACK);
+      let board_width = WorldData::LOGICAL_BOARD_WIDTH as usize;
+      let bottom_row_start = board.len() - board_width;
+
+      assert_eq!(
+        board.len(),
+        board_width * WorldData::LOGICAL_BOARD_HEIGHT as usize
+      );
+      assert!(board[..bottom_row_start - (3 * board_width)]
+        .iter()
+        .all(Option::is_none));
+      assert_eq!(board[bottom_row_start], Some(MinoType::I));
+      assert_eq!(board[bottom_row_start - board_width + 2], Some(MinoType::S));
+      assert_eq!(board[bottom_row_start - board_width + 3], None);
+      assert_eq!(board[bottom_row_start - 1], Some(MinoType::Z));
+    }
+
+    #[test]
+    fn boards_round_trip_through_strings() {
+      let board = WorldData::board_from_str(STACK);
+      let board_string = WorldData::board_to_str(&board);
+
+      assert_eq!(
+        board_string.lines().count(),
+        WorldData::LOGICAL_BOARD_HEIGHT as usize
+      );
+      assert!(board_string.ends_with("....T.....\n..S.TTJ..Z\nIISSTJJOZZ"));
+      assert_eq!(WorldData::board_from_str(&board_string), board);
+      assert_eq!(
+        WorldData::board_to_str(&WorldData::board_from_str(&board_string)),
+        board_string
+      );
+    }
+
+    #[test]
+    fn locked_pieces_show_up_in_the_board_string() {
+      let mut world_data = game_with_piece(MinoType::O, (0, 0));
+
+      world_data.hard_drop();
+
+      assert!(WorldData::board_to_str(world_data.board()).ends_with("\n.OO.......\n.OO......."));
+    }
+
+    #[test]
+    #[should_panic]
+    fn rows_of_the_wrong_width_are_rejected() {
+      WorldData::board_from_str("....");
+    }
+
+    #[test]
+    #[should_panic]
+    fn unknown_cells_are_rejected() {
+      WorldData::board_from_str("X.........");
+    }
+  }
+
+  mod stats_logic {
+    use super::*;
+
+    #[test]
+    fn dropped_pieces_are_counted() {
+      let mut world_data = game_with_piece(MinoType::O, (0, 0));
+
+      world_data.hard_drop();
+
+      assert_eq!(world_data.stats().pieces_placed(MinoType::O), 1);
+      assert_eq!(world_data.stats().total_pieces_placed(), 1);
+      assert_eq!(world_data.stats().total_lines_cleared(), 0);
+    }
+
+    #[test]
+    fn cleared_lines_are_counted_by_clear_type() {
+      let mut world_data = game_with_piece(MinoType::I, (0, 0));
+      world_data.board = WorldData::board_from_str("....OOOOOO");
+
+      world_data.hard_drop();
+
+      assert_eq!(world_data.stats().pieces_placed(MinoType::I), 1);
+      assert_eq!(world_data.stats().clears(1), 1);
+      assert_eq!(world_data.stats().max_combo(), 0);
+
+      // A vertical I dropped into the well on the left.
+      world_data.board = WorldData::board_from_str(
+        "
+        .OOOOOOOOO
+        .OOOOOOOOO
+        .OOOOOOOOO
+        .OOOOOOOOO
+        ",
+      );
+      world_data.active_piece = Some(ActivePiece {
+        kind: MinoType::I,
+        rotation: Rotation::Left,
+        origin: (-1, 0),
+      });
+
+      world_data.hard_drop();
+
+      assert_eq!(world_data.stats().clears(4), 1);
+      assert_eq!(world_data.stats().total_lines_cleared(), 5);
+      assert_eq!(world_data.stats().max_combo(), 1);
+    }
+
+    #[test]
+    fn stats_are_kept_across_games() {
+      let mut world_data = game_with_piece(MinoType::O, (0, 0));
+
+      world_data.hard_drop();
+      world_data.reset_game();
+
+      assert_eq!(world_data.stats().total_pieces_placed(), 1);
+      assert_eq!(world_data.snapshot().stats, *world_data.stats());
+    }
+  }
+
+  mod garbage_logic {
+    use super::*;
+
+    #[test]
+    fn garbage_rows_are_added_under_the_stack() {
+      let mut world_data = game_with_piece(MinoType::T, (3, 10));
+      world_data.board = WorldData::board_from_str("IIII......");
+
+      world_data.add_garbage(2, 7).unwrap();
+
+      assert!(WorldData::board_to_str(world_data.board())
+        .ends_with("\n..........\nIIII......\nGGGGGGG.GG\nGGGGGGG.GG"));
+      assert_eq!(
+        world_data.board().len(),
+        (WorldData::LOGICAL_BOARD_WIDTH * WorldData::LOGICAL_BOARD_HEIGHT) as usize
+      );
+      assert_eq!(world_data.active_piece().unwrap().origin, (3, 8));
+      assert!(!world_data.game_over);
+    }
+
+    #[test]
+    fn garbage_pushing_the_stack_off_the_board_is_game_over() {
+      let mut world_data = game_with_piece(MinoType::T, (3, 10));
+      // The second cell of the second row, one row under the top of the board.
+      world_data.board[WorldData::LOGICAL_BOARD_WIDTH as usize + 1] = Some(MinoType::O);
+
+      world_data.add_garbage(1, 0).unwrap();
+
+      assert!(!world_data.game_over);
+      assert_eq!(world_data.board[1], Some(MinoType::O));
+
+      world_data.add_garbage(1, 0).unwrap();
+
+      assert!(world_data.game_over);
+    }
+
+    #[test]
+    fn hole_outside_the_board_is_an_error() {
+      let mut world_data = game_with_piece(MinoType::T, (3, 10));
+
+      assert!(world_data
+        .add_garbage(1, WorldData::LOGICAL_BOARD_WIDTH as usize)
+        .is_err());
+      assert!(world_data.board().iter().all(Option::is_none));
+    }
+  }
+
+  mod render_logic {
+    use super::*;
+
+    const BOARD_WIDTH: i32 = WorldData::LOGICAL_BOARD_WIDTH as i32;
+    const BOTTOM_ROW: i32 = WorldData::LOGICAL_BOARD_HEIGHT as i32 - 1;
+    const BACKGROUND: [u8; 4] = [0, 0, 0, 255];
+
+    fn pixel(renderer: &Renderer, x: u32, y: u32) -> [u8; 4] {
+      let index = ((x + (y * renderer.buffer_dimensions().width)) * 4) as usize;
+
+      renderer.frame()[index..(index + 4)].try_into().unwrap()
+    }
+
+    #[test]
+    fn grid_and_border_follow_the_setting() {
+      let mut renderer = Renderer::new_in_memory(RENDERED_WINDOW_DIMENSIONS);
+      let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+      let buffer_dimensions = renderer.buffer_dimensions();
+      let cell_size = WorldData::cell_size(&buffer_dimensions);
+      let board_position = WorldData::board_position(&buffer_dimensions);
+
+      let border_pixel = (board_position.x, board_position.y + (cell_size / 2));
+      let grid_pixel = (
+        board_position.x + cell_size,
+        board_position.y + (cell_size / 2),
+      );
+
+      world_data.render_game(&mut renderer).unwrap();
+
+      assert_ne!(
+        pixel(&renderer, border_pixel.0, border_pixel.1),
+        WorldData::PLAYFIELD_COLOR
+      );
+      assert_ne!(
+        pixel(&renderer, grid_pixel.0, grid_pixel.1),
+        WorldData::PLAYFIELD_COLOR
+      );
+
+      world_data.settings.set_show_grid(false);
+      renderer.clear().unwrap();
+      world_data.render_game(&mut renderer).unwrap();
+
+      assert_eq!(
+        pixel(&renderer, border_pixel.0, border_pixel.1),
+        WorldData::PLAYFIELD_COLOR
+      );
+      assert_eq!(
+        pixel(&renderer, grid_pixel.0, grid_pixel.1),
+        WorldData::PLAYFIELD_COLOR
+      );
+    }
+
+    /// Returns the color of the pixel in the center of the given board cell.
+    fn cell_center_pixel(renderer: &Renderer, (x, y): (i32, i32)) -> [u8; 4] {
+      let buffer_dimensions = renderer.buffer_dimensions();
+      let cell_size = WorldData::cell_size(&buffer_dimensions);
+      let board_position = WorldData::board_position(&buffer_dimensions);
+      let visible_y = (y - WorldData::HIDDEN_BOARD_HEIGHT as i32) as u32;
+
+      let pixel_x = board_position.x + (x as u32 * cell_size) + (cell_size / 2);
+      let pixel_y = board_position.y + (visible_y * cell_size) + (cell_size / 2);
+      let index = ((pixel_x + (pixel_y * buffer_dimensions.width)) * 4) as usize;
+
+      renderer.frame()[index..(index + 4)].try_into().unwrap()
+    }
+
+    #[test]
+    fn filled_cell_is_drawn_with_its_color() {
+      let mut renderer = Renderer::new_in_memory(RENDERED_WINDOW_DIMENSIONS);
+      let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+      world_data.board[(BOTTOM_ROW * BOARD_WIDTH) as usize] = Some(MinoType::T);
+
+      world_data.render_game(&mut renderer).unwrap();
+
+      let [red, green, blue] = Palette::default().color(MinoType::T);
+      assert_eq!(
+        cell_center_pixel(&renderer, (0, BOTTOM_ROW)),
+        [red, green, blue, 255]
+      );
+      assert_eq!(
+        cell_center_pixel(&renderer, (1, BOTTOM_ROW)),
+        WorldData::PLAYFIELD_COLOR
+      );
+    }
+
+    #[test]
+    fn cells_are_drawn_with_the_selected_palette() {
+      let mut renderer = Renderer::new_in_memory(RENDERED_WINDOW_DIMENSIONS);
+      let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+      world_data.board[(BOTTOM_ROW * BOARD_WIDTH) as usize] = Some(MinoType::S);
+      world_data.settings.set_palette(Palette::Deuteranopia);
+
+      world_data.render_game(&mut renderer).unwrap();
+
+      let [red, green, blue] = Palette::Deuteranopia.color(MinoType::S);
+      assert_eq!(
+        cell_center_pixel(&renderer, (0, BOTTOM_ROW)),
+        [red, green, blue, 255]
+      );
+    }
+
+    #[test]
+    fn active_piece_is_drawn_over_its_ghost() {
+      let mut renderer = Renderer::new_in_memory(RENDERED_WINDOW_DIMENSIONS);
+      let world_data = game_with_piece(MinoType::O, (3, BOTTOM_ROW - 10));
+
+      world_data.render_game(&mut renderer).unwrap();
+
+      let [red, green, blue] = Palette::default().color(MinoType::O);
+      // The O piece occupies columns 1-2 of its bounding box.
+      assert_eq!(
+        cell_center_pixel(&renderer, (4, BOTTOM_ROW - 10)),
+        [red, green, blue, 255]
+      );
+
+      let ghost_pixel = cell_center_pixel(&renderer, (4, BOTTOM_ROW));
+      assert_ne!(ghost_pixel, WorldData::PLAYFIELD_COLOR);
+      assert_ne!(ghost_pixel, [red, green, blue, 255]);
+    }
+
+    #[test]
+    fn disabled_ghost_is_not_drawn() {
+      let mut renderer = Renderer::new_in_memory(RENDERED_WINDOW_DIMENSIONS);
+      let mut world_data = game_with_piece(MinoType::O, (3, BOTTOM_ROW - 10));
+      world_data.settings.set_ghost_enabled(false);
+
+      world_data.render_game(&mut renderer).unwrap();
+
+      assert_eq!(
+        cell_center_pixel(&renderer, (4, BOTTOM_ROW)),
+        WorldData::PLAYFIELD_COLOR
+      );
+    }
+
+    #[test]
+    fn ghost_is_drawn_with_the_set_alpha() {
+      let mut renderer = Renderer::new_in_memory(RENDERED_WINDOW_DIMENSIONS);
+      let mut world_data = game_with_piece(MinoType::O, (3, BOTTOM_ROW - 10));
+      let [red, green, blue] = Palette::default().color(MinoType::O);
+
+      world_data.settings.set_ghost_alpha(255);
+      world_data.render_game(&mut renderer).unwrap();
+      assert_eq!(
+        cell_center_pixel(&renderer, (4, BOTTOM_ROW)),
+        [red, green, blue, 255]
+      );
+
+      world_data.settings.set_ghost_alpha(0);
+      world_data.render_game(&mut renderer).unwrap();
+      assert_eq!(
+        cell_center_pixel(&renderer, (4, BOTTOM_ROW)),
+        WorldData::PLAYFIELD_COLOR
+      );
+    }
+
+    #[test]
+    fn background_gradient_follows_the_level() {
+      let mut renderer = Renderer::new_in_memory(RENDERED_WINDOW_DIMENSIONS);
+      let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+      let corner = (0, RENDERED_WINDOW_DIMENSIONS.height - 1);
+      let gradient_pixel = |level| {
+        let [red, green, blue] = Renderer::background_gradient_color(
+          corner.0,
+          corner.1,
+          &RENDERED_WINDOW_DIMENSIONS,
+          level,
+        );
+
+        [red, green, blue, 255]
+      };
+
+      world_data.render_game(&mut renderer).unwrap();
+      assert_eq!(pixel(&renderer, corner.0, corner.1), gradient_pixel(1));
+
+      while world_data.scoring.level() < 5 {
+        world_data.scoring.award_clear(4, None);
+      }
+
+      world_data.render_game(&mut renderer).unwrap();
+      assert_eq!(pixel(&renderer, corner.0, corner.1), gradient_pixel(5));
+      assert_ne!(gradient_pixel(5), gradient_pixel(1));
+    }
+
+    #[test]
+    fn hidden_cells_are_not_drawn() {
+      let mut renderer = Renderer::new_in_memory(RENDERED_WINDOW_DIMENSIONS);
+      let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+      let last_hidden_row = WorldData::HIDDEN_BOARD_HEIGHT as i32 - 1;
+      world_data.board[(last_hidden_row * BOARD_WIDTH) as usize] = Some(MinoType::T);
+      world_data.active_piece = Some(ActivePiece::new(MinoType::I, (3, 0)));
+
+      world_data.render_board(&mut renderer).unwrap();
+      world_data.render_active_piece(&mut renderer).unwrap();
+
+      assert!(renderer
+        .frame()
+        .chunks_exact(4)
+        .all(|pixel| pixel == BACKGROUND));
+    }
+
+    #[test]
+    fn visible_boundary_is_only_drawn_when_enabled() {
+      let mut renderer = Renderer::new_in_memory(RENDERED_WINDOW_DIMENSIONS);
+      let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+      let board_position = WorldData::board_position(&RENDERED_WINDOW_DIMENSIONS);
+      let index =
+        ((board_position.x + (board_position.y * RENDERED_WINDOW_DIMENSIONS.width)) * 4) as usize;
+
+      world_data.render_game(&mut renderer).unwrap();
+      let boundary_pixel = renderer.frame()[index..(index + 4)].to_vec();
+
+      world_data.settings.set_show_visible_boundary(true);
+      world_data.render_game(&mut renderer).unwrap();
+      assert_ne!(renderer.frame()[index..(index + 4)], boundary_pixel);
+    }
+
+    #[test]
+    fn entering_the_game_renders_an_empty_playfield() {
+      let assets = Assets::load_assets();
+      let mut renderer = Renderer::new_in_memory(RENDERED_WINDOW_DIMENSIONS);
+      let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+
+      for _ in 0..2 {
+        world_data
+          .update_world(Some(PlayerAction::MenuAction(MenuAction::Select)))
+          .unwrap();
+      }
+      assert_eq!(world_data.world_state(), WorldState::Countdown);
+
+      world_data.active_piece = None;
+      world_data.render(&assets, &mut renderer).unwrap();
+
+      assert_eq!(
+        cell_center_pixel(&renderer, (0, BOTTOM_ROW)),
+        WorldData::PLAYFIELD_COLOR
+      );
+    }
+
+    #[test]
+    fn next_queue_shows_the_upcoming_pieces() {
+      let mut renderer = Renderer::new_in_memory(RENDERED_WINDOW_DIMENSIONS);
+      let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+      world_data.bag = SevenBag::new(1234);
+      let preview_count = world_data.settings.preview_count();
+      let expected_pieces = world_data.bag.peek(preview_count);
+      let (box_position, _) =
+        WorldData::next_queue_box(&RENDERED_WINDOW_DIMENSIONS, preview_count).unwrap();
+      let preview_cell_size = WorldData::preview_cell_size(&RENDERED_WINDOW_DIMENSIONS);
+
+      world_data.render_game(&mut renderer).unwrap();
+
+      assert_eq!(expected_pieces.len(), preview_count);
+
+      for (slot, kind) in expected_pieces.into_iter().enumerate() {
+        let [red, green, blue] = Palette::default().color(kind);
+
+        for cell in WorldData::preview_cells(kind) {
+          let cell_position =
+            WorldData::next_queue_cell_position(&box_position, preview_cell_size, slot, cell);
+          let index =
+            ((cell_position.x + (cell_position.y * RENDERED_WINDOW_DIMENSIONS.width)) * 4) as usize;
+
+          assert_eq!(
+            renderer.frame()[index..(index + 4)],
+            [red, green, blue, 255],
+            "Slot {} should show {:?}",
+            slot,
+            kind
+          );
+        }
+      }
+    }
+
+    #[test]
+    fn next_queue_is_skipped_when_it_does_not_fit() {
+      assert!(WorldData::next_queue_box(&RENDERED_WINDOW_DIMENSIONS, 5).is_some());
+      assert!(WorldData::next_queue_box(&RENDERED_WINDOW_DIMENSIONS, 0).is_none());
+      assert!(WorldData::next_queue_box(&LogicalSize::new(100, 400), 5).is_none());
+    }
+
+    /// Returns the color of the first cell of the held piece in the hold box.
+    fn hold_cell_pixel(renderer: &Renderer, kind: MinoType) -> [u8; 4] {
+      let (box_position, _) = WorldData::hold_box(&RENDERED_WINDOW_DIMENSIONS).unwrap();
+      let preview_cell_size = WorldData::preview_cell_size(&RENDERED_WINDOW_DIMENSIONS);
+      let cell_position = WorldData::hold_cell_position(
+        &box_position,
+        preview_cell_size,
+        WorldData::preview_cells(kind)[0],
+      );
+      let index =
+        ((cell_position.x + (cell_position.y * RENDERED_WINDOW_DIMENSIONS.width)) * 4) as usize;
+
+      renderer.frame()[index..(index + 4)].try_into().unwrap()
+    }
+
+    #[test]
+    fn held_piece_is_drawn_in_the_hold_box() {
+      let mut renderer = Renderer::new_in_memory(RENDERED_WINDOW_DIMENSIONS);
+      let mut world_data = game_with_piece(MinoType::T, WorldData::SPAWN_ORIGIN);
+
+      world_data.hold_piece();
+      world_data.render_game(&mut renderer).unwrap();
+
+      assert_eq!(
+        hold_cell_pixel(&renderer, MinoType::T),
+        WorldData::HOLD_LOCKED_COLOR
+      );
+
+      world_data.hard_drop();
+      world_data.render_game(&mut renderer).unwrap();
+
+      let [red, green, blue] = Palette::default().color(MinoType::T);
+      assert_eq!(
+        hold_cell_pixel(&renderer, MinoType::T),
+        [red, green, blue, 255]
+      );
+    }
+
+    #[test]
+    fn hud_shows_the_current_score() {
+      let assets = Assets::load_assets();
+      let mut renderer = Renderer::new_in_memory(RENDERED_WINDOW_DIMENSIONS);
+      renderer
+        .load_font_from_bytes(assets.get_font("gadugi").unwrap(), "gadugi")
+        .unwrap();
+      let mut world_data = game_with_piece(MinoType::O, WorldData::SPAWN_ORIGIN);
+
+      world_data.render_game(&mut renderer).unwrap();
+      world_data.scoring.award_hard_drop(50);
+      world_data.render_game(&mut renderer).unwrap();
+
+      let hud = world_data.hud.borrow();
+      assert_eq!(hud.value_text_box("score").unwrap().text(), "100");
+    }
+
+    #[test]
+    fn cell_size_adapts_to_the_buffer() {
+      let buffer_dimensions = LogicalSize::new(250, 400);
+
+      assert_eq!(WorldData::cell_size(&buffer_dimensions), 20);
+      assert_eq!(
+        WorldData::board_position(&buffer_dimensions),
+        LogicalPosition::new(25, 0)
+      );
+
+      let buffer_dimensions = LogicalSize::new(100, 400);
+
+      assert_eq!(WorldData::cell_size(&buffer_dimensions), 10);
+      assert_eq!(
+        WorldData::board_position(&buffer_dimensions),
+        LogicalPosition::new(0, 100)
+      );
+    }
+
+    #[test]
+    fn game_renders_at_a_larger_resolution() {
+      let mut settings = GameSettings::default();
+      settings.set_render_resolution(RenderResolution::Large);
+      let buffer_dimensions = settings.render_resolution().dimensions();
+
+      let assets = Assets::load_assets();
+      let mut renderer = Renderer::new_in_memory(buffer_dimensions);
+      let mut world_data = WorldData::new(settings, TEST_SEED);
+
+      world_data.render(&assets, &mut renderer).unwrap();
+      world_data.transition(WorldState::Game).unwrap();
+      world_data.render(&assets, &mut renderer).unwrap();
+
+      let cell_size = WorldData::cell_size(&buffer_dimensions);
+      let board_position = WorldData::board_position(&buffer_dimensions);
+
+      assert_eq!(renderer.buffer_dimensions(), LogicalSize::new(375, 600));
+      assert_eq!(renderer.frame().len(), 375 * 600 * 4);
+      let right_margin =
+        buffer_dimensions.width - board_position.x - (cell_size * WorldData::VISIBLE_BOARD_WIDTH);
+
+      assert_eq!(cell_size, 30);
+      assert!(board_position.x.abs_diff(right_margin) <= 1);
+    }
+  }
+
+  mod state_transition_logic {
+    use super::*;
+
+    #[test]
+    fn legal_transition_succeeds() {
+      let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+
+      world_data.transition(WorldState::Game).unwrap();
+      assert_eq!(world_data.world_state(), WorldState::Game);
+
+      world_data.transition(WorldState::Menu).unwrap();
+      assert_eq!(world_data.world_state(), WorldState::Menu);
+    }
+
+    #[test]
+    fn illegal_transition_errors_and_keeps_the_state() {
+      let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+
+      assert!(world_data.transition(WorldState::Menu).is_err());
+      assert_eq!(world_data.world_state(), WorldState::Menu);
+    }
+  }
+
+  mod menu_logic {
+    use super::*;
+
+    fn apply_action(world_data: &mut WorldData, menu_action: MenuAction) {
+      world_data
+        .update_world(Some(PlayerAction::MenuAction(menu_action)))
+        .unwrap();
+    }
+
+    /// Returns a world with the settings menu open, having selected options from the main menu.
+    fn world_in_settings() -> WorldData {
+      let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+
+      apply_action(&mut world_data, MenuAction::Down);
+      apply_action(&mut world_data, MenuAction::Select);
+
+      world_data
+    }
+
+    #[test]
+    fn selecting_options_opens_the_settings_menu() {
+      let world_data = world_in_settings();
+
+      assert_eq!(world_data.world_state(), WorldState::Menu);
+      assert_eq!(
+        world_data.current_menu,
+        Some(Settings::GENERAL_SETTINGS_NAME)
+      );
+    }
+
+    #[test]
+    fn left_and_right_adjust_the_fps_slider() {
+      let mut world_data = world_in_settings();
+
+      apply_action(&mut world_data, MenuAction::Left);
+      assert_eq!(world_data.settings.fps(), 140);
+
+      apply_action(&mut world_data, MenuAction::Right);
+      apply_action(&mut world_data, MenuAction::Right);
+      assert_eq!(world_data.settings.fps(), 144);
+    }
+
+    #[test]
+    fn fps_slider_stops_at_its_bounds() {
+      let mut world_data = world_in_settings();
+
+      for _ in 0..50 {
+        apply_action(&mut world_data, MenuAction::Left);
+      }
+
+      assert_eq!(world_data.settings.fps(), 20);
+
+      for _ in 0..50 {
+        apply_action(&mut world_data, MenuAction::Right);
+      }
+
+      assert_eq!(world_data.settings.fps(), 144);
+    }
+
+    #[test]
+    fn fps_slider_starts_at_the_loaded_fps() {
+      let mut settings = GameSettings::default();
+      settings.set_fps(60);
+      let mut world_data = WorldData::new(settings, TEST_SEED);
+
+      apply_action(&mut world_data, MenuAction::Down);
+      apply_action(&mut world_data, MenuAction::Select);
+      apply_action(&mut world_data, MenuAction::Right);
+
+      assert_eq!(world_data.settings.fps(), 64);
+    }
+
+    #[test]
+    fn selecting_the_palette_option_cycles_palettes() {
+      let mut world_data = world_in_settings();
+
+      apply_action(&mut world_data, MenuAction::Down);
+      apply_action(&mut world_data, MenuAction::Select);
+
+      assert_eq!(world_data.settings.palette(), Palette::Deuteranopia);
+      assert_eq!(
+        world_data.current_menu,
+        Some(Settings::GENERAL_SETTINGS_NAME)
+      );
+    }
+
+    #[test]
+    fn toggles_write_through_to_the_settings() {
+      let mut world_data = world_in_settings();
+
+      (0..2).for_each(|_| apply_action(&mut world_data, MenuAction::Down));
+      apply_action(&mut world_data, MenuAction::Select);
+      assert!(!world_data.settings.show_grid());
+
+      apply_action(&mut world_data, MenuAction::Right);
+      assert!(world_data.settings.show_grid());
+
+      apply_action(&mut world_data, MenuAction::Down);
+      apply_action(&mut world_data, MenuAction::Left);
+      assert!(world_data.settings.muted());
+      assert!(world_data.audio.is_muted());
+
+      apply_action(&mut world_data, MenuAction::Down);
+      apply_action(&mut world_data, MenuAction::Select);
+      assert!(world_data.settings.fullscreen());
+
+      apply_action(&mut world_data, MenuAction::Down);
+      apply_action(&mut world_data, MenuAction::Select);
+      assert_eq!(world_data.settings.present_mode(), PresentMode::Immediate);
+
+      apply_action(&mut world_data, MenuAction::Down);
+      apply_action(&mut world_data, MenuAction::Select);
+      assert!(!world_data.settings.ghost_enabled());
+
+      apply_action(&mut world_data, MenuAction::Down);
+      apply_action(&mut world_data, MenuAction::Right);
+      assert_eq!(world_data.settings.ghost_alpha(), 0x55 + 17);
+    }
+
+    #[test]
+    fn controls_submenus_are_opened_and_backed_out_of() {
+      let mut world_data = world_in_settings();
+
+      (0..8).for_each(|_| apply_action(&mut world_data, MenuAction::Down));
+      apply_action(&mut world_data, MenuAction::Select);
+      assert_eq!(world_data.current_menu, Some(Settings::GAME_CONTROLS_NAME));
+
+      apply_action(&mut world_data, MenuAction::Back);
+      assert_eq!(
+        world_data.current_menu,
+        Some(Settings::GENERAL_SETTINGS_NAME)
+      );
+
+      apply_action(&mut world_data, MenuAction::Down);
+      apply_action(&mut world_data, MenuAction::Select);
+      assert_eq!(world_data.current_menu, Some(Settings::MENU_CONTROLS_NAME));
+
+      apply_action(&mut world_data, MenuAction::Back);
+      apply_action(&mut world_data, MenuAction::Back);
+      assert_eq!(world_data.current_menu, Some(MainMenu::MENU_NAME));
+    }
+
+    #[test]
+    fn holding_down_repeats_until_released() {
+      use crate::general_data::timer::clock;
+
+      let mut world_data = world_in_settings();
+      let menu_das = Duration::from_millis(world_data.settings.menu_das_ms());
+      let menu_arr = Duration::from_millis(world_data.settings.menu_arr_ms());
+      let cursor_position =
+        |world_data: &WorldData| world_data.current_menu().unwrap().cursor_position();
+
+      world_data.update_held_menu_actions(&[MenuAction::Down]);
+      world_data.update_world(None).unwrap();
+      assert_eq!(cursor_position(&world_data), 0);
+
+      clock::advance(menu_das);
+      world_data.update_world(None).unwrap();
+      assert_eq!(cursor_position(&world_data), 1);
+
+      for _ in 0..3 {
+        clock::advance(menu_arr);
+        world_data.update_world(None).unwrap();
+      }
+
+      assert_eq!(cursor_position(&world_data), 4);
+
+      world_data.update_held_menu_actions(&[]);
+      clock::advance(menu_das * 2);
+      world_data.update_world(None).unwrap();
+
+      assert_eq!(cursor_position(&world_data), 4);
+    }
+
+    #[test]
+    fn cursor_is_kept_when_returning_to_a_menu() {
+      let mut world_data = world_in_settings();
+
+      assert!(world_data
+        .current_menu_mut()
+        .unwrap()
+        .select_by_name("fullscreen"));
+
+      apply_action(&mut world_data, MenuAction::Back);
+      apply_action(&mut world_data, MenuAction::Select);
+
+      let settings_menu = world_data.current_menu().unwrap();
+
+      assert_eq!(settings_menu.name(), Settings::GENERAL_SETTINGS_NAME);
+      assert_eq!(
+        Some(settings_menu.cursor_position()),
+        settings_menu.index_of("fullscreen")
+      );
+    }
+
+    #[test]
+    fn back_returns_through_the_menu_stack() {
+      let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+
+      world_data.push_menu(Settings::GENERAL_SETTINGS_NAME);
+      world_data.push_menu(Settings::MENU_CONTROLS_NAME);
+
+      apply_action(&mut world_data, MenuAction::Back);
+      assert_eq!(
+        world_data.current_menu,
+        Some(Settings::GENERAL_SETTINGS_NAME)
+      );
+
+      apply_action(&mut world_data, MenuAction::Back);
+      assert_eq!(world_data.current_menu, Some(MainMenu::MENU_NAME));
+      assert!(world_data.menu_stack.is_empty());
+    }
+
+    #[test]
+    fn back_from_the_main_menu_only_exits_when_set() {
+      let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+      let back = Some(PlayerAction::MenuAction(MenuAction::Back));
+
+      assert!(!world_data.update_world(back.clone()).unwrap());
+      assert_eq!(world_data.current_menu, Some(MainMenu::MENU_NAME));
+
+      world_data.settings.set_back_exits_main_menu(true);
+
+      assert!(world_data.update_world(back).unwrap());
+    }
+
+    #[test]
+    fn settings_menus_render() {
+      let assets = Assets::load_assets();
+      let mut renderer = Renderer::new_in_memory(RENDERED_WINDOW_DIMENSIONS);
+      renderer
+        .load_font_from_bytes(assets.get_font("gadugi").unwrap(), "gadugi")
+        .unwrap();
+      let mut world_data = world_in_settings();
+
+      for menu_name in [
+        Settings::GENERAL_SETTINGS_NAME,
+        Settings::GAME_CONTROLS_NAME,
+        Settings::MENU_CONTROLS_NAME,
+      ] {
+        world_data.current_menu = Some(menu_name);
+
+        world_data.render(&assets, &mut renderer).unwrap();
+      }
+    }
+  }
+
+  mod line_clear_logic {
+    use super::*;
+
+    const BOARD_WIDTH: usize = WorldData::LOGICAL_BOARD_WIDTH as usize;
+    const BOTTOM_ROW: usize = WorldData::LOGICAL_BOARD_HEIGHT as usize - 1;
+
+    fn row(world_data: &WorldData, y: usize) -> &[Option<MinoType>] {
+      &world_data.board[(y * BOARD_WIDTH)..((y + 1) * BOARD_WIDTH)]
+    }
+
+    fn fill_row(world_data: &mut WorldData, y: usize, kind: MinoType) {
+      world_data.board[(y * BOARD_WIDTH)..((y + 1) * BOARD_WIDTH)].fill(Some(kind));
+    }
+
+    #[test]
+    fn board_without_full_rows_is_unchanged() {
+      let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+      world_data.board[BOTTOM_ROW * BOARD_WIDTH] = Some(MinoType::T);
+      let expected_board = world_data.board.clone();
+
+      assert_eq!(world_data.clear_lines(), 0);
+      assert_eq!(world_data.board, expected_board);
+    }
+
+    #[test]
+    fn non_adjacent_full_rows_collapse_the_rows_above() {
+      let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+
+      fill_row(&mut world_data, BOTTOM_ROW, MinoType::I);
+      world_data.board[(BOTTOM_ROW - 1) * BOARD_WIDTH] = Some(MinoType::J);
+      fill_row(&mut world_data, BOTTOM_ROW - 2, MinoType::I);
+      world_data.board[((BOTTOM_ROW - 3) * BOARD_WIDTH) + 4] = Some(MinoType::S);
+      world_data.board[((BOTTOM_ROW - 3) * BOARD_WIDTH) + 5] = Some(MinoType::Z);
+
+      assert_eq!(world_data.clear_lines(), 2);
+
+      let mut expected_bottom_row = vec![None; BOARD_WIDTH];
+      expected_bottom_row[0] = Some(MinoType::J);
+      let mut expected_second_row = vec![None; BOARD_WIDTH];
+      expected_second_row[4] = Some(MinoType::S);
+      expected_second_row[5] = Some(MinoType::Z);
+
+      assert_eq!(row(&world_data, BOTTOM_ROW), expected_bottom_row);
+      assert_eq!(row(&world_data, BOTTOM_ROW - 1), expected_second_row);
+      assert!(world_data.board[..((BOTTOM_ROW - 1) * BOARD_WIDTH)]
+        .iter()
+        .all(Option::is_none));
+      assert_eq!(
+        world_data.board.len(),
+        BOARD_WIDTH * WorldData::LOGICAL_BOARD_HEIGHT as usize
+      );
+    }
+
+    #[test]
+    fn four_rows_clear_at_once() {
+      let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+
+      for y in (BOTTOM_ROW - 3)..=BOTTOM_ROW {
+        fill_row(&mut world_data, y, MinoType::I);
+      }
+      world_data.board[((BOTTOM_ROW - 4) * BOARD_WIDTH) + 9] = Some(MinoType::L);
+
+      assert_eq!(world_data.clear_lines(), 4);
+      assert_eq!(row(&world_data, BOTTOM_ROW)[9], Some(MinoType::L));
+      assert_eq!(
+        world_data
+          .board
+          .iter()
+          .filter(|cell| cell.is_some())
+          .count(),
+        1
+      );
+    }
+  }
+
+  mod hard_drop_logic {
+    use super::*;
+
+    const BOARD_WIDTH: i32 = WorldData::LOGICAL_BOARD_WIDTH as i32;
+    const BOTTOM_ROW: i32 = WorldData::LOGICAL_BOARD_HEIGHT as i32 - 1;
+
+    fn cell(world_data: &WorldData, x: i32, y: i32) -> Option<MinoType> {
+      world_data.board[(x + (y * BOARD_WIDTH)) as usize]
+    }
+
+    #[test]
+    fn piece_lands_on_the_floor_and_a_new_piece_spawns() {
+      let mut world_data = game_with_piece(MinoType::T, WorldData::SPAWN_ORIGIN);
+
+      world_data
+        .update_world(Some(PlayerAction::GameAction(vec![GameAction::HardDrop])))
+        .unwrap();
+
+      for (x, y) in [
+        (4, BOTTOM_ROW - 1),
+        (3, BOTTOM_ROW),
+        (4, BOTTOM_ROW),
+        (5, BOTTOM_ROW),
+      ] {
+        assert_eq!(cell(&world_data, x, y), Some(MinoType::T));
+      }
+      assert_eq!(world_data.board.iter().flatten().count(), 4);
+
+      let next_piece = world_data.active_piece.unwrap();
+      assert_eq!(next_piece.origin, WorldData::SPAWN_ORIGIN);
+      assert_eq!(next_piece.rotation, Rotation::Spawn);
+
+      let dropped_cells = (BOTTOM_ROW - 1 - WorldData::SPAWN_ORIGIN.1) as u32;
+      assert_eq!(
+        world_data.scoring.score(),
+        dropped_cells * Scoring::HARD_DROP_POINTS
+      );
+      assert!(!world_data.game_over);
+    }
+
+    #[test]
+    fn completed_lines_are_cleared_on_lock() {
+      let mut world_data = game_with_piece(MinoType::I, WorldData::SPAWN_ORIGIN);
+
+      // Leave a gap along the bottom for the flat I piece to complete.
+      for x in 0..BOARD_WIDTH {
+        if !(3..=6).contains(&x) {
+          world_data.board[(x + (BOTTOM_ROW * BOARD_WIDTH)) as usize] = Some(MinoType::O);
+        }
+      }
+
+      world_data.hard_drop();
+
+      assert!(world_data.board.iter().all(Option::is_none));
+      assert_eq!(world_data.scoring.lines(), 1);
+    }
+
+    #[test]
+    fn locking_above_the_visible_board_is_game_over() {
+      let mut world_data = game_with_piece(MinoType::T, WorldData::SPAWN_ORIGIN);
+      world_data.settings.set_top_out_grace_placements(0);
+
+      // A tower that reaches the top of the visible board under the spawn position.
+      for y in WorldData::HIDDEN_BOARD_HEIGHT as i32..=BOTTOM_ROW {
+        for x in 3..=5 {
+          world_data.board[(x + (y * BOARD_WIDTH)) as usize] = Some(MinoType::O);
+        }
+      }
+
+      world_data.hard_drop();
+
+      assert!(world_data.game_over);
+      assert!(world_data.active_piece.is_none());
+    }
+
+    /// Fills the visible board up to its top row, leaving column 0 empty and the spawn columns 3-5 filled.
+    ///
+    /// Returns the world with a T piece at the spawn position, which will lock out when dropped.
+    fn topped_out_world(grace_placements: u32) -> WorldData {
+      let mut world_data = game_with_piece(MinoType::T, WorldData::SPAWN_ORIGIN);
+      world_data
+        .settings
+        .set_top_out_grace_placements(grace_placements);
+
+      for y in WorldData::HIDDEN_BOARD_HEIGHT as i32..=BOTTOM_ROW {
+        for x in 1..BOARD_WIDTH {
+          world_data.board[(x + (y * BOARD_WIDTH)) as usize] = Some(MinoType::O);
+        }
+      }
+
+      world_data
+    }
+
+    #[test]
+    fn recoverable_top_out_continues_the_game() {
+      let mut world_data = topped_out_world(1);
+
+      world_data.hard_drop();
+
+      assert!(!world_data.game_over);
+      assert_eq!(world_data.top_out_grace, Some(0));
+
+      // A vertical I piece down the empty column clears the 4 rows, dropping the stack out of the hidden rows.
+      world_data.active_piece = Some(ActivePiece {
+        kind: MinoType::I,
+        rotation: Rotation::Left,
+        origin: (-1, BOTTOM_ROW - 3),
+      });
+      world_data.lock_active_piece();
+
+      assert!(!world_data.game_over);
+      assert_eq!(world_data.top_out_grace, None);
+      assert!(!world_data.is_topped_out());
+      assert!(world_data.active_piece.is_some());
+    }
+
+    #[test]
+    fn unrecoverable_top_out_ends_the_game() {
+      let mut world_data = topped_out_world(1);
+
+      world_data.hard_drop();
+      assert!(!world_data.game_over);
+
+      // Nothing is cleared on the grace placement.
+      world_data.active_piece = Some(ActivePiece::new(MinoType::O, (-1, 0)));
+      world_data.lock_active_piece();
+
+      assert!(world_data.game_over);
+    }
+
+    #[test]
+    fn blocked_spawn_moves_the_piece_up() {
+      let mut world_data = game_with_piece(MinoType::T, WorldData::SPAWN_ORIGIN);
+      world_data.active_piece = None;
+
+      // Block the spawn rows.
+      let spawn_y = WorldData::SPAWN_ORIGIN.1;
+      for x in 0..BOARD_WIDTH {
+        world_data.board[(x + ((spawn_y + 1) * BOARD_WIDTH)) as usize] = Some(MinoType::O);
+      }
+
+      world_data.spawn_next_piece();
+
+      let active_piece = world_data.active_piece.unwrap();
+      assert!(active_piece.origin.1 < spawn_y);
+      assert!(!world_data.collides(
+        active_piece.kind,
+        active_piece.rotation,
+        active_piece.origin
+      ));
+    }
+
+    #[test]
+    fn ghost_origin_matches_the_hard_drop_landing() {
+      let mut world_data = game_with_piece(MinoType::J, WorldData::SPAWN_ORIGIN);
+
+      // A step in the stack the J piece's hook lands on, with a gap on the right so nothing clears.
+      for x in 0..(BOARD_WIDTH - 1) {
+        let stack_height = if x < 4 { 3 } else { 1 };
+
+        for y in (BOTTOM_ROW - stack_height + 1)..=BOTTOM_ROW {
+          world_data.board[(x + (y * BOARD_WIDTH)) as usize] = Some(MinoType::O);
+        }
+      }
+
+      let ghost_origin = world_data.ghost_origin().unwrap();
+      let landing_piece = ActivePiece {
+        origin: ghost_origin,
+        ..world_data.active_piece.unwrap()
+      };
+
+      // The hook rests on the taller part of the stack.
+      assert_eq!(ghost_origin, (3, BOTTOM_ROW - 4));
+
+      world_data.hard_drop();
+
+      for (x, y) in landing_piece.cells() {
+        assert_eq!(cell(&world_data, x, y), Some(MinoType::J));
+      }
+    }
+
+    #[test]
+    fn no_ghost_without_an_active_piece() {
+      let world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+
+      assert_eq!(world_data.ghost_origin(), None);
+    }
+
+    #[test]
+    fn hard_drop_without_an_active_piece_does_nothing() {
+      let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+
+      world_data.hard_drop();
+
+      assert!(world_data.board.iter().all(Option::is_none));
+      assert_eq!(world_data.scoring.score(), 0);
+    }
+  }
+
+  mod gravity_logic {
+    use super::*;
+    use crate::general_data::timer::clock;
+
+    const BOTTOM_ROW: i32 = WorldData::LOGICAL_BOARD_HEIGHT as i32 - 1;
+
+    #[test]
+    fn piece_falls_a_row_each_gravity_tick() {
+      let start_origin = (3, 25);
+      let mut world_data = game_with_piece(MinoType::T, start_origin);
+      let gravity_duration = WorldData::gravity_duration(world_data.scoring.level());
+
+      // The first update starts the gravity timer.
+      world_data.update_game(None).unwrap();
+
+      for _ in 0..3 {
+        clock::advance(gravity_duration);
+        world_data.update_game(None).unwrap();
+      }
+
+      assert_eq!(
+        world_data.active_piece.unwrap().origin,
+        (start_origin.0, start_origin.1 + 3)
+      );
+    }
+
+    #[test]
+    fn gravity_speeds_up_with_level() {
+      assert!(WorldData::gravity_duration(2) < WorldData::gravity_duration(1));
+      assert_eq!(
+        WorldData::gravity_duration(100),
+        WorldData::gravity_duration(15)
+      );
+    }
+
+    #[test]
+    fn grounded_piece_locks_after_the_lock_delay() {
+      // The T piece's lowest cells sit on the bottom row.
+      let grounded_origin = (3, BOTTOM_ROW - 1);
+      let mut world_data = game_with_piece(MinoType::T, grounded_origin);
+      let gravity_duration = WorldData::gravity_duration(world_data.scoring.level());
+
+      world_data.update_game(None).unwrap();
+      clock::advance(gravity_duration);
+      world_data.update_game(None).unwrap();
+
+      assert_eq!(world_data.active_piece.unwrap().origin, grounded_origin);
+      assert!(world_data.board.iter().all(Option::is_none));
+
+      clock::advance(Duration::from_millis(world_data.settings.lock_delay_ms()));
+      world_data.update_game(None).unwrap();
+
+      assert!(world_data.board.iter().any(Option::is_some));
+    }
+
+    /// Returns a world with a T piece resting on the floor with its lock delay running.
+    fn grounded_world(lock_reset_cap: u32) -> WorldData {
+      let mut world_data = game_with_piece(MinoType::T, (3, BOTTOM_ROW - 1));
+      world_data.settings.set_lock_reset_cap(lock_reset_cap);
+      let gravity_duration = WorldData::gravity_duration(world_data.scoring.level());
+
+      world_data.update_game(None).unwrap();
+      clock::advance(gravity_duration);
+      world_data.update_game(None).unwrap();
+
+      assert!(world_data.lock_delay_timer().running());
+
+      world_data
+    }
+
+    fn move_piece(world_data: &mut WorldData, game_action: GameAction) {
+      world_data
+        .update_game(Some(PlayerAction::GameAction(vec![game_action])))
+        .unwrap();
+    }
+
+    #[test]
+    fn moving_a_grounded_piece_restarts_the_lock_delay() {
+      let mut world_data = grounded_world(15);
+      let half_lock_delay = Duration::from_millis(world_data.settings.lock_delay_ms() / 2);
+
+      for game_action in [GameAction::MoveLeft, GameAction::MoveRight] {
+        clock::advance(half_lock_delay);
+        move_piece(&mut world_data, game_action);
+      }
+
+      clock::advance(half_lock_delay);
+      world_data.update_game(None).unwrap();
+
+      assert!(world_data.board.iter().all(Option::is_none));
+      assert_eq!(world_data.lock_resets, 2);
+    }
+
+    #[test]
+    fn grounded_piece_locks_once_the_reset_cap_is_exceeded() {
+      let lock_reset_cap = 3;
+      let mut world_data = grounded_world(lock_reset_cap);
+
+      for game_action in [GameAction::MoveLeft, GameAction::MoveRight]
+        .into_iter()
+        .cycle()
+        .take(lock_reset_cap as usize)
+      {
+        move_piece(&mut world_data, game_action);
+
+        assert!(world_data.board.iter().all(Option::is_none));
+      }
+
+      move_piece(&mut world_data, GameAction::MoveLeft);
+
+      assert!(world_data.board.iter().any(Option::is_some));
+      assert_eq!(world_data.lock_resets, 0);
+    }
+  }
+
+  mod game_over_logic {
+    use super::*;
+
+    /// Returns a game where the next piece can't spawn, with every row filled but the first column.
+    fn world_about_to_top_out() -> WorldData {
+      let mut world_data = game_with_piece(MinoType::O, WorldData::SPAWN_ORIGIN);
+      world_data.active_piece = None;
+
+      for (index, cell) in world_data.board.iter_mut().enumerate() {
+        if index % WorldData::LOGICAL_BOARD_WIDTH as usize != 0 {
+          *cell = Some(MinoType::Z);
+        }
+      }
+
+      world_data.spawn_next_piece();
+
+      world_data
+    }
+
+    fn apply_action(world_data: &mut WorldData, menu_action: MenuAction) {
+      world_data
+        .update_world(Some(PlayerAction::MenuAction(menu_action)))
+        .unwrap();
+    }
+
+    #[test]
+    fn topping_out_moves_to_the_game_over_screen() {
+      let mut world_data = world_about_to_top_out();
+
+      assert!(world_data.game_over);
+
+      world_data.update_world(None).unwrap();
+
+      assert_eq!(world_data.world_state(), WorldState::GameOver);
+      assert_eq!(world_data.current_menu, Some(GameOverMenu::MENU_NAME));
+    }
+
+    #[test]
+    fn restart_resets_the_game() {
+      let mut world_data = world_about_to_top_out();
+      world_data.held = Some(MinoType::I);
+      world_data.scoring.award_hard_drop(10);
+      world_data.update_world(None).unwrap();
+      world_data.submit_name();
+
+      apply_action(&mut world_data, MenuAction::Select);
+
+      assert_eq!(world_data.world_state(), WorldState::Countdown);
+      assert!(world_data.board.iter().all(Option::is_none));
+      assert!(world_data.active_piece.is_some());
+      assert!(!world_data.game_over);
+      assert_eq!(world_data.held, None);
+      assert_eq!(world_data.scoring, Scoring::new());
+      assert_eq!(world_data.current_menu, Some(MainMenu::MENU_NAME));
+    }
+
+    #[test]
+    fn quit_returns_to_the_main_menu() {
+      let mut world_data = world_about_to_top_out();
+      world_data.update_world(None).unwrap();
+
+      apply_action(&mut world_data, MenuAction::Down);
+      apply_action(&mut world_data, MenuAction::Select);
+
+      assert_eq!(world_data.world_state(), WorldState::Menu);
+      assert_eq!(world_data.current_menu, Some(MainMenu::MENU_NAME));
+      assert!(world_data.board.iter().all(Option::is_none));
+      assert!(world_data.active_piece.is_none());
+    }
+
+    #[test]
+    fn game_over_screen_renders() {
+      let assets = Assets::load_assets();
+      let mut renderer = Renderer::new_in_memory(RENDERED_WINDOW_DIMENSIONS);
+      renderer
+        .load_font_from_bytes(assets.get_font("gadugi").unwrap(), "gadugi")
+        .unwrap();
+      let mut world_data = world_about_to_top_out();
+      world_data.update_world(None).unwrap();
+
+      world_data.render(&assets, &mut renderer).unwrap();
+    }
+
+    #[test]
+    fn qualifying_score_asks_for_a_name() {
+      let mut world_data = world_about_to_top_out();
+      world_data.scoring.award_hard_drop(10);
+      world_data.update_world(None).unwrap();
+
+      assert!(world_data.is_entering_name());
+
+      // Menu keys are typed into the name instead of picking an option.
+      apply_action(&mut world_data, MenuAction::Select);
+      assert_eq!(world_data.world_state(), WorldState::GameOver);
+
+      for character in "AB C!".chars() {
+        world_data.type_name_character(character);
+      }
+      world_data.erase_name_character();
+
+      assert_eq!(world_data.submit_name(), Some(0));
+      assert!(!world_data.is_entering_name());
+
+      let entry = &world_data.leaderboard().entries()[0];
+      assert_eq!(entry.name, "ABC");
+      assert_eq!(entry.score, world_data.scoring.score());
+      assert_eq!(entry.game_mode, world_data.game_mode());
+    }
+
+    #[test]
+    fn scores_off_the_leaderboard_skip_the_name() {
+      let mut world_data = world_about_to_top_out();
+      world_data.update_world(None).unwrap();
+
+      assert!(!world_data.is_entering_name());
+      assert_eq!(world_data.submit_name(), None);
+      assert!(world_data.leaderboard().entries().is_empty());
+    }
+  }
+
+  mod auto_shift_logic {
+    use super::*;
+    use crate::general_data::timer::clock;
+
+    fn held_left_world() -> WorldData {
+      let mut world_data = game_with_piece(MinoType::O, (4, 25));
+      world_data.settings.set_das_ms(100);
+      world_data.settings.set_arr_ms(20);
+      world_data.update_held_actions(&[GameAction::MoveLeft]);
+
+      world_data
+    }
+
+    /// Advances the clock by the given milliseconds a millisecond at a time, returning how many moves were produced.
+    fn hold_for(world_data: &mut WorldData, milliseconds: u64) -> usize {
+      (0..milliseconds)
+        .map(|_| {
+          clock::advance(Duration::from_millis(1));
+
+          world_data.auto_shift_actions().len()
+        })
+        .sum()
+    }
+
+    #[test]
+    fn nothing_moves_before_the_das_delay() {
+      let mut world_data = held_left_world();
+
+      assert_eq!(hold_for(&mut world_data, 99), 0);
+    }
+
+    #[test]
+    fn moves_repeat_at_the_arr_after_the_das_delay() {
+      let mut world_data = held_left_world();
+
+      // One move after the DAS delay, then one every 20ms.
+      assert_eq!(hold_for(&mut world_data, 100), 1);
+      assert_eq!(hold_for(&mut world_data, 100), 5);
+    }
+
+    #[test]
+    fn releasing_the_direction_stops_the_moves() {
+      let mut world_data = held_left_world();
+      hold_for(&mut world_data, 150);
+
+      world_data.update_held_actions(&[]);
+
+      assert_eq!(hold_for(&mut world_data, 200), 0);
+    }
+
+    #[test]
+    fn held_moves_shift_the_piece() {
+      let mut world_data = held_left_world();
+
+      clock::advance(Duration::from_millis(100));
+      world_data.update_world(None).unwrap();
+
+      assert_eq!(world_data.active_piece.unwrap().origin.0, 3);
+    }
+
+    #[test]
+    fn zero_arr_moves_to_the_wall() {
+      let mut world_data = held_left_world();
+      world_data.settings.set_arr_ms(0);
+
+      clock::advance(Duration::from_millis(100));
+      world_data.update_world(None).unwrap();
+      clock::advance(Duration::from_millis(1));
+      world_data.update_world(None).unwrap();
+
+      let active_piece = world_data.active_piece.unwrap();
+      assert!(world_data.collides(
+        active_piece.kind,
+        active_piece.rotation,
+        (active_piece.origin.0 - 1, active_piece.origin.1)
+      ));
+    }
+  }
+
+  mod seed_logic {
+    use super::*;
+
+    fn spawned_pieces(world_data: &mut WorldData, count: usize) -> Vec<MinoType> {
+      (0..count)
+        .map(|_| {
+          world_data.spawn_next_piece();
+
+          world_data.active_piece.unwrap().kind
+        })
+        .collect()
+    }
+
+    #[test]
+    fn same_seed_spawns_the_same_pieces() {
+      let mut first_world = WorldData::new(GameSettings::default(), TEST_SEED);
+      let mut second_world = WorldData::new(GameSettings::default(), TEST_SEED);
+
+      assert_eq!(first_world.seed(), TEST_SEED);
+      assert_eq!(
+        spawned_pieces(&mut first_world, 20),
+        spawned_pieces(&mut second_world, 20)
+      );
+    }
+
+    #[test]
+    fn different_seeds_spawn_different_pieces() {
+      let mut first_world = WorldData::new(GameSettings::default(), TEST_SEED);
+      let mut second_world = WorldData::new(GameSettings::default(), TEST_SEED + 1);
+
+      assert_ne!(
+        spawned_pieces(&mut first_world, 20),
+        spawned_pieces(&mut second_world, 20)
+      );
+    }
+
+    #[test]
+    fn restarted_games_follow_from_the_first_seed() {
+      let restarted_world = || {
+        let mut world_data = game_with_piece(MinoType::T, WorldData::SPAWN_ORIGIN);
+        world_data.game_over = true;
+        world_data.update_world(None).unwrap();
+        world_data
+          .update_world(Some(PlayerAction::MenuAction(MenuAction::Select)))
+          .unwrap();
+
+        world_data
+      };
+      let mut first_world = restarted_world();
+      let mut second_world = restarted_world();
+
+      assert_ne!(first_world.seed(), TEST_SEED);
+      assert_eq!(first_world.seed(), second_world.seed());
+      assert_eq!(first_world.bag.peek(14), second_world.bag.peek(14));
+      assert_eq!(
+        spawned_pieces(&mut first_world, 20),
+        spawned_pieces(&mut second_world, 20)
+      );
+    }
+  }
+
+  mod countdown_logic {
+    use super::*;
+    use crate::general_data::timer::clock;
+
+    fn move_left(world_data: &mut WorldData) {
+      world_data
+        .update_world(Some(PlayerAction::GameAction(vec![GameAction::MoveLeft])))
+        .unwrap();
+    }
+
+    /// Returns a world counting down to the start of a game, having picked a mode from the main menu.
+    fn world_in_countdown(settings: GameSettings) -> WorldData {
+      let mut world_data = WorldData::new(settings, TEST_SEED);
+
+      for _ in 0..2 {
+        world_data
+          .update_world(Some(PlayerAction::MenuAction(MenuAction::Select)))
+          .unwrap();
+      }
+
+      world_data
+    }
+
+    #[test]
+    fn input_is_ignored_until_the_countdown_finishes() {
+      let mut world_data = world_in_countdown(GameSettings::default());
+      let countdown = Duration::from_millis(world_data.settings.countdown_ms());
+      let spawn_origin = world_data.active_piece.unwrap().origin;
+
+      assert_eq!(world_data.world_state(), WorldState::Countdown);
+
+      move_left(&mut world_data);
+      clock::advance(countdown / 2);
+      move_left(&mut world_data);
+
+      assert_eq!(world_data.world_state(), WorldState::Countdown);
+      assert_eq!(world_data.active_piece.unwrap().origin, spawn_origin);
+
+      clock::advance(countdown / 2);
+      move_left(&mut world_data);
+
+      assert_eq!(world_data.world_state(), WorldState::Game);
+      assert_eq!(
+        world_data.active_piece.unwrap().origin,
+        (spawn_origin.0 - 1, spawn_origin.1)
+      );
+    }
+
+    #[test]
+    fn no_countdown_starts_the_game_right_away() {
+      let mut settings = GameSettings::default();
+      settings.set_countdown_ms(0);
+
+      let world_data = world_in_countdown(settings);
+
+      assert_eq!(world_data.world_state(), WorldState::Game);
+    }
+  }
+
+  mod quit_to_menu_logic {
+    use super::*;
+
+    fn apply_action(world_data: &mut WorldData, menu_action: MenuAction) {
+      world_data
+        .update_world(Some(PlayerAction::MenuAction(menu_action)))
+        .unwrap();
+    }
+
+    fn apply_game_actions(world_data: &mut WorldData, game_actions: Vec<GameAction>) {
+      world_data
+        .update_world(Some(PlayerAction::GameAction(game_actions)))
+        .unwrap();
+    }
+
+    /// Returns a world in a game started from the main menu, skipping the countdown.
+    fn world_in_game() -> WorldData {
+      let mut settings = GameSettings::default();
+      settings.set_countdown_ms(0);
+      let mut world_data = WorldData::new(settings, TEST_SEED);
+
+      for _ in 0..2 {
+        apply_action(&mut world_data, MenuAction::Select);
+      }
+
+      world_data
+    }
+
+    #[test]
+    fn quitting_clears_the_game_and_returns_to_the_main_menu() {
+      let mut world_data = world_in_game();
+
+      apply_game_actions(&mut world_data, vec![GameAction::HardDrop]);
+      apply_game_actions(&mut world_data, vec![GameAction::Hold]);
+      assert!(world_data.board.iter().any(Option::is_some));
+
+      apply_game_actions(&mut world_data, vec![GameAction::QuitToMenu]);
+
+      assert_eq!(world_data.world_state(), WorldState::Menu);
+      assert_eq!(world_data.current_menu, Some(MainMenu::MENU_NAME));
+      assert!(world_data.menu_stack.is_empty());
+      assert!(world_data.board.iter().all(Option::is_none));
+      assert!(world_data.active_piece.is_none());
+      assert_eq!(world_data.held, None);
+      assert_eq!(world_data.scoring, Scoring::new());
+      assert!(world_data.timers.is_empty());
+    }
+
+    #[test]
+    fn a_new_game_can_be_started_after_quitting() {
+      let mut world_data = world_in_game();
+
+      apply_game_actions(&mut world_data, vec![GameAction::QuitToMenu]);
+
+      for _ in 0..2 {
+        apply_action(&mut world_data, MenuAction::Select);
+      }
+
+      assert_eq!(world_data.world_state(), WorldState::Game);
+      assert!(world_data.active_piece.is_some());
+
+      apply_game_actions(&mut world_data, vec![GameAction::HardDrop]);
+      assert!(world_data.board.iter().any(Option::is_some));
+    }
+  }
+
+  mod pause_logic {
+    use super::*;
+    use crate::general_data::timer::clock;
+
+    fn apply_action(world_data: &mut WorldData, menu_action: MenuAction) {
+      world_data
+        .update_world(Some(PlayerAction::MenuAction(menu_action)))
+        .unwrap();
+    }
+
+    fn apply_game_actions(world_data: &mut WorldData, game_actions: Vec<GameAction>) {
+      world_data
+        .update_world(Some(PlayerAction::GameAction(game_actions)))
+        .unwrap();
+    }
+
+    /// Returns a world paused in a game started from the main menu, skipping the countdown.
+    fn paused_world() -> WorldData {
+      let mut settings = GameSettings::default();
+      settings.set_countdown_ms(0);
+      let mut world_data = WorldData::new(settings, TEST_SEED);
+
+      for _ in 0..2 {
+        apply_action(&mut world_data, MenuAction::Select);
+      }
+
+      world_data.update_world(None).unwrap();
+      apply_game_actions(&mut world_data, vec![GameAction::Pause]);
+
+      world_data
+    }
+
+    #[test]
+    fn pausing_opens_the_pause_menu() {
+      let world_data = paused_world();
+
+      assert_eq!(world_data.world_state(), WorldState::Menu);
+      assert_eq!(world_data.current_menu, Some(PauseMenu::MENU_NAME));
+      assert!(world_data.active_piece.is_some());
+    }
+
+    #[test]
+    fn no_game_time_passes_while_paused() {
+      let mut world_data = paused_world();
+      let paused_time = world_data.game_time();
+
+      clock::advance(Duration::from_secs(5));
+      world_data.update_world(None).unwrap();
+
+      assert_eq!(world_data.game_time(), paused_time);
+
+      apply_action(&mut world_data, MenuAction::Select);
+
+      assert_eq!(world_data.world_state(), WorldState::Game);
+      assert!(world_data.game_time().unwrap() < paused_time.unwrap() + Duration::from_secs(1));
+    }
+
+    #[test]
+    fn back_resumes_the_game() {
+      let mut world_data = paused_world();
+      let active_piece = world_data.active_piece;
+
+      apply_action(&mut world_data, MenuAction::Back);
+
+      assert_eq!(world_data.world_state(), WorldState::Game);
+      assert_eq!(world_data.current_menu, Some(MainMenu::MENU_NAME));
+      assert_eq!(world_data.active_piece, active_piece);
+    }
+
+    #[test]
+    fn quit_returns_to_the_main_menu() {
+      let mut world_data = paused_world();
+
+      apply_action(&mut world_data, MenuAction::Down);
+      apply_action(&mut world_data, MenuAction::Select);
+
+      assert_eq!(world_data.world_state(), WorldState::Menu);
+      assert_eq!(world_data.current_menu, Some(MainMenu::MENU_NAME));
+      assert!(world_data.menu_stack.is_empty());
+      assert!(world_data.active_piece.is_none());
+    }
+
+    #[test]
+    fn pause_screen_renders() {
+      let assets = Assets::load_assets();
+      let mut renderer = Renderer::new_in_memory(RENDERED_WINDOW_DIMENSIONS);
+      renderer
+        .load_font_from_bytes(assets.get_font("gadugi").unwrap(), "gadugi")
+        .unwrap();
+      let world_data = paused_world();
+
+      world_data.render(&assets, &mut renderer).unwrap();
+    }
+  }
+
+  mod game_mode_logic {
+    use super::*;
+    use crate::general_data::timer::clock;
+
+    const BOTTOM_ROW: i32 = WorldData::LOGICAL_BOARD_HEIGHT as i32 - 1;
+
+    fn apply_action(world_data: &mut WorldData, menu_action: MenuAction) {
+      world_data
+        .update_world(Some(PlayerAction::MenuAction(menu_action)))
+        .unwrap();
+    }
+
+    /// Returns a sprint game one line away from its goal,
+    /// with an I piece that completes the bottom row when hard dropped.
+    fn sprint_one_line_from_the_goal() -> WorldData {
+      let mut world_data = game_with_piece(MinoType::I, (0, BOTTOM_ROW - 3));
+      world_data.game_mode = GameMode::Sprint;
+
+      for _ in 0..((GameMode::SPRINT_LINES - 1) / 3) {
+        world_data.scoring.award_clear(3, None);
+      }
+
+      let bottom_row_start = (BOTTOM_ROW * WorldData::LOGICAL_BOARD_WIDTH as i32) as usize;
+      world_data.board[bottom_row_start + 4..].fill(Some(MinoType::O));
+
+      world_data
+    }
+
+    #[test]
+    fn picking_a_mode_starts_the_game() {
+      let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+
+      apply_action(&mut world_data, MenuAction::Select);
+      assert_eq!(world_data.current_menu, Some(GameModeMenu::MENU_NAME));
+
+      apply_action(&mut world_data, MenuAction::Down);
+      apply_action(&mut world_data, MenuAction::Select);
+
+      assert_eq!(world_data.world_state(), WorldState::Countdown);
+      assert_eq!(world_data.game_mode(), GameMode::Sprint);
+      assert_eq!(world_data.current_menu, Some(MainMenu::MENU_NAME));
+      assert!(world_data.active_piece.is_some());
+    }
+
+    #[test]
+    fn sprint_ends_when_the_last_line_clears() {
+      let mut world_data = sprint_one_line_from_the_goal();
+
+      world_data.update_world(None).unwrap();
+
+      assert_eq!(world_data.scoring.lines(), GameMode::SPRINT_LINES - 1);
+      assert_eq!(world_data.world_state(), WorldState::Game);
+
+      world_data
+        .update_world(Some(PlayerAction::GameAction(vec![GameAction::HardDrop])))
+        .unwrap();
+
+      assert_eq!(world_data.scoring.lines(), GameMode::SPRINT_LINES);
+      assert_eq!(world_data.world_state(), WorldState::GameOver);
+      assert_eq!(world_data.results_text().0, "Sprint Complete");
+    }
+
+    #[test]
+    fn sprint_time_stops_once_finished() {
+      let mut world_data = sprint_one_line_from_the_goal();
+      world_data.update_world(None).unwrap();
+      world_data
+        .update_world(Some(PlayerAction::GameAction(vec![GameAction::HardDrop])))
+        .unwrap();
+
+      let finished_time = world_data.game_time().unwrap();
+      clock::advance(Duration::from_secs(5));
+
+      assert_eq!(world_data.game_time(), Some(finished_time));
+    }
+
+    #[test]
+    fn marathon_has_no_line_goal() {
+      let mut world_data = sprint_one_line_from_the_goal();
+      world_data.game_mode = GameMode::Marathon;
+
+      world_data
+        .update_world(Some(PlayerAction::GameAction(vec![GameAction::HardDrop])))
+        .unwrap();
+
+      assert_eq!(world_data.scoring.lines(), GameMode::SPRINT_LINES);
+      assert_eq!(world_data.world_state(), WorldState::Game);
+    }
+
+    #[test]
+    fn ultra_ends_when_its_timer_finishes() {
+      let mut world_data = game_with_piece(MinoType::T, WorldData::SPAWN_ORIGIN);
+      world_data.game_mode = GameMode::Ultra;
+
+      world_data.update_world(None).unwrap();
+      clock::advance(GameMode::ULTRA_DURATION - Duration::from_millis(100));
+      world_data.update_world(None).unwrap();
+
+      assert_eq!(world_data.world_state(), WorldState::Game);
+
+      clock::advance(Duration::from_millis(100));
+      world_data.update_world(None).unwrap();
+
+      assert_eq!(world_data.world_state(), WorldState::GameOver);
+      assert_eq!(world_data.results_text().0, "Time's Up");
+    }
+
+    #[test]
+    fn times_are_formatted_as_minutes_and_seconds() {
+      assert_eq!(
+        WorldData::format_time(Duration::from_millis(65_429)),
+        "1:05.42"
+      );
+      assert_eq!(WorldData::format_time(Duration::ZERO), "0:00.00");
+    }
+  }
+
+  mod particles_logic {
+    use super::*;
+    use crate::general_data::timer::clock;
+
+    fn world_with_full_bottom_row(settings: GameSettings) -> WorldData {
+      let mut world_data = WorldData::new(settings, TEST_SEED);
+      let bottom_row_start =
+        ((WorldData::LOGICAL_BOARD_HEIGHT - 1) * WorldData::LOGICAL_BOARD_WIDTH) as usize;
+      world_data.board[bottom_row_start..].fill(Some(MinoType::O));
+
+      world_data
+    }
+
+    #[test]
+    fn line_clears_spawn_particles_that_expire() {
+      let mut world_data = world_with_full_bottom_row(GameSettings::default());
+
+      assert_eq!(world_data.clear_lines(), 1);
+      assert_eq!(
+        world_data.particles.len(),
+        WorldData::LOGICAL_BOARD_WIDTH as usize * Particles::PARTICLES_PER_CELL
+      );
+
+      clock::advance(Particles::LIFETIME);
+      world_data.particles.update();
+
+      assert!(world_data.particles.is_empty());
+    }
+
+    #[test]
+    fn disabled_particles_dont_spawn() {
+      let mut settings = GameSettings::default();
+      settings.set_max_particles(0);
+      let mut world_data = world_with_full_bottom_row(settings);
+
+      assert_eq!(world_data.clear_lines(), 1);
+      assert!(world_data.particles.is_empty());
+    }
+  }
+
+  mod effects_logic {
+    use super::*;
+
+    #[test]
+    fn tetris_shakes_the_screen() {
+      let mut world_data = game_with_piece(MinoType::I, (0, 0));
+      world_data.try_rotate(true);
+
+      let bottom_rows_start =
+        ((WorldData::LOGICAL_BOARD_HEIGHT - 4) * WorldData::LOGICAL_BOARD_WIDTH) as usize;
+
+      for row in world_data.board[bottom_rows_start..].chunks_exact_mut(10) {
+        row[1..].fill(Some(MinoType::O));
+      }
+
+      let origin = world_data.active_piece.unwrap().origin;
+      world_data.active_piece.as_mut().unwrap().origin = (origin.0 - 2, origin.1);
+
+      assert_eq!(world_data.effects.shake_offset(), (0, 0));
+
+      world_data.hard_drop();
+
+      assert_eq!(world_data.scoring.lines(), 4);
+      assert_ne!(world_data.effects.shake_offset(), (0, 0));
+    }
+
+    #[test]
+    fn leaving_the_game_fades_in_from_black() {
+      let mut world_data = game_with_piece(MinoType::T, WorldData::SPAWN_ORIGIN);
+
+      assert_eq!(world_data.effects.fade_alpha(), 0);
+
+      world_data.transition(WorldState::Menu).unwrap();
+
+      assert!(world_data.effects.fade_alpha() > 0);
+    }
+  }
+
+  mod audio_logic {
+    use super::*;
+
+    #[test]
+    fn game_events_play_sounds() {
+      let mut world_data = game_with_piece(MinoType::T, WorldData::SPAWN_ORIGIN);
+
+      world_data.try_rotate(true);
+      world_data.hold_piece();
+      world_data.hard_drop();
+
+      assert_eq!(world_data.audio.play_count(), 3);
+    }
+
+    #[test]
+    fn muting_stops_game_sounds() {
+      let mut world_data = game_with_piece(MinoType::T, WorldData::SPAWN_ORIGIN);
+      world_data.set_muted(true);
+
+      world_data.try_rotate(true);
+      world_data.hard_drop();
+
+      assert!(world_data.settings.muted());
+      assert_eq!(world_data.audio.play_count(), 0);
+
+      world_data.set_muted(false);
+      world_data.hard_drop();
+
+      assert_eq!(world_data.audio.play_count(), 1);
+    }
+  }
+
+  mod snapshot_logic {
+    use super::*;
+
+    #[test]
+    fn restoring_a_saved_game_brings_back_its_board() {
+      let mut world_data = game_with_piece(MinoType::T, WorldData::SPAWN_ORIGIN);
+      world_data.hard_drop();
+      world_data.move_horizontally(-1);
+      world_data.hard_drop();
+      world_data.hold_piece();
+
+      let save_path = std::env::temp_dir().join(format!(
+        "rustris_save_state_test_{}.json",
+        std::process::id()
+      ));
+      let snapshot = world_data.snapshot();
+      world_data.save_state(&save_path).unwrap();
+
+      world_data.board.fill(Some(MinoType::Z));
+      world_data.held = None;
+      world_data.scoring = Scoring::new();
+
+      let load_result = world_data.load_state(&save_path);
+      std::fs::remove_file(&save_path).unwrap();
+
+      assert!(load_result.is_ok());
+      assert_eq!(world_data.board, snapshot.board);
+      assert_eq!(world_data.snapshot(), snapshot);
+    }
+
+    #[test]
+    fn restored_bag_hands_out_the_same_pieces() {
+      let mut world_data = game_with_piece(MinoType::T, WorldData::SPAWN_ORIGIN);
+      let snapshot = world_data.snapshot();
+      let upcoming_pieces = world_data.bag.peek(10);
+
+      world_data.bag.next_piece();
+      world_data.restore(snapshot).unwrap();
+
+      assert_eq!(world_data.bag.peek(10), upcoming_pieces);
+    }
+
+    #[test]
+    fn mismatched_board_size_errors() {
+      let mut world_data = game_with_piece(MinoType::T, WorldData::SPAWN_ORIGIN);
+      let mut snapshot = world_data.snapshot();
+      snapshot.board.pop();
+
+      assert!(world_data.restore(snapshot).is_err());
+    }
+  }
+
+  mod hold_logic {
+    use super::*;
+
+    #[test]
+    fn first_hold_takes_the_next_piece() {
+      let mut world_data = game_with_piece(MinoType::T, (3, 25));
+      let next_kind = world_data.bag.peek(1)[0];
+
+      world_data.hold_piece();
+
+      assert_eq!(world_data.held, Some(MinoType::T));
+      let active_piece = world_data.active_piece.unwrap();
+      assert_eq!(active_piece.kind, next_kind);
+      assert_eq!(active_piece.origin, WorldData::SPAWN_ORIGIN);
+    }
+
+    #[test]
+    fn hold_swaps_once_per_piece() {
+      let mut world_data = game_with_piece(MinoType::T, (3, 25));
+      world_data.held = Some(MinoType::I);
+
+      world_data.hold_piece();
+      assert_eq!(world_data.held, Some(MinoType::T));
+      assert_eq!(world_data.active_piece.unwrap().kind, MinoType::I);
+
+      // The hold is locked until the I piece locks.
+      world_data.hold_piece();
+      assert_eq!(world_data.held, Some(MinoType::T));
+      assert_eq!(world_data.active_piece.unwrap().kind, MinoType::I);
+
+      world_data.hard_drop();
+      let next_kind = world_data.active_piece.unwrap().kind;
+      world_data.hold_piece();
+      assert_eq!(world_data.held, Some(next_kind));
+    }
+  }
+
+  mod rotation_logic {
+    use super::*;
+
+    const BOTTOM_ROW: i32 = WorldData::LOGICAL_BOARD_HEIGHT as i32 - 1;
+
+    /// Fills the bottom three rows, leaving a 3 wide notch in columns 3-5 of the top two.
+    fn fill_notched_stack(world_data: &mut WorldData) {
+      let board_width = WorldData::LOGICAL_BOARD_WIDTH as i32;
+
+      for y in (BOTTOM_ROW - 2)..=BOTTOM_ROW {
+        for x in 0..board_width {
+          if y != BOTTOM_ROW && (3..=5).contains(&x) {
+            continue;
+          }
+
+          world_data.board[(x + (y * board_width)) as usize] = Some(MinoType::O);
+        }
+      }
+    }
+
+    #[test]
+    fn open_space_rotates_without_a_kick() {
+      let world_data = game_with_piece(MinoType::T, (3, 10));
+      let active_piece = world_data.active_piece.unwrap();
+
+      let kick = world_data.find_rotation_kick(&active_piece, Rotation::Right);
+
+      assert_eq!(kick, Some((0, (3, 10))));
+    }
+
+    #[test]
+    fn t_piece_in_a_notch_uses_the_expected_kick() {
+      let mut world_data = game_with_piece(MinoType::T, (3, BOTTOM_ROW - 2));
+      fill_notched_stack(&mut world_data);
+      let active_piece = world_data.active_piece.unwrap();
+
+      // The unmoved and left shifted rotations push the stem of the T into the full bottom row.
+      let kick = world_data.find_rotation_kick(&active_piece, Rotation::Right);
+
+      assert_eq!(kick, Some((2, (2, BOTTOM_ROW - 3))));
+
+      assert!(world_data.try_rotate(true));
+      let active_piece = world_data.active_piece.unwrap();
+      assert_eq!(active_piece.rotation, Rotation::Right);
+      assert_eq!(active_piece.origin, (2, BOTTOM_ROW - 3));
+    }
+
+    #[test]
+    fn fully_blocked_rotation_leaves_piece_unchanged() {
+      let mut world_data = game_with_piece(MinoType::I, (3, BOTTOM_ROW - 1));
+
+      // Surround the flat I piece so no kick has room for it to stand up.
+      let board_width = WorldData::LOGICAL_BOARD_WIDTH as i32;
+      for (index, cell) in world_data.board.iter_mut().enumerate() {
+        let y = index as i32 / board_width;
+
+        if y >= BOTTOM_ROW - 4 && y != BOTTOM_ROW {
+          *cell = Some(MinoType::O);
+        }
+      }
+
+      assert!(!world_data.try_rotate(true));
+      let active_piece = world_data.active_piece.unwrap();
+      assert_eq!(active_piece.rotation, Rotation::Spawn);
+      assert_eq!(active_piece.origin, (3, BOTTOM_ROW - 1));
+    }
+
+    /// Builds the three bottom rows of a T-spin triple, with the T piece above the slot in column 4.
+    ///
+    /// The slot is covered by an overhang in column 5, and column 4 is blocked above the T piece,
+    /// so rotating clockwise can only fit with the last kick.
+    fn t_spin_triple_setup() -> WorldData {
+      let mut world_data = game_with_piece(MinoType::T, (4, BOTTOM_ROW - 4));
+      let board_width = WorldData::LOGICAL_BOARD_WIDTH as i32;
+      let slot = [
+        (4, BOTTOM_ROW - 2),
+        (4, BOTTOM_ROW - 1),
+        (5, BOTTOM_ROW - 1),
+        (4, BOTTOM_ROW),
+      ];
+
+      for y in (BOTTOM_ROW - 2)..=BOTTOM_ROW {
+        for x in 0..board_width {
+          if !slot.contains(&(x, y)) {
+            world_data.board[(x + (y * board_width)) as usize] = Some(MinoType::O);
+          }
+        }
+      }
+
+      world_data.board[(4 + ((BOTTOM_ROW - 4) * board_width)) as usize] = Some(MinoType::O);
+
+      world_data
+    }
+
+    #[test]
+    fn t_spin_triple_is_detected_and_scored() {
+      let mut world_data = t_spin_triple_setup();
+      let active_piece = world_data.active_piece.unwrap();
+
+      assert_eq!(
+        world_data.find_rotation_kick(&active_piece, Rotation::Right),
+        Some((4, (3, BOTTOM_ROW - 2)))
+      );
+      assert!(world_data.try_rotate(true));
+      assert_eq!(world_data.t_spin, Some(TSpin::Full));
+
+      world_data.lock_active_piece();
+
+      assert_eq!(world_data.scoring.lines(), 3);
+      assert_eq!(world_data.scoring.score(), 1600);
+    }
+
+    #[test]
+    fn moving_after_a_t_spin_clears_it() {
+      let mut world_data = game_with_piece(MinoType::T, (3, 10));
+      world_data.t_spin = Some(TSpin::Full);
+
+      assert!(world_data.shift_active_piece(1, 0));
+      assert_eq!(world_data.t_spin, None);
+    }
+
+    #[test]
+    fn rotating_without_a_kick_is_not_a_t_spin() {
+      let mut world_data = game_with_piece(MinoType::T, (3, BOTTOM_ROW - 2));
+      let board_width = WorldData::LOGICAL_BOARD_WIDTH as i32;
+
+      // Fill three corners around the T while leaving room for it to rotate in place.
+      for (x, y) in [(3, BOTTOM_ROW - 2), (5, BOTTOM_ROW - 2), (3, BOTTOM_ROW)] {
+        world_data.board[(x + (y * board_width)) as usize] = Some(MinoType::O);
+      }
+
+      assert!(world_data.try_rotate(true));
+      assert_eq!(world_data.t_spin, None);
+    }
+
+    #[test]
+    fn rotating_without_an_active_piece_does_nothing() {
+      let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+
+      assert!(!world_data.try_rotate(true));
+      assert!(!world_data.try_rotate_180());
+    }
+
+    #[test]
+    fn counterclockwise_then_clockwise_returns_to_the_start() {
+      for kind in MinoType::ALL {
+        let mut world_data = game_with_piece(kind, (3, 10));
+        let active_piece = world_data.active_piece.unwrap();
+
+        world_data
+          .update_world(Some(vec![GameAction::RotateCcw].into()))
+          .unwrap();
+        assert_eq!(world_data.active_piece.unwrap().rotation, Rotation::Left);
+
+        world_data
+          .update_world(Some(vec![GameAction::RotateCw].into()))
+          .unwrap();
+        assert_eq!(world_data.active_piece.unwrap(), active_piece, "{:?}", kind);
+      }
+    }
+
+    #[test]
+    fn rotating_180_flips_the_piece() {
+      let mut world_data = game_with_piece(MinoType::T, (3, 10));
+
+      world_data
+        .update_world(Some(vec![GameAction::Rotate180].into()))
+        .unwrap();
+      let active_piece = world_data.active_piece.unwrap();
+      assert_eq!(active_piece.rotation, Rotation::Flipped);
+      assert_eq!(active_piece.origin, (3, 10));
+
+      assert!(world_data.try_rotate_180());
+      assert_eq!(world_data.active_piece.unwrap().rotation, Rotation::Spawn);
+    }
+
+    #[test]
+    fn blocked_180_falls_back_to_two_kicked_turns() {
+      let mut world_data = t_spin_triple_setup();
+      let active_piece = world_data.active_piece.unwrap();
+
+      // Flipping in place pushes the stem of the T into the overhang.
+      assert!(world_data.collides(MinoType::T, Rotation::Flipped, active_piece.origin));
+
+      assert!(world_data.try_rotate_180());
+      let active_piece = world_data.active_piece.unwrap();
+      assert_eq!(active_piece.rotation, Rotation::Flipped);
+      assert!(!world_data.collides(
+        active_piece.kind,
+        active_piece.rotation,
+        active_piece.origin
+      ));
+    }
+  }
+
+  mod movement_logic {
+    use super::*;
+
+    fn apply_actions(world_data: &mut WorldData, game_actions: Vec<GameAction>) {
+      world_data
+        .update_world(Some(PlayerAction::GameAction(game_actions)))
+        .unwrap();
+    }
+
+    #[test]
+    fn actions_shift_the_origin() {
+      let mut world_data = game_with_piece(MinoType::T, (3, 10));
+
+      apply_actions(
+        &mut world_data,
+        vec![
+          GameAction::MoveLeft,
+          GameAction::MoveLeft,
+          GameAction::SoftDrop,
+        ],
+      );
+      apply_actions(&mut world_data, vec![GameAction::MoveRight]);
+
+      assert_eq!(world_data.active_piece.unwrap().origin, (2, 11));
+    }
+
+    #[test]
+    fn wall_blocked_move_leaves_origin_unchanged() {
+      let mut world_data = game_with_piece(MinoType::T, (0, 10));
+
+      apply_actions(&mut world_data, vec![GameAction::MoveLeft]);
+
+      assert_eq!(world_data.active_piece.unwrap().origin, (0, 10));
+    }
+
+    #[test]
+    fn soft_drop_stops_at_the_floor() {
+      let floor_origin = WorldData::LOGICAL_BOARD_HEIGHT as i32 - 2;
+      let mut world_data = game_with_piece(MinoType::T, (3, floor_origin));
+
+      apply_actions(&mut world_data, vec![GameAction::SoftDrop]);
+
+      assert_eq!(world_data.active_piece.unwrap().origin, (3, floor_origin));
+    }
+
+    #[test]
+    fn rotation_is_applied_before_movement() {
+      // A T piece pointing right, flush against the left wall.
+      let wall_piece = ActivePiece {
+        kind: MinoType::T,
+        rotation: Rotation::Right,
+        origin: (-1, 10),
+      };
+      let mut world_data = game_with_piece(MinoType::T, (0, 0));
+      world_data.active_piece = Some(wall_piece);
+
+      apply_actions(
+        &mut world_data,
+        vec![GameAction::MoveRight, GameAction::RotateCw],
+      );
+
+      // Rotating first kicks the piece off of the wall, which is then moved right.
+      let active_piece = world_data.active_piece.unwrap();
+      assert_eq!(active_piece.rotation, Rotation::Flipped);
+      assert_eq!(active_piece.origin, (1, 10));
+
+      world_data.active_piece = Some(wall_piece);
+
+      apply_actions(
+        &mut world_data,
+        vec![GameAction::RotateCw, GameAction::MoveRight],
+      );
+
+      assert_eq!(world_data.active_piece.unwrap(), active_piece);
+    }
+
+    #[test]
+    fn actions_without_an_active_piece_do_nothing() {
+      let mut world_data = WorldData::new(GameSettings::default(), TEST_SEED);
+      world_data.current_state = WorldState::Game;
+
+      apply_actions(
+        &mut world_data,
+        vec![GameAction::MoveLeft, GameAction::SoftDrop],
+      );
+
+      assert!(world_data.active_piece.is_none());
+      assert!(world_data.board.iter().all(Option::is_none));
     }
   }
 }
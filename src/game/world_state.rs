@@ -1,5 +1,30 @@
-#[derive(Debug, Clone, Copy)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum WorldState {
   Menu,
+  /// The board is shown counting down to the start of the game, ignoring any input.
+  Countdown,
   Game,
+  /// The game has ended, and is waiting for the player to restart or quit.
+  GameOver,
+}
+
+impl WorldState {
+  /// Returns true if the world is allowed to move from this state into the given one.
+  ///
+  /// Transitioning into the state the world is already in is never allowed.
+  pub fn can_transition_to(&self, new_state: WorldState) -> bool {
+    matches!(
+      (self, new_state),
+      (WorldState::Menu, WorldState::Game)
+        | (WorldState::Menu, WorldState::Countdown)
+        | (WorldState::Countdown, WorldState::Game)
+        | (WorldState::GameOver, WorldState::Countdown)
+        | (WorldState::Game, WorldState::Menu)
+        | (WorldState::Game, WorldState::GameOver)
+        | (WorldState::GameOver, WorldState::Game)
+        | (WorldState::GameOver, WorldState::Menu)
+    )
+  }
 }
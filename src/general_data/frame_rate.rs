@@ -0,0 +1,98 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Measures how many frames are drawn per second, averaged over the most recent frames.
+#[derive(Debug, Clone)]
+pub struct FrameRate {
+  /// The time each of the most recent frames took, oldest first.
+  frame_durations: VecDeque<Duration>,
+  /// How many frames the average is taken over.
+  sample_count: usize,
+  /// When the last frame finished. None until the first frame finishes.
+  last_frame_at: Option<Instant>,
+}
+
+impl FrameRate {
+  /// The amount of frames averaged over by default.
+  pub const DEFAULT_SAMPLE_COUNT: usize = 60;
+
+  /// Creates a counter averaging over the given amount of frames, with a minimum of 1.
+  pub fn new(sample_count: usize) -> Self {
+    let sample_count = sample_count.max(1);
+
+    Self {
+      frame_durations: VecDeque::with_capacity(sample_count),
+      sample_count,
+      last_frame_at: None,
+    }
+  }
+
+  /// Marks the end of a frame, measuring how long it's been since the last one finished.
+  pub fn frame_finished(&mut self) {
+    let now = Instant::now();
+
+    if let Some(last_frame_at) = self.last_frame_at {
+      self.record_frame_duration(now - last_frame_at);
+    }
+
+    self.last_frame_at = Some(now);
+  }
+
+  /// Adds the duration of a frame to the average, dropping the oldest frame once there are too many.
+  pub fn record_frame_duration(&mut self, frame_duration: Duration) {
+    if self.frame_durations.len() == self.sample_count {
+      self.frame_durations.pop_front();
+    }
+
+    self.frame_durations.push_back(frame_duration);
+  }
+
+  /// Returns the average frames per second over the recorded frames.
+  ///
+  /// 0 is returned if no time has been recorded.
+  pub fn fps(&self) -> f64 {
+    let total_duration: Duration = self.frame_durations.iter().sum();
+
+    if total_duration.is_zero() {
+      return 0.0;
+    }
+
+    self.frame_durations.len() as f64 / total_duration.as_secs_f64()
+  }
+}
+
+impl Default for FrameRate {
+  fn default() -> Self {
+    Self::new(Self::DEFAULT_SAMPLE_COUNT)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn fps_is_averaged_over_the_recent_frames() {
+    let mut frame_rate = FrameRate::new(4);
+
+    assert_eq!(frame_rate.fps(), 0.0);
+
+    for _ in 0..4 {
+      frame_rate.record_frame_duration(Duration::from_millis(10));
+    }
+
+    assert!((frame_rate.fps() - 100.0).abs() < 0.001);
+
+    // 2 slow frames push out the 2 oldest, leaving 10 + 10 + 40 + 40 milliseconds.
+    frame_rate.record_frame_duration(Duration::from_millis(40));
+    frame_rate.record_frame_duration(Duration::from_millis(40));
+
+    assert!((frame_rate.fps() - 40.0).abs() < 0.001);
+
+    for _ in 0..4 {
+      frame_rate.record_frame_duration(Duration::from_millis(20));
+    }
+
+    assert!((frame_rate.fps() - 50.0).abs() < 0.001);
+  }
+}
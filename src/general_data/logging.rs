@@ -1,23 +1,29 @@
 use chrono::Utc;
 use log::LevelFilter;
 use log4rs::{
-  append::file::FileAppender,
+  append::{console::ConsoleAppender, file::FileAppender},
   config::{Appender, Config, Root},
   encode::pattern::PatternEncoder,
 };
 use std::env;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 const LATEST_COMMIT_SHA: &str = env!("LATEST_COMMIT_SHA");
 const DEFAULT_LOG_LEVEL: &str = "info";
 const DEFAULT_LOG_FORMAT: &str = "short";
+/// The directory log files are created in when `RUSTRIS_LOG_DIR` isn't set, relative to the working directory.
+const DEFAULT_LOG_DIRECTORY: &str = "logs";
 
-/// Creates a new log file in "crate/logs/".
+/// Creates a new log file in "logs/", or the directory set with the `RUSTRIS_LOG_DIR` environment variable.
+/// The directory is created if it doesn't exist.
 /// The new log file will be named after the current time and date based on UTC.
 /// The name format is as such Y-M-D-H:M:S-UTC or Year-Month-Day-Hour:Minute:Second-TimeZone.
 ///
 /// The format will be configured with the `RUSTRIS_LOG_FORMAT` environment variable.
 /// The logging level will be configured with the `RUSTRIS_LOG_LEVEL` environment variable.
+/// Setting `RUSTRIS_LOG_STDOUT=1` also writes every log to stdout.
+/// If the log file can't be created, logs are only written to stdout.
 ///
 /// long: "(Hour:Minute:Second)(TimeZone) | FilePath: Line | Level - Message".
 /// short: "FilePath: Line | Level - Message".
@@ -27,16 +33,45 @@ pub fn setup_file_logger() -> Result<log4rs::Handle, Box<dyn std::error::Error>>
   let log_level = get_logging_level()?;
   let logging_format = get_logging_format();
 
+  let log_directory = get_log_directory(env::var("RUSTRIS_LOG_DIR").ok());
   let date = Utc::now().to_string().replace(':', "-");
-  let log_file_path = format!("logs/{date}.log").replace(' ', "-");
+  let log_file_path = get_log_file_path(&log_directory, &date);
 
-  let logfile = FileAppender::builder()
-    .encoder(Box::new(PatternEncoder::new(&logging_format)))
-    .build(log_file_path)?;
+  let logfile = std::fs::create_dir_all(&log_directory).and_then(|_| {
+    FileAppender::builder()
+      .encoder(Box::new(PatternEncoder::new(&logging_format)))
+      .build(&log_file_path)
+  });
 
-  let config = Config::builder()
-    .appender(Appender::builder().build("logfile", Box::new(logfile)))
-    .build(Root::builder().appender("logfile").build(log_level))?;
+  let mut config = Config::builder();
+  let mut root = Root::builder();
+  let mut log_to_stdout = stdout_logging_enabled(env::var("RUSTRIS_LOG_STDOUT").ok());
+
+  match logfile {
+    Ok(logfile) => {
+      config = config.appender(Appender::builder().build("logfile", Box::new(logfile)));
+      root = root.appender("logfile");
+    }
+    Err(error) => {
+      eprintln!(
+        "Failed to create the log file at {:?}, logging to stdout instead. `{}`",
+        log_file_path, error
+      );
+
+      log_to_stdout = true;
+    }
+  }
+
+  if log_to_stdout {
+    let stdout = ConsoleAppender::builder()
+      .encoder(Box::new(PatternEncoder::new(&logging_format)))
+      .build();
+
+    config = config.appender(Appender::builder().build("stdout", Box::new(stdout)));
+    root = root.appender("stdout");
+  }
+
+  let config = config.build(root.build(log_level))?;
 
   log::warn!(
     "This build is: {}\n",
@@ -67,3 +102,66 @@ fn get_logging_format() -> String {
   }
   .into()
 }
+
+/// Returns the directory set through `RUSTRIS_LOG_DIR`, or [`DEFAULT_LOG_DIRECTORY`] if it isn't set or is blank.
+fn get_log_directory(log_directory_var: Option<String>) -> PathBuf {
+  match log_directory_var {
+    Some(log_directory) if !log_directory.trim().is_empty() => PathBuf::from(log_directory.trim()),
+    _ => PathBuf::from(DEFAULT_LOG_DIRECTORY),
+  }
+}
+
+/// Returns the path of the log file named after the date inside of the directory.
+fn get_log_file_path(log_directory: &Path, date: &str) -> PathBuf {
+  log_directory.join(format!("{date}.log").replace(' ', "-"))
+}
+
+/// Returns true if `RUSTRIS_LOG_STDOUT` is set to `1`, `true`, `yes`, or `on`, ignoring case.
+fn stdout_logging_enabled(log_stdout_var: Option<String>) -> bool {
+  log_stdout_var.is_some_and(|value| {
+    matches!(
+      value.trim().to_ascii_lowercase().as_str(),
+      "1" | "true" | "yes" | "on"
+    )
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn log_directory_defaults_when_unset_or_blank() {
+    let default_directory = PathBuf::from(DEFAULT_LOG_DIRECTORY);
+
+    assert_eq!(get_log_directory(None), default_directory);
+    assert_eq!(get_log_directory(Some("  ".into())), default_directory);
+    assert_eq!(
+      get_log_directory(Some(" /tmp/rustris ".into())),
+      PathBuf::from("/tmp/rustris")
+    );
+  }
+
+  #[test]
+  fn log_file_is_named_after_the_date() {
+    let log_file_path = get_log_file_path(Path::new("my logs"), "2024-02-01 12-30-00 UTC");
+
+    assert_eq!(
+      log_file_path,
+      Path::new("my logs").join("2024-02-01-12-30-00-UTC.log")
+    );
+  }
+
+  #[test]
+  fn stdout_logging_is_only_enabled_by_truthy_values() {
+    for enabled_value in ["1", "true", "TRUE", " yes ", "on"] {
+      assert!(stdout_logging_enabled(Some(enabled_value.into())));
+    }
+
+    for disabled_value in ["0", "false", "", "2"] {
+      assert!(!stdout_logging_enabled(Some(disabled_value.into())));
+    }
+
+    assert!(!stdout_logging_enabled(None));
+  }
+}
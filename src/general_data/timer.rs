@@ -0,0 +1,333 @@
+use std::cell::{Cell, RefCell};
+use std::time::{Duration, Instant};
+
+/// A timer that finishes once its duration has passed since it was started.
+///
+/// The state is kept behind interior mutability so timers can be checked and restarted
+/// through a shared reference, such as one handed out by
+/// [`WorldData::get_or_init_timer`](crate::game::world_data::WorldData::get_or_init_timer).
+#[derive(Debug, Clone)]
+pub struct Timer {
+  duration: Cell<Duration>,
+  /// When the timer was last started. None when the timer isn't running.
+  started_at: RefCell<Option<Instant>>,
+  /// When the timer was paused. None when the timer isn't paused.
+  paused_at: Cell<Option<Instant>>,
+}
+
+impl Timer {
+  /// Creates a timer with the given duration that isn't running yet.
+  pub fn new(duration: Duration) -> Self {
+    Self {
+      duration: Cell::new(duration),
+      started_at: RefCell::new(None),
+      paused_at: Cell::new(None),
+    }
+  }
+
+  /// Starts the timer from now, restarting it if it was already running.
+  ///
+  /// A paused timer is unpaused.
+  pub fn start(&self) {
+    *self.started_at.borrow_mut() = Some(clock::now());
+    self.paused_at.set(None);
+  }
+
+  /// Stops the timer, it won't finish until started again.
+  pub fn stop(&self) {
+    *self.started_at.borrow_mut() = None;
+    self.paused_at.set(None);
+  }
+
+  /// Freezes the running timer's progress until it's resumed.
+  ///
+  /// Nothing happens if the timer isn't running or is already paused.
+  pub fn pause(&self) {
+    if self.running() && !self.is_paused() {
+      self.paused_at.set(Some(clock::now()));
+    }
+  }
+
+  /// Continues a paused timer from where it was paused, excluding the time spent paused.
+  pub fn resume(&self) {
+    let Some(paused_at) = self.paused_at.take() else {
+      return;
+    };
+
+    if let Some(started_at) = self.started_at.borrow_mut().as_mut() {
+      *started_at += clock::now().duration_since(paused_at);
+    }
+  }
+
+  /// Returns true if the timer has been paused and not resumed.
+  pub fn is_paused(&self) -> bool {
+    self.paused_at.get().is_some()
+  }
+
+  /// Returns true if the timer has been started and not stopped.
+  pub fn running(&self) -> bool {
+    self.started_at.borrow().is_some()
+  }
+
+  /// Returns true if the timer is running and its duration has passed.
+  /// A paused timer never finishes.
+  ///
+  /// A finished timer is restarted from now, so this only returns true once per duration.
+  pub fn is_finished(&self) -> bool {
+    let Some(elapsed) = self.elapsed() else {
+      return false;
+    };
+
+    if self.is_paused() || elapsed < self.duration.get() {
+      return false;
+    }
+
+    self.start();
+
+    true
+  }
+
+  /// Returns how much longer the running timer has until it finishes, without restarting it.
+  ///
+  /// None is returned if the timer isn't running.
+  pub fn remaining(&self) -> Option<Duration> {
+    let elapsed = self.elapsed()?;
+
+    Some(self.duration.get().saturating_sub(elapsed))
+  }
+
+  /// Returns how far the timer is towards finishing, from 0.0 when just started to 1.0 once finished,
+  /// without restarting it.
+  ///
+  /// A timer that isn't running is at 0.0.
+  pub fn elapsed_fraction(&self) -> f32 {
+    let Some(elapsed) = self.elapsed() else {
+      return 0.0;
+    };
+    let duration = self.duration.get();
+
+    if duration.is_zero() {
+      return 1.0;
+    }
+
+    (elapsed.as_secs_f32() / duration.as_secs_f32()).clamp(0.0, 1.0)
+  }
+
+  /// Returns how long the timer has been running for, or None if it isn't running.
+  ///
+  /// Time spent paused isn't counted.
+  pub fn elapsed(&self) -> Option<Duration> {
+    let started_at = (*self.started_at.borrow())?;
+    let now = self.paused_at.get().unwrap_or_else(clock::now);
+
+    Some(now.duration_since(started_at))
+  }
+
+  pub fn duration(&self) -> Duration {
+    self.duration.get()
+  }
+
+  /// Changes how long the timer takes to finish.
+  ///
+  /// A running timer keeps its start time, so the new duration applies to the current run.
+  pub fn set_duration(&self, duration: Duration) {
+    self.duration.set(duration);
+  }
+}
+
+/// The source of the current time for timers.
+///
+/// Under test this can be advanced manually so timers can be checked without sleeping.
+#[cfg(not(test))]
+mod clock {
+  use std::time::Instant;
+
+  pub fn now() -> Instant {
+    Instant::now()
+  }
+}
+
+#[cfg(test)]
+pub mod clock {
+  use std::cell::Cell;
+  use std::time::{Duration, Instant};
+
+  thread_local! {
+    static OFFSET: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+  }
+
+  pub fn now() -> Instant {
+    Instant::now() + OFFSET.with(Cell::get)
+  }
+
+  /// Moves the clock forward for the current thread.
+  pub fn advance(duration: Duration) {
+    OFFSET.with(|offset| offset.set(offset.get() + duration));
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  mod timer_logic {
+    use super::*;
+
+    const DURATION: Duration = Duration::from_millis(100);
+
+    #[test]
+    fn new_timer_is_not_running() {
+      let timer = Timer::new(DURATION);
+
+      clock::advance(DURATION);
+
+      assert!(!timer.running());
+      assert!(!timer.is_finished());
+    }
+
+    #[test]
+    fn timer_finishes_after_its_duration() {
+      let timer = Timer::new(DURATION);
+      timer.start();
+
+      assert!(!timer.is_finished());
+
+      clock::advance(DURATION);
+
+      assert!(timer.is_finished());
+    }
+
+    #[test]
+    fn finishing_restarts_the_timer() {
+      let timer = Timer::new(DURATION);
+      timer.start();
+
+      clock::advance(DURATION);
+
+      assert!(timer.is_finished());
+      assert!(timer.running());
+      assert!(!timer.is_finished());
+    }
+
+    #[test]
+    fn stopped_timer_never_finishes() {
+      let timer = Timer::new(DURATION);
+      timer.start();
+      timer.stop();
+
+      clock::advance(DURATION);
+
+      assert!(!timer.is_finished());
+    }
+  }
+
+  mod progress_logic {
+    use super::*;
+
+    const DURATION: Duration = Duration::from_millis(100);
+
+    #[test]
+    fn timer_that_isnt_running_has_no_progress() {
+      let timer = Timer::new(DURATION);
+
+      assert_eq!(timer.remaining(), None);
+      assert_eq!(timer.elapsed_fraction(), 0.0);
+    }
+
+    #[test]
+    fn remaining_time_shrinks_as_time_passes() {
+      let timer = Timer::new(DURATION);
+      timer.start();
+
+      let first_remaining = timer.remaining().unwrap();
+      clock::advance(DURATION / 4);
+      let second_remaining = timer.remaining().unwrap();
+
+      assert!(first_remaining <= DURATION);
+      assert!(second_remaining < first_remaining);
+
+      let fraction = timer.elapsed_fraction();
+
+      assert!(fraction > 0.0 && fraction < 1.0);
+    }
+
+    #[test]
+    fn checking_progress_doesnt_restart_the_timer() {
+      let timer = Timer::new(DURATION);
+      timer.start();
+
+      clock::advance(DURATION * 2);
+
+      assert_eq!(timer.remaining(), Some(Duration::ZERO));
+      assert_eq!(timer.elapsed_fraction(), 1.0);
+      assert!(timer.is_finished());
+    }
+  }
+
+  mod pause_logic {
+    use super::*;
+
+    const DURATION: Duration = Duration::from_millis(100);
+
+    #[test]
+    fn paused_timer_doesnt_finish() {
+      let timer = Timer::new(DURATION);
+      timer.start();
+      timer.pause();
+
+      clock::advance(DURATION * 2);
+
+      assert!(timer.is_paused());
+      assert!(timer.running());
+      assert!(!timer.is_finished());
+    }
+
+    #[test]
+    fn paused_span_isnt_counted() {
+      let timer = Timer::new(DURATION);
+      timer.start();
+
+      clock::advance(DURATION / 2);
+      timer.pause();
+      clock::advance(DURATION * 5);
+      timer.resume();
+
+      assert!(!timer.is_paused());
+      assert!(!timer.is_finished());
+
+      let remaining = timer.remaining().unwrap();
+
+      assert!(remaining <= DURATION / 2);
+      assert!(remaining > Duration::ZERO);
+
+      clock::advance(DURATION / 2);
+
+      assert!(timer.is_finished());
+    }
+
+    #[test]
+    fn pausing_a_stopped_timer_does_nothing() {
+      let timer = Timer::new(DURATION);
+      timer.pause();
+
+      assert!(!timer.is_paused());
+
+      timer.resume();
+
+      assert!(!timer.running());
+    }
+
+    #[test]
+    fn restarting_unpauses_the_timer() {
+      let timer = Timer::new(DURATION);
+      timer.start();
+      timer.pause();
+      timer.start();
+
+      clock::advance(DURATION);
+
+      assert!(!timer.is_paused());
+      assert!(timer.is_finished());
+    }
+  }
+}
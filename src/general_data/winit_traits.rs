@@ -1,3 +1,4 @@
+use winit::dpi::{LogicalPosition, LogicalSize};
 use winit::event::Event;
 use winit::window::WindowId;
 
@@ -28,11 +29,52 @@ impl Add for winit::dpi::PhysicalPosition<u32> {
   }
 }
 
+impl Add for LogicalPosition<u32> {
+  fn add(&self, other: &Self) -> Self {
+    Self {
+      x: self.x + other.x,
+      y: self.y + other.y,
+    }
+  }
+}
+
+impl Add for LogicalSize<u32> {
+  fn add(&self, other: &Self) -> Self {
+    Self {
+      width: self.width + other.width,
+      height: self.height + other.height,
+    }
+  }
+}
+
+/// Subtraction that stops at 0 instead of underflowing.
+pub trait Sub {
+  fn subtract(&self, other: &Self) -> Self;
+}
+
+impl Sub for LogicalPosition<u32> {
+  fn subtract(&self, other: &Self) -> Self {
+    Self {
+      x: self.x.saturating_sub(other.x),
+      y: self.y.saturating_sub(other.y),
+    }
+  }
+}
+
+impl Sub for LogicalSize<u32> {
+  fn subtract(&self, other: &Self) -> Self {
+    Self {
+      width: self.width.saturating_sub(other.width),
+      height: self.height.saturating_sub(other.height),
+    }
+  }
+}
+
 pub trait Mul {
   fn multiply(&self, other: u32) -> Self;
 }
 
-impl Mul for winit::dpi::LogicalSize<u32> {
+impl Mul for LogicalSize<u32> {
   fn multiply(&self, other: u32) -> Self {
     Self {
       width: self.width * other,
@@ -40,3 +82,77 @@ impl Mul for winit::dpi::LogicalSize<u32> {
     }
   }
 }
+
+impl Mul for LogicalPosition<u32> {
+  fn multiply(&self, other: u32) -> Self {
+    Self {
+      x: self.x * other,
+      y: self.y * other,
+    }
+  }
+}
+
+/// Returns the center of the rect with the given top left and dimensions, rounded towards the top left.
+pub fn center_of(
+  position: &LogicalPosition<u32>,
+  dimensions: &LogicalSize<u32>,
+) -> LogicalPosition<u32> {
+  LogicalPosition::new(
+    position.x + (dimensions.width / 2),
+    position.y + (dimensions.height / 2),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  mod arithmetic_logic {
+    use super::*;
+
+    #[test]
+    fn positions_and_sizes_add() {
+      assert_eq!(
+        LogicalPosition::new(1, 2).add(&LogicalPosition::new(3, 4)),
+        LogicalPosition::new(4, 6)
+      );
+      assert_eq!(
+        LogicalSize::new(1, 2).add(&LogicalSize::new(3, 4)),
+        LogicalSize::new(4, 6)
+      );
+    }
+
+    #[test]
+    fn subtraction_saturates_at_zero() {
+      assert_eq!(
+        LogicalPosition::new(5, 2).subtract(&LogicalPosition::new(3, 4)),
+        LogicalPosition::new(2, 0)
+      );
+      assert_eq!(
+        LogicalSize::new(2, 5).subtract(&LogicalSize::new(3, 4)),
+        LogicalSize::new(0, 1)
+      );
+    }
+
+    #[test]
+    fn positions_and_sizes_multiply() {
+      assert_eq!(
+        LogicalPosition::new(1, 2).multiply(3),
+        LogicalPosition::new(3, 6)
+      );
+      assert_eq!(LogicalSize::new(1, 2).multiply(3), LogicalSize::new(3, 6));
+    }
+
+    #[test]
+    fn center_is_rounded_towards_the_top_left() {
+      assert_eq!(
+        center_of(&LogicalPosition::new(10, 20), &LogicalSize::new(4, 5)),
+        LogicalPosition::new(12, 22)
+      );
+      assert_eq!(
+        center_of(&LogicalPosition::new(0, 0), &LogicalSize::new(0, 1)),
+        LogicalPosition::new(0, 0)
+      );
+    }
+  }
+}
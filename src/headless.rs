@@ -0,0 +1,79 @@
+use crate::asset_loader::Assets;
+use crate::game::{actions::PlayerAction, game_settings::GameSettings, world_data::WorldData};
+use crate::renderer::Renderer;
+
+/// Runs the game without a window, drawing each tick to a frame buffer in memory.
+///
+/// Ticks are stepped manually with the given actions instead of being driven by a game loop,
+/// which allows whole games to be scripted and checked in tests.
+/// Line clear particles can be skipped by setting [`max_particles`](GameSettings::set_max_particles) to 0.
+pub struct HeadlessGame {
+  world_data: WorldData,
+  renderer: Renderer,
+  assets: Assets,
+  ticks: u64,
+}
+
+impl HeadlessGame {
+  pub fn new(settings: GameSettings, seed: u64) -> anyhow::Result<Self> {
+    let assets = Assets::load_assets();
+    let mut renderer = Renderer::new_in_memory(settings.render_resolution().dimensions());
+
+    assets
+      .font_assets()
+      .iter()
+      .try_for_each(|(font_name, font_bytes)| {
+        renderer.load_font_from_bytes(font_bytes, font_name)
+      })?;
+
+    Ok(Self {
+      world_data: WorldData::new(settings, seed),
+      renderer,
+      assets,
+      ticks: 0,
+    })
+  }
+
+  /// Updates the world with the given action, then renders the result.
+  ///
+  /// True is returned when a request to close the program was made.
+  pub fn step(&mut self, player_action: Option<PlayerAction>) -> anyhow::Result<bool> {
+    let exit_requested = self.world_data.update_world(player_action)?;
+
+    self.renderer.clear()?;
+    self.world_data.render(&self.assets, &mut self.renderer)?;
+    self.ticks += 1;
+
+    Ok(exit_requested)
+  }
+
+  /// Steps through each action in the script in order, one per tick.
+  ///
+  /// Stops early if a request to close the program was made, returning true.
+  pub fn run_script(&mut self, script: &[Option<PlayerAction>]) -> anyhow::Result<bool> {
+    for player_action in script {
+      if self.step(player_action.clone())? {
+        return Ok(true);
+      }
+    }
+
+    Ok(false)
+  }
+
+  pub fn world_data(&self) -> &WorldData {
+    &self.world_data
+  }
+
+  pub fn world_data_mut(&mut self) -> &mut WorldData {
+    &mut self.world_data
+  }
+
+  pub fn renderer(&self) -> &Renderer {
+    &self.renderer
+  }
+
+  /// Returns how many ticks have been stepped.
+  pub fn ticks(&self) -> u64 {
+    self.ticks
+  }
+}
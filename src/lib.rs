@@ -1,28 +1,49 @@
 pub mod general_data {
+  pub mod frame_rate;
   pub mod logging;
   pub mod result_traits;
+  pub mod rng;
+  pub mod timer;
   pub mod winit_traits;
 }
 
 pub mod game {
   pub mod actions;
+  pub mod audio;
+  pub mod effects;
+  pub mod game_mode;
   pub mod game_settings;
+  pub mod game_snapshot;
+  pub mod hud;
+  pub mod kicks;
+  pub mod leaderboard;
   pub mod minos;
+  pub mod palette;
+  pub mod particles;
+  pub mod resolution;
+  pub mod scoring;
+  pub mod seven_bag;
+  pub mod stats;
   pub mod world_data;
   pub mod world_state;
 }
 
 pub mod menus {
   pub mod templates {
+    pub mod game_mode;
+    pub mod game_over;
     pub mod game_settings;
     pub mod main_menu;
+    pub mod pause_menu;
   }
 
   pub mod menu_data;
   pub mod menu_items;
+  pub mod theme;
 }
 
 pub mod renderer;
 
 pub mod asset_loader;
+pub mod headless;
 pub mod rustris_config;
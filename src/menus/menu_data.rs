@@ -1,10 +1,17 @@
+use crate::asset_loader::Assets;
+use crate::general_data::winit_traits::center_of;
 pub use crate::menus::menu_items::*;
+use crate::menus::theme::Theme;
+use crate::renderer::fonts::TextBox;
 use crate::renderer::*;
-use crate::{asset_loader::Assets, rustris_config::RENDERED_WINDOW_DIMENSIONS};
 use anyhow::anyhow;
 use image::GenericImageView;
+use std::cell::{Cell, RefCell};
 use winit::dpi::*;
 
+/// The area an option was drawn in, as its top left position and dimensions.
+pub type OptionRect = (LogicalPosition<u32>, LogicalSize<u32>);
+
 /// Creating a menu is best done through the [`define_menu_items`](crate::define_menu_items) macro.
 /// This macro will easily define every item in a menu, and its corresponding asset.
 ///
@@ -13,13 +20,14 @@ use winit::dpi::*;
 /// use rustris::menus::menu_data::*;
 ///
 /// define_menu_items! {
-///   pub enum MyMenu {
+///   pub enum MyMenu(menu_name = "my_menu") {
 ///     ItemOne(item_name = "item_one", asset_name = "item_one_menu_image"),
 ///   }
 /// }
 ///
-/// let menu_name = "my_menu";
-/// let menu = Menu::new::<MyMenu>(menu_name);
+/// let menu = Menu::new::<MyMenu>();
+///
+/// assert_eq!(menu.name(), "my_menu");
 ///
 /// let current_option = menu.current_option().unwrap();
 /// let current_option = MyMenu::from_menu_item(current_option);
@@ -32,24 +40,66 @@ pub struct Menu {
   /// The index for which option is currently selected.
   selected: usize,
   options: Vec<MenuItem>,
+  /// The index of each option drawn the last time the menu was rendered, and where it was drawn.
+  ///
+  /// Used to find the option under the mouse cursor.
+  option_rects: RefCell<Vec<(usize, OptionRect)>>,
+  /// The height in pixels of the band options are drawn in. None draws every option.
+  viewport_height: Option<u32>,
+  /// The index of the first option drawn in the viewport.
+  scroll_offset: usize,
+  /// How many options fit entirely in the viewport the last time the menu was rendered.
+  visible_option_count: Cell<usize>,
 }
 
 impl Menu {
-  /// Creates a new menu from a list of options.
+  /// The length in pixels of the arrow drawn beside the selected option.
+  const SELECTION_INDICATOR_LENGTH: u32 = 8;
+  /// The gap in pixels between the selection arrow's tip and the selected option.
+  const SELECTION_INDICATOR_GAP: u32 = 4;
+  /// The dimensions in pixels of the bar drawn under a slider's label.
+  const SLIDER_BAR_DIMENSIONS: LogicalSize<u32> = LogicalSize::new(100, 6);
+  /// The gap in pixels between a slider's label and its bar.
+  const SLIDER_BAR_GAP: u32 = 3;
+  /// The width and height in pixels of the box drawn beside a toggle's label, which is filled in while it's on.
+  const TOGGLE_INDICATOR_SIZE: u32 = 8;
+  /// The gap in pixels between a toggle's label and its box.
+  const TOGGLE_INDICATOR_GAP: u32 = 6;
+
+  /// Creates a new menu from a list of options, named with the options' [`MENU_NAME`](MenuItemData::MENU_NAME).
   ///
   /// Each option will implement [`MenuItemData`](crate::menus::menu_items::MenuItemData).
   /// This will force each option to know its name, and what asset it's tied to.
   /// This allows for better organization of the possible options in a menu.
-  pub fn new<M: MenuItemData>(name: &'static str) -> Self {
+  pub fn new<M: MenuItemData>() -> Self {
     let options = M::full_list();
 
     Self {
-      name,
+      name: M::MENU_NAME,
       selected: 0,
       options,
+      option_rects: RefCell::new(Vec::new()),
+      viewport_height: None,
+      scroll_offset: 0,
+      visible_option_count: Cell::new(usize::MAX),
     }
   }
 
+  /// Limits the options to a band of the given height in pixels below the menu's position,
+  /// scrolling through the options as the cursor moves past either edge of the band.
+  ///
+  /// Options partially in the band are cut off at its edge.
+  pub fn with_viewport_height(mut self, viewport_height: Option<u32>) -> Self {
+    self.viewport_height = viewport_height;
+
+    self
+  }
+
+  /// Returns the index of the first option drawn in the viewport.
+  pub fn scroll_offset(&self) -> usize {
+    self.scroll_offset
+  }
+
   /// Returns the assigned name of this menu.
   pub fn name(&self) -> &'static str {
     self.name
@@ -73,6 +123,8 @@ impl Menu {
     } else {
       self.selected -= 1;
     }
+
+    self.scroll_to_selected();
   }
 
   /// Moves the cursor to the next option, wrapping back around to the first option
@@ -89,6 +141,121 @@ impl Menu {
     } else {
       self.selected += 1
     }
+
+    self.scroll_to_selected();
+  }
+
+  /// Scrolls just far enough for the selected option to be entirely in the viewport.
+  ///
+  /// How many options fit in the viewport is only known once the menu has been rendered,
+  /// before then every option is assumed to fit.
+  fn scroll_to_selected(&mut self) {
+    let visible_option_count = self.visible_option_count.get().max(1);
+
+    if self.selected < self.scroll_offset {
+      self.scroll_offset = self.selected;
+    } else if self.selected >= self.scroll_offset.saturating_add(visible_option_count) {
+      self.scroll_offset = self.selected + 1 - visible_option_count;
+    }
+  }
+
+  /// Moves the value of the selected option up or down by its step, returning the new value.
+  ///
+  /// None is returned if the selected option isn't a [`Slider`](Slider).
+  pub fn adjust_slider(&mut self, increase: bool) -> Option<u32> {
+    let slider = self.options.get_mut(self.selected)?.slider_mut()?;
+
+    if increase {
+      slider.increment();
+    } else {
+      slider.decrement();
+    }
+
+    Some(slider.value())
+  }
+
+  /// Flips the value of the selected option, returning the new value.
+  ///
+  /// None is returned if the selected option isn't a [`Toggle`](Toggle).
+  pub fn flip_toggle(&mut self) -> Option<bool> {
+    let toggle = self.options.get_mut(self.selected)?.toggle_mut()?;
+
+    Some(toggle.flip())
+  }
+
+  /// Sets the value of the toggle with the given item name, such as to match a loaded setting.
+  ///
+  /// Nothing happens if there's no toggle with the name.
+  pub fn set_toggle_value(&mut self, item_name: &str, value: bool) {
+    let toggle = self
+      .options
+      .iter_mut()
+      .filter(|option| option.name() == item_name)
+      .find_map(MenuItem::toggle_mut);
+
+    if let Some(toggle) = toggle {
+      toggle.set_value(value);
+    }
+  }
+
+  /// Sets the value of the slider with the given item name, such as to match a loaded setting.
+  ///
+  /// Nothing happens if there's no slider with the name.
+  pub fn set_slider_value(&mut self, item_name: &str, value: u32) {
+    let slider = self
+      .options
+      .iter_mut()
+      .filter(|option| option.name() == item_name)
+      .find_map(MenuItem::slider_mut);
+
+    if let Some(slider) = slider {
+      slider.set_value(value);
+    }
+  }
+
+  /// Moves the cursor to the option at the index.
+  ///
+  /// Nothing happens if there's no option at the index.
+  pub fn select(&mut self, index: usize) {
+    if index < self.options.len() {
+      self.selected = index;
+      self.scroll_to_selected();
+    }
+  }
+
+  /// Moves the cursor to the option with the given item name, returning true if there was one.
+  pub fn select_by_name(&mut self, item_name: &str) -> bool {
+    let Some(index) = self.index_of(item_name) else {
+      return false;
+    };
+
+    self.select(index);
+
+    true
+  }
+
+  /// Returns the index of the option with the given item name.
+  pub fn index_of(&self, item_name: &str) -> Option<usize> {
+    self
+      .options
+      .iter()
+      .position(|option| option.name() == item_name)
+  }
+
+  /// Returns the index of the option drawn at the given position in the buffer.
+  ///
+  /// Options are found using where they were drawn the last time the menu was rendered,
+  /// so None is returned for every position until the menu has been rendered.
+  pub fn option_index_at(&self, position: &LogicalPosition<u32>) -> Option<usize> {
+    self
+      .option_rects
+      .borrow()
+      .iter()
+      .find(|(_, (top_left, dimensions))| {
+        (top_left.x..top_left.x + dimensions.width).contains(&position.x)
+          && (top_left.y..top_left.y + dimensions.height).contains(&position.y)
+      })
+      .map(|(index, _)| *index)
   }
 
   /// Returns the currently selected menu option.
@@ -124,37 +291,348 @@ impl Menu {
     renderer: &mut Renderer,
     option_spacing: u32,
   ) -> anyhow::Result<()> {
+    let theme = Theme {
+      option_spacing,
+      ..Default::default()
+    };
+
+    self.render_with_theme(assets, position, renderer, &theme)
+  }
+
+  /// Renders the menu to the buffer with the given offset, using the spacing and colors of the theme.
+  ///
+  /// Options with a [`label`](MenuItem::label) are drawn as text with the theme's text color,
+  /// or its highlight color if selected. Every other option is drawn with its image asset.
+  /// With a [`viewport`](Menu::with_viewport_height), only the options scrolled into it are drawn.
+  pub fn render_with_theme(
+    &self,
+    assets: &Assets,
+    position: &LogicalPosition<i32>,
+    renderer: &mut Renderer,
+    theme: &Theme,
+  ) -> anyhow::Result<()> {
+    let viewport = self.viewport_height.map(|viewport_height| {
+      (
+        LogicalPosition::new(0, position.y.max(0) as u32),
+        LogicalSize::new(renderer.buffer_dimensions().width, viewport_height),
+      )
+    });
+    let previous_clip = renderer.clip();
+
+    if viewport.is_some() {
+      renderer.set_clip(viewport);
+    }
+
+    let result = self.render_visible_options(assets, position, renderer, theme, viewport);
+    renderer.set_clip(previous_clip);
+
+    result
+  }
+
+  /// Draws each option starting from the scroll offset, stopping once an option starts past the bottom of the viewport.
+  fn render_visible_options(
+    &self,
+    assets: &Assets,
+    position: &LogicalPosition<i32>,
+    renderer: &mut Renderer,
+    theme: &Theme,
+    viewport: Option<ClipRegion>,
+  ) -> anyhow::Result<()> {
+    let viewport_bottom = viewport.map(|(top_left, dimensions)| top_left.y + dimensions.height);
     let mut previous_option_bottom = position.y as u32;
+    let mut option_rects = Vec::with_capacity(self.options.len());
+    let mut visible_option_count = 0;
+
+    for (index, menu_option) in self.options.iter().enumerate().skip(self.scroll_offset) {
+      let option_top = previous_option_bottom + theme.option_spacing;
+
+      if viewport_bottom.is_some_and(|viewport_bottom| option_top >= viewport_bottom) {
+        break;
+      }
 
-    for menu_option in self.options.iter() {
+      let (option_position, option_dimensions) = self.render_option(
+        assets,
+        position,
+        renderer,
+        theme,
+        index,
+        menu_option,
+        option_top,
+      )?;
+
+      if index == self.selected {
+        Self::draw_menu_selection_indicator(
+          renderer,
+          &option_position,
+          &option_dimensions,
+          theme.highlight_color,
+        )?;
+      }
+
+      previous_option_bottom = option_top + option_dimensions.height;
+      option_rects.push((index, (option_position, option_dimensions)));
+
+      if viewport_bottom.is_none_or(|viewport_bottom| previous_option_bottom <= viewport_bottom) {
+        visible_option_count += 1;
+      }
+    }
+
+    *self.option_rects.borrow_mut() = option_rects;
+    self.visible_option_count.set(visible_option_count);
+
+    Ok(())
+  }
+
+  /// Draws a single option with its top at the given y, returning where it was drawn.
+  #[allow(clippy::too_many_arguments)]
+  fn render_option(
+    &self,
+    assets: &Assets,
+    position: &LogicalPosition<i32>,
+    renderer: &mut Renderer,
+    theme: &Theme,
+    index: usize,
+    menu_option: &MenuItem,
+    option_top: u32,
+  ) -> anyhow::Result<OptionRect> {
+    let Some(label) = menu_option.label() else {
       let Some(image_asset) = assets.get_image(menu_option.asset_name()) else {
         return Err(anyhow!("Failed to load asset {}", menu_option.asset_name()));
       };
       let (image_width, image_height) = image_asset.dimensions();
 
+      let buffer_dimensions = renderer.buffer_dimensions();
       let position = LogicalPosition {
-        x: (((RENDERED_WINDOW_DIMENSIONS.width / 2) - (image_width / 2)) as i32 + position.x).max(0)
-          as u32,
-        y: previous_option_bottom + option_spacing,
+        x: Self::centered_x(buffer_dimensions.width, image_width, position.x),
+        y: option_top,
       };
 
-      renderer.render_image(&position, image_asset, &RENDERED_WINDOW_DIMENSIONS)?;
+      renderer.render_image(&position, image_asset, &buffer_dimensions)?;
+
+      return Ok((position, LogicalSize::new(image_width, image_height)));
+    };
+
+    let color = if index == self.selected {
+      theme.highlight_color
+    } else {
+      theme.text_color
+    };
 
-      previous_option_bottom = position.y + image_height;
+    match (menu_option.slider(), menu_option.toggle()) {
+      (Some(slider), _) => Self::render_slider(
+        renderer,
+        label,
+        slider,
+        position.x,
+        option_top,
+        theme.text_size,
+        color,
+      ),
+      (None, Some(toggle)) => Self::render_toggle(
+        renderer,
+        label,
+        toggle,
+        position.x,
+        option_top,
+        theme.text_size,
+        color,
+      ),
+      (None, None) => Self::render_label(
+        renderer,
+        label,
+        position.x,
+        option_top,
+        theme.text_size,
+        color,
+      ),
     }
+  }
 
-    Ok(())
+  /// Draws an arrow pointing at the left side of the selected option, vertically centered on it.
+  fn draw_menu_selection_indicator(
+    renderer: &mut Renderer,
+    option_position: &LogicalPosition<u32>,
+    option_dimensions: &LogicalSize<u32>,
+    color: [u8; 4],
+  ) -> anyhow::Result<()> {
+    let tip = Self::selection_indicator_tip(option_position, option_dimensions);
+
+    renderer.draw_arrow(
+      &tip,
+      Self::SELECTION_INDICATOR_LENGTH,
+      Direction::Right,
+      color,
+    )
+  }
+
+  /// Returns where the tip of the selection arrow goes for an option drawn at the given position.
+  ///
+  /// Options too close to the left edge of the window for the arrow to fit have it pushed right,
+  /// keeping the whole arrow in the window.
+  fn selection_indicator_tip(
+    option_position: &LogicalPosition<u32>,
+    option_dimensions: &LogicalSize<u32>,
+  ) -> LogicalPosition<i32> {
+    let option_center = center_of(option_position, option_dimensions);
+    let tip_x = option_position
+      .x
+      .saturating_sub(Self::SELECTION_INDICATOR_GAP)
+      .max(Self::SELECTION_INDICATOR_LENGTH);
+
+    LogicalPosition::new(tip_x, option_center.y).cast()
+  }
+
+  /// Draws the label horizontally centered in the window, returning the position and dimensions of the drawn text.
+  fn render_label(
+    renderer: &mut Renderer,
+    label: &str,
+    x_offset: i32,
+    y: u32,
+    text_size: f32,
+    color: [u8; 4],
+  ) -> anyhow::Result<OptionRect> {
+    let text_dimensions = TextBox::measure(renderer, 0, label, text_size)?;
+
+    let buffer_dimensions = renderer.buffer_dimensions();
+    let position = LogicalPosition::new(
+      Self::centered_x(buffer_dimensions.width, text_dimensions.width, x_offset),
+      y,
+    );
+    let text_box = TextBox::new(renderer, 0, label, &position, text_size);
+
+    renderer.render_text_box(&text_box, color, &buffer_dimensions)?;
+
+    Ok((position, text_dimensions))
+  }
+
+  /// Draws the label followed by the slider's value, with a bar below it filled up to the value.
+  ///
+  /// Returns the position and dimensions of the label and bar together.
+  fn render_slider(
+    renderer: &mut Renderer,
+    label: &str,
+    slider: &Slider,
+    x_offset: i32,
+    y: u32,
+    text_size: f32,
+    color: [u8; 4],
+  ) -> anyhow::Result<OptionRect> {
+    let label = format!("{} {}", label, slider.value());
+    let (label_position, label_dimensions) =
+      Self::render_label(renderer, &label, x_offset, y, text_size, color)?;
+
+    let bar_dimensions = Self::SLIDER_BAR_DIMENSIONS;
+    let bar_position = LogicalPosition::new(
+      Self::centered_x(
+        renderer.buffer_dimensions().width,
+        bar_dimensions.width,
+        x_offset,
+      ),
+      y + label_dimensions.height + Self::SLIDER_BAR_GAP,
+    );
+    let filled_dimensions = LogicalSize::new(
+      (bar_dimensions.width as f32 * slider.fraction()).round() as u32,
+      bar_dimensions.height,
+    );
+
+    renderer.filled_rectangle(&bar_position, &filled_dimensions, color)?;
+    renderer.bounding_rectangle(
+      &bar_position.cast(),
+      &LogicalPosition::new(
+        (bar_position.x + bar_dimensions.width - 1) as i32,
+        (bar_position.y + bar_dimensions.height - 1) as i32,
+      ),
+      color,
+    )?;
+
+    let left = label_position.x.min(bar_position.x);
+    let right =
+      (label_position.x + label_dimensions.width).max(bar_position.x + bar_dimensions.width);
+
+    Ok((
+      LogicalPosition::new(left, y),
+      LogicalSize::new(right - left, bar_position.y + bar_dimensions.height - y),
+    ))
+  }
+
+  /// Draws the label with a box beside it, which is filled in while the toggle is on.
+  ///
+  /// The label and box are centered together. Returns the position and dimensions of the label and box together.
+  fn render_toggle(
+    renderer: &mut Renderer,
+    label: &str,
+    toggle: &Toggle,
+    x_offset: i32,
+    y: u32,
+    text_size: f32,
+    color: [u8; 4],
+  ) -> anyhow::Result<OptionRect> {
+    let indicator_width = Self::TOGGLE_INDICATOR_GAP + Self::TOGGLE_INDICATOR_SIZE;
+    let (label_position, label_dimensions) = Self::render_label(
+      renderer,
+      label,
+      x_offset - (indicator_width / 2) as i32,
+      y,
+      text_size,
+      color,
+    )?;
+
+    let indicator_size = Self::TOGGLE_INDICATOR_SIZE;
+    let indicator_position = LogicalPosition::new(
+      label_position.x + label_dimensions.width + Self::TOGGLE_INDICATOR_GAP,
+      y + label_dimensions.height.saturating_sub(indicator_size) / 2,
+    );
+
+    if toggle.value() {
+      renderer.filled_rectangle(
+        &indicator_position,
+        &LogicalSize::new(indicator_size, indicator_size),
+        color,
+      )?;
+    }
+
+    renderer.bounding_rectangle(
+      &indicator_position.cast(),
+      &LogicalPosition::new(
+        (indicator_position.x + indicator_size - 1) as i32,
+        (indicator_position.y + indicator_size - 1) as i32,
+      ),
+      color,
+    )?;
+
+    Ok((
+      label_position,
+      LogicalSize::new(
+        label_dimensions.width + indicator_width,
+        label_dimensions.height.max(indicator_size),
+      ),
+    ))
+  }
+
+  /// Returns the x position that horizontally centers something of the given width in a window of the given width.
+  ///
+  /// Anything wider than the window is placed at its left edge, as is anything offset past it.
+  fn centered_x(window_width: u32, width: u32, x_offset: i32) -> u32 {
+    let window_center = center_of(
+      &LogicalPosition::new(0, 0),
+      &LogicalSize::new(window_width, 0),
+    );
+    let centered_x = window_center.x as i64 - (width / 2) as i64 + x_offset as i64;
+
+    centered_x.clamp(0, u32::MAX as i64) as u32
   }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::game::resolution::RenderResolution;
+  use crate::rustris_config::RENDERED_WINDOW_DIMENSIONS;
   use test_data::*;
 
   #[test]
   fn cursor_moves_as_expected() {
-    let mut menu = Menu::new::<TestMenu>("test_menu");
+    let mut menu = Menu::new::<TestMenu>();
 
     let expected_options: Vec<MenuItem> = TestMenu::full_list();
 
@@ -171,12 +649,320 @@ mod tests {
     assert_eq!(menu.current_option(), expected_options.get(2));
   }
 
+  #[test]
+  fn menus_are_named_after_their_items() {
+    use crate::menus::templates::main_menu::{MainMenu, MainMenuItems};
+
+    assert_eq!(Menu::new::<MainMenuItems>().name(), MainMenu::MENU_NAME);
+    assert_eq!(Menu::new::<TestMenu>().name(), "test_menu");
+  }
+
+  #[test]
+  fn options_are_selected_by_name() {
+    let mut menu = Menu::new::<TestMenu>();
+
+    assert_eq!(menu.index_of("exit"), Some(2));
+    assert!(menu.select_by_name("exit"));
+    assert_eq!(menu.cursor_position(), 2);
+
+    assert_eq!(menu.index_of("missing"), None);
+    assert!(!menu.select_by_name("missing"));
+    assert_eq!(menu.cursor_position(), 2);
+  }
+
+  #[test]
+  fn labels_are_drawn_with_the_theme_colors() {
+    let assets = Assets::load_assets();
+    let mut renderer = Renderer::new_in_memory(RENDERED_WINDOW_DIMENSIONS);
+    renderer
+      .load_font_from_bytes(assets.get_font("gadugi").unwrap(), "gadugi")
+      .unwrap();
+
+    let menu = Menu::new::<TestTextMenu>();
+    let theme = Theme {
+      highlight_color: [255, 0, 0, 255],
+      text_color: [0, 255, 0, 255],
+      ..Default::default()
+    };
+
+    menu
+      .render_with_theme(&assets, &LogicalPosition::new(0, 0), &mut renderer, &theme)
+      .unwrap();
+
+    let pixels: Vec<&[u8]> = renderer.frame().chunks_exact(4).collect();
+
+    assert!(pixels.contains(&&theme.text_color[..]));
+    assert!(pixels.contains(&&theme.highlight_color[..]));
+  }
+
+  #[test]
+  fn menus_can_mix_image_and_text_options() {
+    let assets = Assets::load_assets();
+    let mut renderer = Renderer::new_in_memory(RENDERED_WINDOW_DIMENSIONS);
+    renderer
+      .load_font_from_bytes(assets.get_font("gadugi").unwrap(), "gadugi")
+      .unwrap();
+    let menu = Menu::new::<TestMixedMenu>();
+
+    menu
+      .render_with_theme(
+        &assets,
+        &LogicalPosition::new(0, 0),
+        &mut renderer,
+        &Theme::default(),
+      )
+      .unwrap();
+
+    let option_rects = menu.option_rects.borrow();
+    let image_dimensions = assets.get_image("menu_exit").unwrap().dimensions();
+
+    assert_eq!(option_rects.len(), 2);
+    assert_eq!(
+      option_rects[0].1 .1,
+      LogicalSize::new(image_dimensions.0, image_dimensions.1)
+    );
+    assert!(option_rects[1].1 .1.width > 0);
+  }
+
+  #[test]
+  fn cursor_position_maps_to_the_option_under_it() {
+    let assets = Assets::load_assets();
+    let mut renderer = Renderer::new_in_memory(RENDERED_WINDOW_DIMENSIONS);
+    renderer
+      .load_font_from_bytes(assets.get_font("gadugi").unwrap(), "gadugi")
+      .unwrap();
+    let mut menu = Menu::new::<TestTextMenu>();
+
+    assert_eq!(menu.option_index_at(&LogicalPosition::new(125, 40)), None);
+
+    menu
+      .render_with_theme(
+        &assets,
+        &LogicalPosition::new(0, 0),
+        &mut renderer,
+        &Theme::default(),
+      )
+      .unwrap();
+
+    let option_rects = menu.option_rects.borrow().clone();
+    let (_, (second_position, second_dimensions)) = option_rects[1];
+    let second_center = LogicalPosition::new(
+      second_position.x + second_dimensions.width / 2,
+      second_position.y + second_dimensions.height / 2,
+    );
+
+    // A window 3 times the size of the buffer with 150 pixel bars on each side.
+    renderer
+      .resize_surface(PhysicalSize::new(
+        RENDERED_WINDOW_DIMENSIONS.width * 3 + 300,
+        RENDERED_WINDOW_DIMENSIONS.height * 3,
+      ))
+      .unwrap();
+    let window_position = PhysicalPosition::new(
+      (second_center.x * 3 + 150) as f32,
+      (second_center.y * 3) as f32,
+    );
+    let buffer_position = renderer.window_to_buffer_position(window_position).unwrap();
+
+    assert_eq!(menu.option_index_at(&buffer_position), Some(1));
+    assert_eq!(menu.option_index_at(&LogicalPosition::new(0, 0)), None);
+
+    menu.select(menu.option_index_at(&buffer_position).unwrap());
+
+    assert_eq!(menu.cursor_position(), 1);
+  }
+
+  #[test]
+  fn left_and_right_adjust_the_selected_slider() {
+    let mut menu = Menu::new::<TestSliderMenu>();
+
+    assert_eq!(menu.adjust_slider(true), None);
+
+    menu.next();
+
+    assert_eq!(menu.adjust_slider(true), Some(5));
+    assert_eq!(menu.adjust_slider(true), Some(5));
+    assert_eq!(menu.adjust_slider(false), Some(3));
+
+    menu.set_slider_value("slider", 0);
+
+    assert_eq!(menu.adjust_slider(false), Some(1));
+  }
+
+  #[test]
+  fn toggle_indicator_is_filled_while_on() {
+    let assets = Assets::load_assets();
+    let mut renderer = Renderer::new_in_memory(RENDERED_WINDOW_DIMENSIONS);
+    renderer
+      .load_font_from_bytes(assets.get_font("gadugi").unwrap(), "gadugi")
+      .unwrap();
+    let mut menu = Menu::new::<TestToggleMenu>();
+    let theme = Theme::default();
+
+    let mut render_indicator_center = |menu: &Menu| {
+      renderer.clear().unwrap();
+      menu
+        .render_with_theme(&assets, &LogicalPosition::new(0, 0), &mut renderer, &theme)
+        .unwrap();
+
+      let (_, (position, dimensions)) = menu.option_rects.borrow()[0];
+      let center = LogicalPosition::new(
+        position.x + dimensions.width - (Menu::TOGGLE_INDICATOR_SIZE / 2),
+        position.y + dimensions.height / 2,
+      );
+      let pixel_index = ((center.x + center.y * RENDERED_WINDOW_DIMENSIONS.width) * 4) as usize;
+
+      renderer.frame()[pixel_index..pixel_index + 4].to_vec()
+    };
+
+    assert_eq!(render_indicator_center(&menu), [0, 0, 0, 255]);
+    assert_eq!(menu.flip_toggle(), Some(true));
+    assert_eq!(render_indicator_center(&menu), theme.highlight_color);
+  }
+
+  #[test]
+  fn oversized_options_are_clamped_to_the_left_edge() {
+    let window_width = RENDERED_WINDOW_DIMENSIONS.width;
+
+    assert_eq!(Menu::centered_x(window_width, window_width * 2, 0), 0);
+    assert_eq!(Menu::centered_x(window_width, u32::MAX, 0), 0);
+    assert_eq!(Menu::centered_x(window_width, 10, -1000), 0);
+    assert_eq!(
+      Menu::centered_x(window_width, 10, 0),
+      (window_width / 2) - 5
+    );
+  }
+
+  #[test]
+  fn options_are_centered_at_every_resolution() {
+    for resolution in RenderResolution::ALL {
+      let window_width = resolution.dimensions().width;
+
+      assert_eq!(
+        Menu::centered_x(window_width, 10, 0),
+        (window_width / 2) - 5
+      );
+    }
+  }
+
+  #[test]
+  fn selection_indicator_stays_in_the_window() {
+    let option_dimensions = LogicalSize::new(20, 10);
+    let min_tip_x = Menu::SELECTION_INDICATOR_LENGTH as i32;
+
+    for option_x in [0, 1, Menu::SELECTION_INDICATOR_GAP] {
+      let tip =
+        Menu::selection_indicator_tip(&LogicalPosition::new(option_x, 30), &option_dimensions);
+
+      assert_eq!(tip, LogicalPosition::new(min_tip_x, 35));
+    }
+
+    let tip = Menu::selection_indicator_tip(&LogicalPosition::new(100, 30), &option_dimensions);
+
+    assert_eq!(
+      tip,
+      LogicalPosition::new(100 - Menu::SELECTION_INDICATOR_GAP as i32, 35)
+    );
+  }
+
+  #[test]
+  fn moving_past_the_viewport_scrolls() {
+    let assets = Assets::load_assets();
+    let mut renderer = Renderer::new_in_memory(RENDERED_WINDOW_DIMENSIONS);
+    renderer
+      .load_font_from_bytes(assets.get_font("gadugi").unwrap(), "gadugi")
+      .unwrap();
+    let theme = Theme::default();
+    let position = LogicalPosition::new(0, 0);
+    let menu = Menu::new::<TestLongMenu>();
+
+    menu
+      .render_with_theme(&assets, &position, &mut renderer, &theme)
+      .unwrap();
+
+    // Enough room for 2 options, and part of a third.
+    let (_, (third_position, third_dimensions)) = menu.option_rects.borrow()[2];
+    let viewport_height = third_position.y + third_dimensions.height / 2;
+    let mut menu = menu.with_viewport_height(Some(viewport_height));
+
+    renderer.clear().unwrap();
+    menu
+      .render_with_theme(&assets, &position, &mut renderer, &theme)
+      .unwrap();
+
+    assert_eq!(menu.visible_option_count.get(), 2);
+    // The partially visible third option is still drawn.
+    assert_eq!(menu.option_rects.borrow().len(), 3);
+
+    menu.next();
+    assert_eq!(menu.scroll_offset(), 0);
+
+    menu.next();
+    assert_eq!(menu.scroll_offset(), 1);
+
+    menu.next();
+    menu.next();
+    assert_eq!(menu.scroll_offset(), 3);
+
+    menu
+      .render_with_theme(&assets, &position, &mut renderer, &theme)
+      .unwrap();
+
+    assert_eq!(menu.option_rects.borrow()[0].0, 3);
+    assert!(renderer
+      .frame()
+      .chunks_exact(4)
+      .skip((viewport_height * RENDERED_WINDOW_DIMENSIONS.width) as usize)
+      .all(|pixel| pixel == [0, 0, 0, 255]));
+
+    // Wrapping to the first option scrolls back to the top.
+    menu.next();
+    assert_eq!(menu.scroll_offset(), 0);
+  }
+
   mod test_data {
     use super::*;
     use crate::define_menu_items;
 
     define_menu_items! {
-      pub enum TestMenu {
+      pub enum TestTextMenu(menu_name = "test_text_menu") {
+        First(item_name = "first", asset_name = "unknown", label = "FIRST"),
+        Second(item_name = "second", asset_name = "unknown", label = "SECOND"),
+      }
+    }
+
+    define_menu_items! {
+      pub enum TestLongMenu(menu_name = "test_long_menu") {
+        First(item_name = "first", asset_name = "unknown", label = "FIRST"),
+        Second(item_name = "second", asset_name = "unknown", label = "SECOND"),
+        Third(item_name = "third", asset_name = "unknown", label = "THIRD"),
+        Fourth(item_name = "fourth", asset_name = "unknown", label = "FOURTH"),
+        Fifth(item_name = "fifth", asset_name = "unknown", label = "FIFTH"),
+      }
+    }
+
+    define_menu_items! {
+      pub enum TestToggleMenu(menu_name = "test_toggle_menu") {
+        Toggle(item_name = "toggle", asset_name = "unknown", label = "TOGGLE", toggle = false),
+      }
+    }
+
+    define_menu_items! {
+      pub enum TestSliderMenu(menu_name = "test_slider_menu") {
+        Plain(item_name = "plain", asset_name = "unknown", label = "PLAIN"),
+        Slider(item_name = "slider", asset_name = "unknown", label = "SLIDER", slider = (1, 5, 2, 3)),
+      }
+    }
+
+    define_menu_items! {
+      pub enum TestMixedMenu(menu_name = "test_mixed_menu") {
+        Image(item_name = "image", asset_name = "menu_exit"),
+        Text(item_name = "text", asset_name = "unknown", label = "TEXT"),
+      }
+    }
+
+    define_menu_items! {
+      pub enum TestMenu(menu_name = "test_menu") {
         Start(item_name = "start", asset_name = "start_asset"),
         Options(item_name = "options", asset_name = "options_asset"),
         Exit(item_name = "exit", asset_name = "exit_asset"),
@@ -2,11 +2,61 @@
 pub struct MenuItem {
   name: &'static str,
   asset_name: &'static str,
+  /// The text drawn in place of the asset for text based menus.
+  label: Option<&'static str>,
+  /// The numeric value this item adjusts, if it's a slider.
+  slider: Option<Slider>,
+  /// The value this item switches on and off, if it's a toggle.
+  toggle: Option<Toggle>,
+}
+
+/// A numeric value that can be moved between a min and max by a fixed step, such as a setting.
+///
+/// The value always stays within the bounds, even if it was created outside of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slider {
+  min: u32,
+  max: u32,
+  step: u32,
+  value: u32,
+}
+
+/// A value that's either on or off, such as a setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Toggle {
+  value: bool,
 }
 
 impl MenuItem {
   pub fn new(name: &'static str, asset_name: &'static str) -> Self {
-    Self { name, asset_name }
+    Self {
+      name,
+      asset_name,
+      label: None,
+      slider: None,
+      toggle: None,
+    }
+  }
+
+  /// Turns this item into a toggle, or back into a plain item with None.
+  pub fn with_toggle(mut self, toggle: Option<Toggle>) -> Self {
+    self.toggle = toggle;
+
+    self
+  }
+
+  /// Turns this item into a slider, or back into a plain item with None.
+  pub fn with_slider(mut self, slider: Option<Slider>) -> Self {
+    self.slider = slider;
+
+    self
+  }
+
+  /// Sets the text drawn in place of this item's asset.
+  pub fn with_label(mut self, label: Option<&'static str>) -> Self {
+    self.label = label;
+
+    self
   }
 
   pub fn name(&self) -> &'static str {
@@ -16,12 +66,121 @@ impl MenuItem {
   pub fn asset_name(&self) -> &'static str {
     self.asset_name
   }
+
+  /// Returns the text drawn in place of this item's asset.
+  ///
+  /// None is returned if this item is drawn with its asset.
+  pub fn label(&self) -> Option<&'static str> {
+    self.label
+  }
+
+  /// Returns the value this item adjusts.
+  ///
+  /// None is returned if this item isn't a slider.
+  pub fn slider(&self) -> Option<&Slider> {
+    self.slider.as_ref()
+  }
+
+  pub fn slider_mut(&mut self) -> Option<&mut Slider> {
+    self.slider.as_mut()
+  }
+
+  /// Returns the value this item switches.
+  ///
+  /// None is returned if this item isn't a toggle.
+  pub fn toggle(&self) -> Option<&Toggle> {
+    self.toggle.as_ref()
+  }
+
+  pub fn toggle_mut(&mut self) -> Option<&mut Toggle> {
+    self.toggle.as_mut()
+  }
+}
+
+impl Toggle {
+  pub fn new(value: bool) -> Self {
+    Self { value }
+  }
+
+  pub fn value(&self) -> bool {
+    self.value
+  }
+
+  pub fn set_value(&mut self, value: bool) {
+    self.value = value;
+  }
+
+  /// Switches the value, returning the new one.
+  pub fn flip(&mut self) -> bool {
+    self.value = !self.value;
+
+    self.value
+  }
+}
+
+impl Slider {
+  /// Creates a slider with the value clamped between the min and max.
+  ///
+  /// A max below the min is raised to the min, and a step of 0 is raised to 1.
+  pub fn new(min: u32, max: u32, step: u32, value: u32) -> Self {
+    let max = max.max(min);
+
+    Self {
+      min,
+      max,
+      step: step.max(1),
+      value: value.clamp(min, max),
+    }
+  }
+
+  pub fn min(&self) -> u32 {
+    self.min
+  }
+
+  pub fn max(&self) -> u32 {
+    self.max
+  }
+
+  pub fn step(&self) -> u32 {
+    self.step
+  }
+
+  pub fn value(&self) -> u32 {
+    self.value
+  }
+
+  /// Sets the value, clamped between the min and max.
+  pub fn set_value(&mut self, value: u32) {
+    self.value = value.clamp(self.min, self.max);
+  }
+
+  /// Moves the value up by the step, stopping at the max.
+  pub fn increment(&mut self) {
+    self.set_value(self.value.saturating_add(self.step));
+  }
+
+  /// Moves the value down by the step, stopping at the min.
+  pub fn decrement(&mut self) {
+    self.set_value(self.value.saturating_sub(self.step));
+  }
+
+  /// Returns how far along the value is from the min to the max, from 0.0 to 1.0.
+  pub fn fraction(&self) -> f32 {
+    if self.max == self.min {
+      return 1.0;
+    }
+
+    (self.value - self.min) as f32 / (self.max - self.min) as f32
+  }
 }
 
 /// This trait will label the items for a menu.
 ///
 /// Each item will have a way to convert into the name of its button asset.
 pub trait MenuItemData {
+  /// The name of the menu made from these items, which [`Menu::new`](crate::menus::menu_data::Menu::new) gives it.
+  const MENU_NAME: &'static str;
+
   /// Gets the name of an individual menu item.
   fn item_name(&self) -> &'static str {
     "unknown"
@@ -33,6 +192,29 @@ pub trait MenuItemData {
     "unknown"
   }
 
+  /// Gets the text label for an individual menu item.
+  ///
+  /// Items with a label are drawn as text rather than with their asset.
+  fn label(&self) -> Option<&'static str> {
+    None
+  }
+
+  /// Gets the starting value of an individual menu item, if it's a slider.
+  ///
+  /// Sliders are moved with [`MenuAction::Left`](crate::game::actions::MenuAction) and
+  /// [`MenuAction::Right`](crate::game::actions::MenuAction) instead of being selected.
+  fn slider(&self) -> Option<Slider> {
+    None
+  }
+
+  /// Gets the starting value of an individual menu item, if it's a toggle.
+  ///
+  /// Toggles are flipped with [`MenuAction::Select`](crate::game::actions::MenuAction),
+  /// [`MenuAction::Left`](crate::game::actions::MenuAction), and [`MenuAction::Right`](crate::game::actions::MenuAction).
+  fn toggle(&self) -> Option<Toggle> {
+    None
+  }
+
   /// Returns the list of every possible menu item in order, converted into [`MenuItem`](MenuItem)s
   fn full_list() -> Vec<MenuItem>;
 
@@ -46,7 +228,12 @@ pub trait MenuItemData {
 
 /// Defines the creation of an enum that can be used to create a [`Menu`](crate::menus::menu_data::Menu).
 ///
-/// When defining a menu, each variant needs an item name and asset name.
+/// When defining a menu, the enum is given the name of the menu it makes,
+/// and each variant needs an item name and either an asset name or text.
+/// Variants with text have no asset, and are drawn as that text.
+/// A variant with an asset can also be given a label, which is drawn as text in place of its asset.
+/// Following the text or label, a variant can be made into a [`Slider`](Slider) with its `(min, max, step, value)`,
+/// or into a [`Toggle`](Toggle) with its starting value.
 /// The syntax for creating will look something like this:
 ///
 /// ```
@@ -54,31 +241,80 @@ pub trait MenuItemData {
 /// use rustris::menus::menu_data::*;
 ///
 /// define_menu_items! {
-///   pub enum MainMenu {
+///   pub enum MainMenu(menu_name = "main_menu") {
 ///     Start(item_name = "start", asset_name = "menu_start"),
 ///     Settings(item_name = "settings", asset_name = "menu_settings"),
-///     Exit(item_name = "exit", asset_name = "menu_exit"),
+///     Exit(item_name = "exit", asset_name = "menu_exit", label = "Exit"),
+///     Volume(item_name = "volume", text = "Volume", slider = (0, 100, 10, 50)),
+///     Mute(item_name = "mute", text = "Mute", toggle = false),
 ///   }
 /// }
 /// ```
 ///
 /// This will expand into creating the enum and implementing [`MenuItemData`](MenuItemData) and Into<[`MenuItem`](MenuItem)>,
 /// MenuItemData will allow for each item in the enum to have methods for obtaining the item's
-/// name and the name of its corresponding asset.
+/// name and the name of its corresponding asset, along with the name of the menu.
 /// Into<[`MenuItem`](MenuItem)> Will allow for the creation of a [`Menu`](crate::menus::menu_data::Menu).
 ///
 /// Creating a menu will end up looking like this:
 ///
-/// ```ignore,no_run
-/// let menu_name = "main_menu";
+/// ```
+/// use rustris::menus::menu_data::Menu;
+/// use rustris::menus::templates::main_menu::{MainMenu, MainMenuItems};
+///
+/// // The menu is named after its items.
+/// let menu = Menu::new::<MainMenuItems>();
 ///
-/// Menu::new::<MainMenu>(menu_name)
+/// assert_eq!(menu.name(), MainMenu::MENU_NAME);
 /// ```
 #[macro_export]
 macro_rules! define_menu_items {
+  (@asset_name [$asset_value:literal] []) => {
+    $asset_value
+  };
+  (@asset_name [] [$text_value:literal]) => {
+    "unknown"
+  };
+  (@asset_name [] []) => {
+    compile_error!("Every menu item needs either an `asset_name` or `text`.")
+  };
+  (@asset_name [$asset_value:literal] [$text_value:literal]) => {
+    compile_error!("A menu item with `text` has no asset, remove its `asset_name`.")
+  };
+  (@label [] []) => {
+    None
+  };
+  (@label [$label_value:literal] []) => {
+    Some($label_value)
+  };
+  (@label [] [$text_value:literal]) => {
+    Some($text_value)
+  };
+  (@label [$label_value:literal] [$text_value:literal]) => {
+    compile_error!("A menu item's `text` is already drawn as its label, remove its `label`.")
+  };
+  (@slider) => {
+    None
+  };
+  (@slider $min:literal, $max:literal, $step:literal, $value:literal) => {
+    Some($crate::menus::menu_items::Slider::new($min, $max, $step, $value))
+  };
+  (@toggle) => {
+    None
+  };
+  (@toggle $toggle_value:literal) => {
+    Some($crate::menus::menu_items::Toggle::new($toggle_value))
+  };
   {
-    pub enum $name:ident {
-      $($variant:ident ( item_name = $name_value:literal, asset_name = $asset_value:literal ) ),* $(,)?
+    pub enum $name:ident (menu_name = $menu_name:literal) {
+      $($variant:ident (
+        item_name = $name_value:literal
+        $(, asset_name = $asset_value:literal)?
+        $(, text = $text_value:literal)?
+        $(, label = $label_value:literal)?
+        $(, slider = ($min:literal, $max:literal, $step:literal, $value:literal))?
+        $(, toggle = $toggle_value:literal)?
+      ) ),* $(,)?
     }
   } => {
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -87,6 +323,8 @@ macro_rules! define_menu_items {
     }
 
     impl $crate::menus::menu_items::MenuItemData for $name {
+      const MENU_NAME: &'static str = $menu_name;
+
       fn item_name(&self) -> &'static str {
         match &self {
           $(Self::$variant => $name_value),*,
@@ -95,7 +333,25 @@ macro_rules! define_menu_items {
 
       fn asset_name(&self) -> &'static str {
         match &self {
-          $(Self::$variant => $asset_value),*,
+          $(Self::$variant => $crate::define_menu_items!(@asset_name [$($asset_value)?] [$($text_value)?])),*,
+        }
+      }
+
+      fn label(&self) -> Option<&'static str> {
+        match &self {
+          $(Self::$variant => $crate::define_menu_items!(@label [$($label_value)?] [$($text_value)?])),*,
+        }
+      }
+
+      fn slider(&self) -> Option<$crate::menus::menu_items::Slider> {
+        match &self {
+          $(Self::$variant => $crate::define_menu_items!(@slider $($min, $max, $step, $value)?)),*,
+        }
+      }
+
+      fn toggle(&self) -> Option<$crate::menus::menu_items::Toggle> {
+        match &self {
+          $(Self::$variant => $crate::define_menu_items!(@toggle $($toggle_value)?)),*,
         }
       }
 
@@ -117,6 +373,9 @@ macro_rules! define_menu_items {
     impl From<&$name> for $crate::menus::menu_items::MenuItem {
       fn from(menu_item: &$name) -> $crate::menus::menu_items::MenuItem {
         $crate::menus::menu_items::MenuItem::new(menu_item.item_name(), menu_item.asset_name())
+          .with_label(menu_item.label())
+          .with_slider(menu_item.slider())
+          .with_toggle(menu_item.toggle())
       }
     }
 
@@ -164,16 +423,121 @@ mod tests {
     assert_eq!(list, expected_list);
   }
 
+  mod slider_logic {
+    use super::*;
+
+    #[test]
+    fn sliders_move_by_the_step_and_clamp_at_the_bounds() {
+      let mut slider = Slider::new(0, 10, 4, 6);
+
+      slider.increment();
+      assert_eq!(slider.value(), 10);
+      slider.increment();
+      assert_eq!(slider.value(), 10);
+
+      slider.decrement();
+      assert_eq!(slider.value(), 6);
+      slider.decrement();
+      assert_eq!(slider.value(), 2);
+      slider.decrement();
+      assert_eq!(slider.value(), 0);
+      slider.decrement();
+      assert_eq!(slider.value(), 0);
+    }
+
+    #[test]
+    fn slider_values_start_within_the_bounds() {
+      assert_eq!(Slider::new(20, 144, 4, 200).value(), 144);
+      assert_eq!(Slider::new(20, 144, 4, 0).value(), 20);
+    }
+
+    #[test]
+    fn macro_declares_slider_items() {
+      let list = TestSliderMenu::full_list();
+
+      assert_eq!(list[0].slider(), None);
+      assert_eq!(list[1].slider(), Some(&Slider::new(0, 100, 10, 50)));
+      assert_eq!(list[1].label(), Some("Volume"));
+    }
+  }
+
+  mod toggle_logic {
+    use super::*;
+
+    #[test]
+    fn flipping_switches_the_value() {
+      let mut toggle = Toggle::new(false);
+
+      assert!(toggle.flip());
+      assert!(toggle.value());
+      assert!(!toggle.flip());
+      assert!(!toggle.value());
+    }
+
+    #[test]
+    fn macro_declares_toggle_items() {
+      let list = TestSliderMenu::full_list();
+
+      assert_eq!(list[0].toggle(), None);
+      assert_eq!(list[2].toggle(), Some(&Toggle::new(true)));
+      assert_eq!(list[2].slider(), None);
+    }
+  }
+
+  mod text_item_logic {
+    use super::*;
+
+    #[test]
+    fn text_items_are_labelled_without_an_asset() {
+      let list = TestMixedMenu::full_list();
+
+      assert_eq!(list[0], MenuItem::new("start", "start_asset"));
+      assert_eq!(list[1].asset_name(), "unknown");
+      assert_eq!(list[1].label(), Some("FPS:"));
+      assert_eq!(list[2].asset_name(), "exit_asset");
+      assert_eq!(list[2].label(), Some("Exit"));
+      assert_eq!(list[3].label(), Some("Mute"));
+      assert_eq!(list[3].toggle(), Some(&Toggle::new(true)));
+    }
+
+    #[test]
+    fn text_items_convert_back_from_menu_items() {
+      let fps_item = MenuItem::new("fps", "unknown").with_label(Some("FPS:"));
+
+      assert_eq!(
+        TestMixedMenu::from_menu_item(&fps_item),
+        Some(TestMixedMenu::Fps)
+      );
+      assert_eq!(TestMixedMenu::Fps.item_name(), "fps");
+    }
+  }
+
   mod test_data {
     use super::*;
-    use crate::define_menu_items;
 
     define_menu_items! {
-      pub enum TestMenu {
+      pub enum TestMixedMenu(menu_name = "test_mixed_menu") {
+        Start(item_name = "start", asset_name = "start_asset"),
+        Fps(item_name = "fps", text = "FPS:"),
+        Exit(item_name = "exit", asset_name = "exit_asset", label = "Exit"),
+        Mute(item_name = "mute", text = "Mute", toggle = true),
+      }
+    }
+
+    define_menu_items! {
+      pub enum TestMenu(menu_name = "test_menu") {
         Start(item_name = "start", asset_name = "start_asset"),
         Options(item_name = "options", asset_name = "options_asset"),
         Exit(item_name = "exit", asset_name = "exit_asset"),
       }
     }
+
+    define_menu_items! {
+      pub enum TestSliderMenu(menu_name = "test_slider_menu") {
+        Back(item_name = "back", asset_name = "unknown", label = "Back"),
+        Volume(item_name = "volume", asset_name = "unknown", label = "Volume", slider = (0, 100, 10, 50)),
+        Grid(item_name = "grid", asset_name = "unknown", label = "Grid", toggle = true),
+      }
+    }
   }
 }
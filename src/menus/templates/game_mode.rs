@@ -0,0 +1,22 @@
+use crate::{
+  define_menu_items,
+  menus::{menu_data::Menu, menu_items::*},
+};
+
+pub struct GameModeMenu;
+
+impl GameModeMenu {
+  pub const MENU_NAME: &'static str = GameModeMenuItems::MENU_NAME;
+
+  pub fn new_menu() -> Menu {
+    Menu::new::<GameModeMenuItems>()
+  }
+}
+
+define_menu_items! {
+  pub enum GameModeMenuItems(menu_name = "game_mode") {
+    Marathon(item_name = "marathon", text = "Marathon"),
+    Sprint(item_name = "sprint", text = "Sprint (40 lines)"),
+    Ultra(item_name = "ultra", text = "Ultra (2 minutes)"),
+  }
+}
@@ -0,0 +1,21 @@
+use crate::{
+  define_menu_items,
+  menus::{menu_data::Menu, menu_items::*},
+};
+
+pub struct GameOverMenu;
+
+impl GameOverMenu {
+  pub const MENU_NAME: &'static str = GameOverMenuItems::MENU_NAME;
+
+  pub fn new_menu() -> Menu {
+    Menu::new::<GameOverMenuItems>()
+  }
+}
+
+define_menu_items! {
+  pub enum GameOverMenuItems(menu_name = "game_over") {
+    Restart(item_name = "restart", text = "Restart"),
+    Quit(item_name = "quit", text = "Quit to menu"),
+  }
+}
@@ -4,47 +4,62 @@ use crate::{define_menu_items, menus::menu_data::*, menus::menu_items::*};
 pub struct Settings;
 
 impl Settings {
-  pub const GENERAL_SETTINGS_NAME: &'static str = "settings_menu";
-  pub const GAME_CONTROLS_NAME: &'static str = "game_controls";
-  pub const MENU_CONTROLS_NAME: &'static str = "menu_controls";
+  pub const GENERAL_SETTINGS_NAME: &'static str = GeneralSettingsMenuItems::MENU_NAME;
+  pub const GAME_CONTROLS_NAME: &'static str = GameControlsMenu::MENU_NAME;
+  pub const MENU_CONTROLS_NAME: &'static str = MenuControlsMenuItems::MENU_NAME;
+  /// The height in pixels of the band the options of each settings menu are scrolled through in.
+  pub const VIEWPORT_HEIGHT: u32 = 300;
 
   pub fn general_settings_menu() -> Menu {
-    Menu::new::<GeneralSettingsMenuItems>(Self::GENERAL_SETTINGS_NAME)
+    Menu::new::<GeneralSettingsMenuItems>().with_viewport_height(Some(Self::VIEWPORT_HEIGHT))
   }
 
   pub fn game_controls_menu() -> Menu {
-    Menu::new::<MenuControlsMenuItems>(Self::GAME_CONTROLS_NAME)
+    Menu::new::<GameControlsMenu>().with_viewport_height(Some(Self::VIEWPORT_HEIGHT))
   }
 
   pub fn menu_controls_menu() -> Menu {
-    Menu::new::<MenuControlsMenuItems>(Self::MENU_CONTROLS_NAME)
+    Menu::new::<MenuControlsMenuItems>().with_viewport_height(Some(Self::VIEWPORT_HEIGHT))
   }
 }
 
 define_menu_items! {
-  pub enum GeneralSettingsMenuItems {
-    Fps(item_name = "fps", asset_name = "unknown"),
+  pub enum GeneralSettingsMenuItems(menu_name = "settings_menu") {
+    Fps(item_name = "fps", text = "FPS", slider = (20, 144, 4, 144)),
+    Palette(item_name = "palette", text = "Color Palette"),
+    Grid(item_name = "grid", text = "Show Grid", toggle = true),
+    Mute(item_name = "mute", text = "Mute", toggle = false),
+    Fullscreen(item_name = "fullscreen", text = "Fullscreen", toggle = false),
+    Vsync(item_name = "vsync", text = "VSync", toggle = true),
+    Ghost(item_name = "ghost", text = "Ghost Piece", toggle = true),
+    GhostAlpha(item_name = "ghost_alpha", text = "Ghost Opacity", slider = (0, 255, 17, 85)),
+    GameControls(item_name = "game_controls", text = "Game Controls"),
+    MenuControls(item_name = "menu_controls", text = "Menu Controls"),
   }
 }
 
 define_menu_items! {
-  pub enum GameControlsMenu {
-    MoveLeft(item_name = "move_left", asset_name = "move_left_game_option_text"),
-    MoveRight(item_name = "move_right", asset_name = "move_right_game_option_text"),
-    HardDrop(item_name = "hard_drop", asset_name = "hard_drop_game_option_text"),
-    SoftDrop(item_name = "soft_drop", asset_name = "soft_drop_game_option_text"),
-    HoldPiece(item_name = "hold_piece", asset_name = "hold_piece_game_option_text"),
-    Pause(item_name = "pause", asset_name = "pause_game_option_text"),
+  pub enum GameControlsMenu(menu_name = "game_controls") {
+    MoveLeft(item_name = "move_left", text = "Move Left"),
+    MoveRight(item_name = "move_right", text = "Move Right"),
+    RotateCw(item_name = "rotate_cw", text = "Rotate Clockwise"),
+    RotateCcw(item_name = "rotate_ccw", text = "Rotate Counterclockwise"),
+    Rotate180(item_name = "rotate_180", text = "Rotate 180"),
+    HardDrop(item_name = "hard_drop", text = "Hard Drop"),
+    SoftDrop(item_name = "soft_drop", text = "Soft Drop"),
+    HoldPiece(item_name = "hold_piece", text = "Hold Piece"),
+    Pause(item_name = "pause", text = "Pause"),
+    QuitToMenu(item_name = "quit_to_menu", text = "Quit to Menu"),
   }
 }
 
 define_menu_items! {
-  pub enum MenuControlsMenuItems {
-    Up(item_name = "move_up", asset_name = "move_up_menu_option_text"),
-    Down(item_name = "move_down", asset_name = "move_down_menu_option_text"),
-    Left(item_name = "move_left", asset_name = "move_left_menu_option_text"),
-    Right(item_name = "move_right", asset_name = "move_right_menu_option_text"),
-    Select(item_name = "select", asset_name = "select_menu_option_text"),
-    Back(item_name = "back", asset_name = "back_menu_option_text"),
+  pub enum MenuControlsMenuItems(menu_name = "menu_controls") {
+    Up(item_name = "move_up", text = "Up"),
+    Down(item_name = "move_down", text = "Down"),
+    Left(item_name = "move_left", text = "Left"),
+    Right(item_name = "move_right", text = "Right"),
+    Select(item_name = "select", text = "Select"),
+    Back(item_name = "back", text = "Back"),
   }
 }
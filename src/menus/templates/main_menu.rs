@@ -6,17 +6,15 @@ use crate::{
 pub struct MainMenu;
 
 impl MainMenu {
-  pub const MENU_NAME: &'static str = "main_menu";
+  pub const MENU_NAME: &'static str = MainMenuItems::MENU_NAME;
 
   pub fn new_menu() -> Menu {
-    let menu_name = Self::MENU_NAME;
-
-    Menu::new::<MainMenuItems>(menu_name)
+    Menu::new::<MainMenuItems>()
   }
 }
 
 define_menu_items! {
-  pub enum MainMenuItems {
+  pub enum MainMenuItems(menu_name = "main_menu") {
     Start(item_name = "start", asset_name = "menu_start_v2"),
     Options(item_name = "options", asset_name = "menu_options"),
     Exit(item_name = "exit", asset_name = "menu_exit"),
@@ -0,0 +1,21 @@
+use crate::{
+  define_menu_items,
+  menus::{menu_data::Menu, menu_items::*},
+};
+
+pub struct PauseMenu;
+
+impl PauseMenu {
+  pub const MENU_NAME: &'static str = PauseMenuItems::MENU_NAME;
+
+  pub fn new_menu() -> Menu {
+    Menu::new::<PauseMenuItems>()
+  }
+}
+
+define_menu_items! {
+  pub enum PauseMenuItems(menu_name = "pause_menu") {
+    Resume(item_name = "resume", text = "Resume"),
+    Quit(item_name = "quit", text = "Quit to menu"),
+  }
+}
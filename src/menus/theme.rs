@@ -0,0 +1,23 @@
+/// The spacing and colors used when rendering a [`Menu`](crate::menus::menu_data::Menu).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+  /// The gap between each option in pixels.
+  pub option_spacing: u32,
+  /// The color of the label for the currently selected option.
+  pub highlight_color: [u8; 4],
+  /// The color of the labels for every option that isn't selected.
+  pub text_color: [u8; 4],
+  /// The size in pixels of the labels.
+  pub text_size: f32,
+}
+
+impl Default for Theme {
+  fn default() -> Self {
+    Self {
+      option_spacing: 10,
+      highlight_color: [255, 215, 0, 255],
+      text_color: [255, 255, 255, 255],
+      text_size: 20.0,
+    }
+  }
+}
@@ -0,0 +1,97 @@
+/// A color with red, green, blue, and alpha channels.
+///
+/// An alpha of 255 is fully opaque, and 0 is fully transparent.
+/// Converts to and from `[r, g, b, a]` arrays, so either can be passed to the [`Renderer`](crate::renderer::Renderer).
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Color {
+  pub r: u8,
+  pub g: u8,
+  pub b: u8,
+  pub a: u8,
+}
+
+impl Color {
+  pub const WHITE: Color = Color::rgb(255, 255, 255);
+  pub const BLACK: Color = Color::rgb(0, 0, 0);
+  pub const TRANSPARENT: Color = Color::rgba(0, 0, 0, 0);
+
+  /// Creates a fully opaque color.
+  pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+    Self::rgba(r, g, b, 255)
+  }
+
+  pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+    Self { r, g, b, a }
+  }
+
+  /// Returns the same color with the given alpha.
+  pub const fn with_alpha(self, a: u8) -> Self {
+    Self { a, ..self }
+  }
+
+  pub const fn to_array(self) -> [u8; 4] {
+    [self.r, self.g, self.b, self.a]
+  }
+}
+
+/// How a drawn color is combined with the color already in the buffer.
+///
+/// The alpha of the drawn color scales how strongly it's applied in every mode.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum BlendMode {
+  /// The drawn color is placed over the existing one.
+  #[default]
+  Normal,
+  /// The drawn color is added to the existing one, with each channel capped at 255. Useful for glows.
+  Additive,
+  /// The existing color is scaled by the drawn color, only ever darkening it. Useful for shadows.
+  Multiply,
+}
+
+impl From<[u8; 4]> for Color {
+  fn from([r, g, b, a]: [u8; 4]) -> Self {
+    Self::rgba(r, g, b, a)
+  }
+}
+
+/// Colors without an alpha channel are fully opaque.
+impl From<[u8; 3]> for Color {
+  fn from([r, g, b]: [u8; 3]) -> Self {
+    Self::rgb(r, g, b)
+  }
+}
+
+impl From<Color> for [u8; 4] {
+  fn from(color: Color) -> Self {
+    color.to_array()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn arrays_convert_both_ways() {
+    let color = Color::from([1, 2, 3, 4]);
+
+    assert_eq!(color, Color::rgba(1, 2, 3, 4));
+    assert_eq!(<[u8; 4]>::from(color), [1, 2, 3, 4]);
+    assert_eq!(Color::from([1, 2, 3]), Color::rgba(1, 2, 3, 255));
+  }
+
+  #[test]
+  fn named_colors_have_the_expected_channels() {
+    assert_eq!(Color::WHITE.to_array(), [255, 255, 255, 255]);
+    assert_eq!(Color::BLACK.to_array(), [0, 0, 0, 255]);
+    assert_eq!(Color::TRANSPARENT.a, 0);
+  }
+
+  #[test]
+  fn with_alpha_only_changes_the_alpha() {
+    assert_eq!(
+      Color::WHITE.with_alpha(0x80),
+      Color::rgba(255, 255, 255, 0x80)
+    );
+  }
+}
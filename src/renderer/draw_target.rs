@@ -0,0 +1,163 @@
+use super::{Renderer, Viewport};
+use pixels::Pixels;
+use winit::dpi::*;
+
+/// Something a [`Renderer`](super::Renderer) can draw to, such as the surface of a window or a buffer in memory.
+///
+/// The frame is an rgba8 buffer of the target's dimensions, with rows running top to bottom.
+/// Targets that are never shown only need to hand out their frame,
+/// which lets anything that draws be tested without a window or GPU.
+pub trait DrawTarget {
+  fn frame(&self) -> &[u8];
+
+  fn frame_mut(&mut self) -> &mut [u8];
+
+  /// Returns the width and height of the frame in pixels.
+  fn dimensions(&self) -> LogicalSize<u32>;
+
+  /// Shows the frame. Does nothing by default.
+  fn present(&self) -> anyhow::Result<()> {
+    Ok(())
+  }
+
+  /// Resizes whatever the frame is shown on. Does nothing by default.
+  fn resize_surface(&mut self, _new_dimensions: PhysicalSize<u32>) -> anyhow::Result<()> {
+    Ok(())
+  }
+
+  /// Returns where in a window of the given dimensions the frame is shown.
+  ///
+  /// By default, the frame is stretched over the window's [`letterbox rect`](Renderer::letterbox_rect).
+  fn viewport(&self, window_dimensions: PhysicalSize<u32>) -> Viewport {
+    Renderer::letterbox_rect(window_dimensions, self.dimensions())
+  }
+
+  /// Converts a position in the window to the pixel of the frame shown there, with None if no pixel is shown there.
+  ///
+  /// By default, the frame is assumed to be stretched over the viewport.
+  fn window_to_buffer_position(
+    &self,
+    window_position: PhysicalPosition<f32>,
+    viewport: &Viewport,
+  ) -> Option<LogicalPosition<u32>> {
+    let (viewport_position, viewport_dimensions) = viewport;
+    let dimensions = self.dimensions();
+    let x = window_position.x - viewport_position.x as f32;
+    let y = window_position.y - viewport_position.y as f32;
+
+    if x < 0.0
+      || y < 0.0
+      || x >= viewport_dimensions.width as f32
+      || y >= viewport_dimensions.height as f32
+    {
+      return None;
+    }
+
+    let scale = viewport_dimensions.width as f32 / dimensions.width as f32;
+
+    Some(LogicalPosition::new(
+      ((x / scale) as u32).min(dimensions.width - 1),
+      ((y / scale) as u32).min(dimensions.height - 1),
+    ))
+  }
+}
+
+/// A plain buffer that is never presented, used when there is no window to draw to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InMemoryTarget {
+  buffer: Vec<u8>,
+  dimensions: LogicalSize<u32>,
+}
+
+impl InMemoryTarget {
+  /// Creates a buffer of the given dimensions, filled with opaque black.
+  pub fn new(dimensions: LogicalSize<u32>) -> Self {
+    let pixel_count = (dimensions.width * dimensions.height) as usize;
+
+    Self {
+      buffer: [0, 0, 0, 255].repeat(pixel_count),
+      dimensions,
+    }
+  }
+}
+
+impl DrawTarget for InMemoryTarget {
+  fn frame(&self) -> &[u8] {
+    &self.buffer
+  }
+
+  fn frame_mut(&mut self) -> &mut [u8] {
+    &mut self.buffer
+  }
+
+  fn dimensions(&self) -> LogicalSize<u32> {
+    self.dimensions
+  }
+}
+
+/// The surface of a window, presented when a render is completed.
+pub struct PixelsTarget {
+  pixels: Pixels,
+  dimensions: LogicalSize<u32>,
+}
+
+impl PixelsTarget {
+  /// Wraps the pixels, whose buffer has the given dimensions.
+  ///
+  /// Any part of the window the buffer isn't shown in is cleared to black.
+  pub fn new(mut pixels: Pixels, dimensions: LogicalSize<u32>) -> Self {
+    pixels.clear_color(pixels::wgpu::Color::BLACK);
+
+    Self { pixels, dimensions }
+  }
+}
+
+impl DrawTarget for PixelsTarget {
+  fn frame(&self) -> &[u8] {
+    self.pixels.frame()
+  }
+
+  fn frame_mut(&mut self) -> &mut [u8] {
+    self.pixels.frame_mut()
+  }
+
+  fn dimensions(&self) -> LogicalSize<u32> {
+    self.dimensions
+  }
+
+  fn present(&self) -> anyhow::Result<()> {
+    self.pixels.render().map_err(Into::into)
+  }
+
+  fn resize_surface(&mut self, new_dimensions: PhysicalSize<u32>) -> anyhow::Result<()> {
+    self
+      .pixels
+      .resize_surface(new_dimensions.width.max(1), new_dimensions.height.max(1))
+      .map_err(Into::into)
+  }
+
+  /// pixels scales the buffer by whole numbers, so the frame is shown in its clip rect rather than the letterbox rect.
+  ///
+  /// The surface has to have been resized to the window for the clip rect to be up to date.
+  fn viewport(&self, _window_dimensions: PhysicalSize<u32>) -> Viewport {
+    let (x, y, width, height) = self.pixels.context().scaling_renderer.clip_rect();
+
+    (
+      PhysicalPosition::new(x, y),
+      PhysicalSize::new(width, height),
+    )
+  }
+
+  /// pixels knows exactly where in the window it drew the buffer, so the viewport is ignored.
+  fn window_to_buffer_position(
+    &self,
+    window_position: PhysicalPosition<f32>,
+    _viewport: &Viewport,
+  ) -> Option<LogicalPosition<u32>> {
+    self
+      .pixels
+      .window_pos_to_pixel(window_position.into())
+      .ok()
+      .map(|(x, y)| LogicalPosition::new(x as u32, y as u32))
+  }
+}
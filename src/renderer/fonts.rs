@@ -1,10 +1,89 @@
 use crate::renderer::Renderer;
 use anyhow::anyhow;
-use fontdue::layout::{CoordinateSystem, GlyphPosition, Layout, LayoutSettings, TextStyle};
+use fontdue::layout::{
+  CoordinateSystem, GlyphPosition, HorizontalAlign, Layout, LayoutSettings, TextStyle, WrapStyle,
+};
+use fontdue::{Font, Metrics};
+use std::collections::HashMap;
 use winit::dpi::*;
 
+/// Stores every glyph rasterized for drawing text, so each one is only rasterized once.
+///
+/// Glyphs are keyed by their font index, character, and size in pixels.
+#[derive(Debug, Default)]
+pub struct GlyphCache {
+  glyphs: HashMap<(usize, char, u32), (Metrics, Vec<u8>)>,
+  /// How many glyphs were served from the cache instead of being rasterized.
+  hits: usize,
+}
+
+impl GlyphCache {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Returns the metrics and bitmap of the character at the given size, rasterizing it if it hasn't been yet.
+  ///
+  /// The font index must be the index of the given font in the renderer.
+  pub fn rasterize(
+    &mut self,
+    font: &Font,
+    font_index: usize,
+    character: char,
+    px: f32,
+  ) -> &(Metrics, Vec<u8>) {
+    let key = (font_index, character, px.to_bits());
+
+    if self.glyphs.contains_key(&key) {
+      self.hits += 1;
+    }
+
+    self
+      .glyphs
+      .entry(key)
+      .or_insert_with(|| font.rasterize(character, px))
+  }
+
+  /// Returns how many glyphs were served from the cache instead of being rasterized.
+  pub fn hits(&self) -> usize {
+    self.hits
+  }
+
+  /// Returns how many glyphs are stored in the cache.
+  pub fn len(&self) -> usize {
+    self.glyphs.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.glyphs.is_empty()
+  }
+}
+
+/// Where each line of a [`TextBox`](TextBox) is placed horizontally within its width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alignment {
+  #[default]
+  Left,
+  Center,
+  Right,
+}
+
+impl From<Alignment> for HorizontalAlign {
+  fn from(alignment: Alignment) -> Self {
+    match alignment {
+      Alignment::Left => HorizontalAlign::Left,
+      Alignment::Center => HorizontalAlign::Center,
+      Alignment::Right => HorizontalAlign::Right,
+    }
+  }
+}
+
 pub struct TextBox {
   layout: Layout,
+  /// Whether a line is drawn under each line of text.
+  underline: bool,
+  /// Whether a line is drawn through the middle of each line of text.
+  strikethrough: bool,
 }
 
 impl std::fmt::Debug for TextBox {
@@ -14,6 +93,13 @@ impl std::fmt::Debug for TextBox {
 }
 
 impl TextBox {
+  /// The smallest font size [`new_fit()`](TextBox::new_fit) will shrink text down to.
+  pub const MIN_FIT_SIZE: f32 = 4.0;
+  /// How many times [`new_fit()`](TextBox::new_fit) halves the range of sizes it searches.
+  const FIT_SEARCH_STEPS: u32 = 10;
+  /// How far up from the baseline a strikethrough is drawn, relative to the line's ascent.
+  const STRIKETHROUGH_HEIGHT: f32 = 0.35;
+
   pub fn new(
     renderer: &Renderer,
     font_index: usize,
@@ -21,23 +107,256 @@ impl TextBox {
     position: &LogicalPosition<u32>,
     size: f32,
   ) -> Self {
-    let style = TextStyle::new(text, size, font_index);
+    let layout_settings = LayoutSettings {
+      x: position.x as f32,
+      y: position.y as f32,
+      ..Default::default()
+    };
 
-    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    Self::with_layout_settings(renderer, font_index, text, size, &layout_settings)
+  }
+
+  /// Creates a text box that wraps its text onto new lines at word boundaries
+  /// to keep each line within the given width in pixels.
+  ///
+  /// Words wider than the max width are broken onto multiple lines.
+  pub fn new_wrapped(
+    renderer: &Renderer,
+    font_index: usize,
+    text: &str,
+    position: &LogicalPosition<u32>,
+    size: f32,
+    max_width: u32,
+  ) -> Self {
+    let layout_settings = LayoutSettings {
+      x: position.x as f32,
+      y: position.y as f32,
+      max_width: Some(max_width as f32),
+      wrap_style: WrapStyle::Word,
+      ..Default::default()
+    };
+
+    Self::with_layout_settings(renderer, font_index, text, size, &layout_settings)
+  }
+
+  /// Creates a text box with each line aligned within the given width in pixels, starting from the given position.
+  ///
+  /// Text wider than the width is wrapped like in [`new_wrapped()`](TextBox::new_wrapped).
+  pub fn new_aligned(
+    renderer: &Renderer,
+    font_index: usize,
+    text: &str,
+    position: &LogicalPosition<u32>,
+    size: f32,
+    alignment: Alignment,
+    width: u32,
+  ) -> Self {
     let layout_settings = LayoutSettings {
       x: position.x as f32,
       y: position.y as f32,
+      max_width: Some(width as f32),
+      wrap_style: WrapStyle::Word,
+      horizontal_align: alignment.into(),
       ..Default::default()
     };
-    layout.reset(&layout_settings);
+
+    Self::with_layout_settings(renderer, font_index, text, size, &layout_settings)
+  }
+
+  /// Creates a text box using the largest font size up to `max_size` that keeps the text within the given bounds.
+  ///
+  /// Text that doesn't fit even at [`MIN_FIT_SIZE`](TextBox::MIN_FIT_SIZE) is drawn at that size regardless.
+  pub fn new_fit(
+    renderer: &Renderer,
+    font_index: usize,
+    text: &str,
+    position: &LogicalPosition<u32>,
+    max_size: f32,
+    bounds: LogicalSize<u32>,
+  ) -> Self {
+    let size = Self::fit_size(renderer, font_index, text, max_size, bounds);
+
+    Self::new(renderer, font_index, text, position, size)
+  }
+
+  /// Binary searches for the largest size between [`MIN_FIT_SIZE`](TextBox::MIN_FIT_SIZE) and `max_size` that fits within the bounds.
+  fn fit_size(
+    renderer: &Renderer,
+    font_index: usize,
+    text: &str,
+    max_size: f32,
+    bounds: LogicalSize<u32>,
+  ) -> f32 {
+    let fits = |size: f32| {
+      Self::measure(renderer, font_index, text, size).is_ok_and(|dimensions| {
+        dimensions.width <= bounds.width && dimensions.height <= bounds.height
+      })
+    };
+
+    if max_size <= Self::MIN_FIT_SIZE || fits(max_size) {
+      return max_size;
+    }
+
+    let (mut fitting, mut too_large) = (Self::MIN_FIT_SIZE, max_size);
+
+    for _ in 0..Self::FIT_SEARCH_STEPS {
+      let size = (fitting + too_large) / 2.0;
+
+      if fits(size) {
+        fitting = size;
+      } else {
+        too_large = size;
+      }
+    }
+
+    fitting
+  }
+
+  /// Creates a text box for each of the given texts, stacked top to bottom starting at the given position.
+  ///
+  /// Each text box starts the given gap in pixels below the lowest pixel of the one before it.
+  pub fn new_set_from_list(
+    renderer: &Renderer,
+    font_index: usize,
+    texts: &[&str],
+    position: &LogicalPosition<u32>,
+    size: f32,
+    text_gap: u32,
+  ) -> Vec<Self> {
+    let mut offset = *position;
+
+    texts
+      .iter()
+      .map(|text| {
+        let text_box = Self::new(renderer, font_index, text, &offset, size);
+
+        let lowest_pixel = text_box
+          .character_data()
+          .iter()
+          .map(|glyph| (glyph.y + glyph.height as f32).ceil() as u32)
+          .max()
+          .unwrap_or(offset.y);
+        offset.y = lowest_pixel + text_gap;
+
+        text_box
+      })
+      .collect()
+  }
+
+  fn with_layout_settings(
+    renderer: &Renderer,
+    font_index: usize,
+    text: &str,
+    size: f32,
+    layout_settings: &LayoutSettings,
+  ) -> Self {
+    let style = TextStyle::new(text, size, font_index);
+
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    layout.reset(layout_settings);
 
     layout.append(renderer.fonts(), &style);
 
-    Self { layout }
+    Self {
+      layout,
+      underline: false,
+      strikethrough: false,
+    }
+  }
+
+  /// Returns the width and height in pixels the text would take up in a text box, without creating one.
+  ///
+  /// Returns an error if there's no font loaded at the given index.
+  pub fn measure(
+    renderer: &Renderer,
+    font_index: usize,
+    text: &str,
+    size: f32,
+  ) -> anyhow::Result<LogicalSize<u32>> {
+    let fonts = renderer.fonts();
+
+    if fonts.len() < font_index + 1 {
+      return Err(anyhow!(
+        "Attempted to measure text with an invalid font index."
+      ));
+    }
+
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    layout.reset(&LayoutSettings::default());
+    layout.append(fonts, &TextStyle::new(text, size, font_index));
+
+    Ok(Self::calculate_dimensions(&layout))
+  }
+
+  /// Sets whether a line is drawn under each line of text, just below its baseline.
+  pub fn with_underline(mut self, underline: bool) -> Self {
+    self.underline = underline;
+
+    self
+  }
+
+  /// Sets whether a line is drawn through the middle of each line of text.
+  pub fn with_strikethrough(mut self, strikethrough: bool) -> Self {
+    self.strikethrough = strikethrough;
+
+    self
+  }
+
+  /// Returns the start and end of every underline and strikethrough drawn with this textbox instance.
+  ///
+  /// Each line of text gets its own decorations, spanning from its leftmost drawn pixel to its rightmost,
+  /// so they follow the text's alignment and wrapping.
+  pub fn decoration_lines(&self) -> Vec<(LogicalPosition<i32>, LogicalPosition<i32>)> {
+    if !self.underline && !self.strikethrough {
+      return Vec::new();
+    }
+
+    let Some(lines) = self.layout.lines() else {
+      return Vec::new();
+    };
+    let glyphs = self.layout.glyphs();
+
+    lines
+      .iter()
+      .filter_map(|line| {
+        let drawn_glyphs = glyphs
+          .get(line.glyph_start..=line.glyph_end)?
+          .iter()
+          .filter(|glyph| glyph.width > 0 && !glyph.parent.is_whitespace());
+
+        let (left, right) = drawn_glyphs.fold((i32::MAX, i32::MIN), |(left, right), glyph| {
+          (
+            left.min(glyph.x as i32),
+            right.max(glyph.x as i32 + glyph.width as i32 - 1),
+          )
+        });
+
+        (left <= right).then_some((line, left, right))
+      })
+      .flat_map(|(line, left, right)| {
+        let underline_y = line.baseline_y.round() as i32 + 1;
+        let strikethrough_y =
+          (line.baseline_y - (line.max_ascent * Self::STRIKETHROUGH_HEIGHT)).round() as i32;
+
+        [
+          (self.underline, underline_y),
+          (self.strikethrough, strikethrough_y),
+        ]
+        .into_iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(move |(_, y)| {
+          (
+            LogicalPosition::new(left, y),
+            LogicalPosition::new(right, y),
+          )
+        })
+      })
+      .collect()
   }
 
   /// Updates the text contained in this textbox.
   ///
+  /// A wrapped or aligned text box keeps wrapping and aligning to the same width.
   /// If the textbox was originally an empty string, the font_index is set to 0.
   pub fn update_text(
     &mut self,
@@ -48,7 +367,7 @@ impl TextBox {
   ) {
     let mut layout_settings = *self.layout.settings();
 
-    if layout_settings.x != position.x as f32 && layout_settings.y != position.y as f32 {
+    if layout_settings.x != position.x as f32 || layout_settings.y != position.y as f32 {
       layout_settings.x = position.x as f32;
       layout_settings.y = position.y as f32;
     }
@@ -106,6 +425,87 @@ impl TextBox {
     })
   }
 
+  /// Returns the width and height in pixels of the text within this textbox instance.
+  ///
+  /// For wrapped text the width is that of the widest line, and the height spans every line.
+  pub fn dimensions(&self) -> LogicalSize<u32> {
+    Self::calculate_dimensions(&self.layout)
+  }
+
+  fn calculate_dimensions(layout: &Layout) -> LogicalSize<u32> {
+    let glyphs = layout.glyphs();
+
+    if glyphs.is_empty() {
+      return LogicalSize::new(0, 0);
+    }
+
+    let left = glyphs.iter().map(|glyph| glyph.x).fold(f32::MAX, f32::min);
+    let right = glyphs
+      .iter()
+      .map(|glyph| glyph.x + glyph.width as f32)
+      .fold(0.0, f32::max);
+
+    LogicalSize::new((right - left).ceil() as u32, layout.height().ceil() as u32)
+  }
+
+  /// Returns the top left position and dimensions of the pixels this textbox instance draws to.
+  ///
+  /// Unlike [`position()`](TextBox::position) and [`dimensions()`](TextBox::dimensions), this covers every glyph
+  /// from the highest pixel to the lowest, so it matches what's actually rendered.
+  /// An empty text box has no size and sits at the position it was created at.
+  pub fn bounds(&self) -> (LogicalPosition<u32>, LogicalSize<u32>) {
+    let drawn_glyphs = || {
+      self
+        .layout
+        .glyphs()
+        .iter()
+        .filter(|glyph| glyph.width > 0 && glyph.height > 0)
+    };
+
+    let Some(left) = drawn_glyphs().map(|glyph| glyph.x as u32).min() else {
+      let settings = self.layout.settings();
+
+      return (
+        LogicalPosition::new(settings.x as u32, settings.y as u32),
+        LogicalSize::new(0, 0),
+      );
+    };
+    let top = drawn_glyphs()
+      .map(|glyph| glyph.y as u32)
+      .min()
+      .unwrap_or(0);
+    let right = drawn_glyphs()
+      .map(|glyph| glyph.x as u32 + glyph.width as u32)
+      .max()
+      .unwrap_or(left);
+    let bottom = drawn_glyphs()
+      .map(|glyph| glyph.y as u32 + glyph.height as u32)
+      .max()
+      .unwrap_or(top);
+
+    (
+      LogicalPosition::new(left, top),
+      LogicalSize::new(right - left, bottom - top),
+    )
+  }
+
+  /// Returns true if the point is within the [`bounds()`](TextBox::bounds) of this textbox instance.
+  pub fn contains(&self, point: LogicalPosition<u32>) -> bool {
+    let (position, dimensions) = self.bounds();
+
+    (position.x..position.x + dimensions.width).contains(&point.x)
+      && (position.y..position.y + dimensions.height).contains(&point.y)
+  }
+
+  /// Returns how each line of this textbox instance is aligned.
+  pub fn alignment(&self) -> Alignment {
+    match self.layout.settings().horizontal_align {
+      HorizontalAlign::Left => Alignment::Left,
+      HorizontalAlign::Center => Alignment::Center,
+      HorizontalAlign::Right => Alignment::Right,
+    }
+  }
+
   /// Returns the text contained in this textbox instance.
   pub fn text(&self) -> String {
     self
@@ -128,3 +528,503 @@ impl TextBox {
     self.layout.glyphs()
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::asset_loader::Assets;
+
+  fn renderer_with_font() -> Renderer {
+    let assets = Assets::load_assets();
+    let mut renderer = Renderer::new_in_memory(LogicalSize::new(100, 100));
+    renderer
+      .load_font_from_bytes(assets.get_font("gadugi").unwrap(), "gadugi")
+      .unwrap();
+
+    renderer
+  }
+
+  mod update_text_logic {
+    use super::*;
+
+    #[test]
+    fn changing_only_x_moves_the_text() {
+      let renderer = renderer_with_font();
+      let mut text_box = TextBox::new(&renderer, 0, "AB", &LogicalPosition::new(0, 10), 12.0);
+      let original_glyphs = text_box.character_data().clone();
+
+      text_box.update_text(&renderer, "AB", 12.0, &LogicalPosition::new(20, 10));
+
+      for (original, moved) in original_glyphs.iter().zip(text_box.character_data()) {
+        assert_eq!(moved.x, original.x + 20.0);
+        assert_eq!(moved.y, original.y);
+      }
+    }
+
+    #[test]
+    fn changing_only_y_moves_the_text() {
+      let renderer = renderer_with_font();
+      let mut text_box = TextBox::new(&renderer, 0, "AB", &LogicalPosition::new(0, 10), 12.0);
+      let original_glyphs = text_box.character_data().clone();
+
+      text_box.update_text(&renderer, "AB", 12.0, &LogicalPosition::new(0, 30));
+
+      for (original, moved) in original_glyphs.iter().zip(text_box.character_data()) {
+        assert_eq!(moved.x, original.x);
+        assert_eq!(moved.y, original.y + 20.0);
+      }
+    }
+  }
+
+  mod text_set_logic {
+    use super::*;
+
+    #[test]
+    fn listed_text_boxes_are_stacked_without_overlapping() {
+      let renderer = renderer_with_font();
+      let text_gap = 3;
+
+      let text_boxes = TextBox::new_set_from_list(
+        &renderer,
+        0,
+        &["Move left", "Soft drop", "Hold"],
+        &LogicalPosition::new(0, 0),
+        12.0,
+        text_gap,
+      );
+
+      assert_eq!(text_boxes.len(), 3);
+
+      for pair in text_boxes.windows(2) {
+        let previous_bottom = pair[0]
+          .character_data()
+          .iter()
+          .map(|glyph| glyph.y + glyph.height as f32)
+          .fold(0.0, f32::max);
+        let next_top = pair[1]
+          .character_data()
+          .iter()
+          .map(|glyph| glyph.y)
+          .fold(f32::MAX, f32::min);
+
+        assert!(next_top >= previous_bottom + text_gap as f32);
+      }
+    }
+  }
+
+  mod unicode_logic {
+    use super::*;
+
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+
+    fn render_text(text: &str) -> (Renderer, anyhow::Result<()>) {
+      let mut renderer = renderer_with_font();
+      let text_box = TextBox::new(&renderer, 0, text, &LogicalPosition::new(2, 2), 16.0);
+      let buffer_dimensions = renderer.buffer_dimensions();
+
+      let result = renderer.render_text_box(&text_box, WHITE, &buffer_dimensions);
+
+      (renderer, result)
+    }
+
+    fn drawn_pixel_count(renderer: &Renderer) -> usize {
+      renderer
+        .frame()
+        .chunks_exact(4)
+        .filter(|pixel| pixel[0] != 0)
+        .count()
+    }
+
+    #[test]
+    fn accented_characters_are_rendered() {
+      let (renderer, result) = render_text("é");
+
+      assert!(result.is_ok());
+      assert!(renderer.fonts()[0].lookup_glyph_index('é') != 0);
+      assert!(drawn_pixel_count(&renderer) > 0);
+    }
+
+    #[test]
+    fn missing_glyphs_are_drawn_as_a_box() {
+      let (renderer, result) = render_text("\u{E000}");
+
+      assert!(result.is_ok());
+      assert_eq!(renderer.fonts()[0].lookup_glyph_index('\u{E000}'), 0);
+      // The outline of an 8x12 box.
+      assert_eq!(drawn_pixel_count(&renderer), 36);
+    }
+  }
+
+  mod glyph_cache_logic {
+    use super::*;
+
+    #[test]
+    fn repeated_glyphs_are_served_from_the_cache() {
+      let renderer = renderer_with_font();
+      let font = &renderer.fonts()[0];
+      let mut glyph_cache = GlyphCache::new();
+
+      let first = glyph_cache.rasterize(font, 0, 'A', 12.0).clone();
+      let second = glyph_cache.rasterize(font, 0, 'A', 12.0).clone();
+
+      assert_eq!(glyph_cache.hits(), 1);
+      assert_eq!(glyph_cache.len(), 1);
+      assert_eq!(first.1, second.1);
+    }
+
+    #[test]
+    fn different_sizes_are_cached_separately() {
+      let renderer = renderer_with_font();
+      let font = &renderer.fonts()[0];
+      let mut glyph_cache = GlyphCache::new();
+
+      glyph_cache.rasterize(font, 0, 'A', 12.0);
+      glyph_cache.rasterize(font, 0, 'A', 16.0);
+
+      assert_eq!(glyph_cache.hits(), 0);
+      assert_eq!(glyph_cache.len(), 2);
+    }
+
+    #[test]
+    fn rendering_text_fills_the_renderers_cache() {
+      let mut renderer = renderer_with_font();
+      let text_box = TextBox::new(&renderer, 0, "AA", &LogicalPosition::new(0, 0), 12.0);
+      let buffer_dimensions = renderer.buffer_dimensions();
+
+      renderer
+        .render_text_box(&text_box, [255, 255, 255, 255], &buffer_dimensions)
+        .unwrap();
+
+      assert_eq!(renderer.glyph_cache().len(), 1);
+      assert_eq!(renderer.glyph_cache().hits(), 1);
+    }
+  }
+
+  mod alignment_logic {
+    use super::*;
+
+    const POSITION: LogicalPosition<u32> = LogicalPosition::new(10, 0);
+    const WIDTH: u32 = 80;
+
+    fn first_glyph_x(text_box: &TextBox) -> f32 {
+      text_box.character_data().first().unwrap().x
+    }
+
+    #[test]
+    fn centered_text_is_offset_by_half_the_free_width() {
+      let renderer = renderer_with_font();
+
+      let left = TextBox::new(&renderer, 0, "AB", &POSITION, 12.0);
+      let centered = TextBox::new_aligned(
+        &renderer,
+        0,
+        "AB",
+        &POSITION,
+        12.0,
+        Alignment::Center,
+        WIDTH,
+      );
+
+      let text_width = left.dimensions().width as f32;
+      let expected_x = first_glyph_x(&left) + ((WIDTH as f32 - text_width) / 2.0);
+
+      assert_eq!(centered.alignment(), Alignment::Center);
+      assert!(
+        (first_glyph_x(&centered) - expected_x).abs() <= 2.0,
+        "Expected the first glyph near {}, found {}",
+        expected_x,
+        first_glyph_x(&centered)
+      );
+    }
+
+    #[test]
+    fn right_aligned_text_ends_at_the_width() {
+      let renderer = renderer_with_font();
+
+      let right =
+        TextBox::new_aligned(&renderer, 0, "AB", &POSITION, 12.0, Alignment::Right, WIDTH);
+
+      let last_glyph = right.character_data().last().unwrap();
+      let text_end = last_glyph.x + last_glyph.width as f32;
+
+      assert!(((POSITION.x + WIDTH) as f32 - text_end).abs() <= 2.0);
+    }
+
+    #[test]
+    fn alignment_is_kept_when_updating_text() {
+      let renderer = renderer_with_font();
+      let mut text_box = TextBox::new_aligned(
+        &renderer,
+        0,
+        "AB",
+        &POSITION,
+        12.0,
+        Alignment::Center,
+        WIDTH,
+      );
+      let first_x = first_glyph_x(&text_box);
+
+      text_box.update_text(&renderer, "ABAB", 12.0, &POSITION);
+
+      assert_eq!(text_box.alignment(), Alignment::Center);
+      assert!(first_glyph_x(&text_box) < first_x);
+    }
+  }
+
+  mod wrap_logic {
+    use super::*;
+
+    const TEXT: &str = "Rotate the piece clockwise";
+    const POSITION: LogicalPosition<u32> = LogicalPosition::new(5, 5);
+    const MAX_WIDTH: u32 = 60;
+
+    #[test]
+    fn long_text_wraps_onto_multiple_lines() {
+      let renderer = renderer_with_font();
+
+      let single_line = TextBox::new(&renderer, 0, TEXT, &POSITION, 12.0);
+      let wrapped = TextBox::new_wrapped(&renderer, 0, TEXT, &POSITION, 12.0, MAX_WIDTH);
+
+      assert!(single_line.dimensions().width > MAX_WIDTH);
+      assert!(wrapped.dimensions().width <= MAX_WIDTH);
+      assert!(wrapped.dimensions().height >= single_line.dimensions().height * 2);
+      assert_eq!(wrapped.text(), single_line.text());
+    }
+
+    #[test]
+    fn updated_text_keeps_wrapping() {
+      let renderer = renderer_with_font();
+      let mut text_box = TextBox::new_wrapped(&renderer, 0, "Hold", &POSITION, 12.0, MAX_WIDTH);
+      let single_line_height = text_box.dimensions().height;
+
+      text_box.update_text(&renderer, TEXT, 12.0, &POSITION);
+
+      assert!(text_box.dimensions().height >= single_line_height * 2);
+    }
+  }
+
+  mod measure_logic {
+    use super::*;
+
+    #[test]
+    fn measured_text_matches_the_text_box() {
+      let renderer = renderer_with_font();
+
+      for (text, size) in [("Rustris", 12.0), ("Hold", 20.0), ("", 12.0)] {
+        let text_box = TextBox::new(&renderer, 0, text, &LogicalPosition::new(7, 3), size);
+
+        assert_eq!(
+          TextBox::measure(&renderer, 0, text, size).unwrap(),
+          text_box.dimensions()
+        );
+      }
+    }
+
+    #[test]
+    fn invalid_font_index_is_an_error() {
+      let renderer = renderer_with_font();
+
+      assert!(TextBox::measure(&renderer, 1, "Rustris", 12.0).is_err());
+    }
+  }
+
+  mod fit_logic {
+    use super::*;
+
+    const TEXT: &str = "Rotate the piece clockwise";
+    const POSITION: LogicalPosition<u32> = LogicalPosition::new(0, 0);
+
+    #[test]
+    fn long_text_is_shrunk_to_fit() {
+      let renderer = renderer_with_font();
+      let bounds = LogicalSize::new(60, 20);
+
+      let text_box = TextBox::new_fit(&renderer, 0, TEXT, &POSITION, 20.0, bounds);
+      let dimensions = text_box.dimensions();
+
+      assert!(text_box.text_size().unwrap() <= 20.0);
+      assert!(dimensions.width <= bounds.width);
+      assert!(dimensions.height <= bounds.height);
+    }
+
+    #[test]
+    fn fitting_text_keeps_the_max_size() {
+      let renderer = renderer_with_font();
+
+      let text_box = TextBox::new_fit(
+        &renderer,
+        0,
+        "Hold",
+        &POSITION,
+        12.0,
+        LogicalSize::new(100, 100),
+      );
+
+      assert_eq!(text_box.text_size(), Some(12.0));
+    }
+
+    #[test]
+    fn impossible_bounds_stop_at_the_min_size() {
+      let renderer = renderer_with_font();
+
+      let text_box = TextBox::new_fit(&renderer, 0, TEXT, &POSITION, 20.0, LogicalSize::new(1, 1));
+
+      assert_eq!(text_box.text_size(), Some(TextBox::MIN_FIT_SIZE));
+    }
+  }
+
+  mod bounds_logic {
+    use super::*;
+
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+    const POSITION: LogicalPosition<u32> = LogicalPosition::new(20, 30);
+
+    fn drawn_pixels(renderer: &Renderer) -> Vec<LogicalPosition<u32>> {
+      let width = renderer.buffer_dimensions().width;
+
+      renderer
+        .frame()
+        .chunks_exact(4)
+        .enumerate()
+        .filter(|(_, pixel)| pixel[..3] != [0, 0, 0])
+        .map(|(index, _)| LogicalPosition::new(index as u32 % width, index as u32 / width))
+        .collect()
+    }
+
+    #[test]
+    fn every_rendered_pixel_is_contained() {
+      let mut renderer = renderer_with_font();
+      let text_box = TextBox::new(&renderer, 0, "Hold", &POSITION, 16.0);
+
+      renderer
+        .render_text_box(&text_box, WHITE, &renderer.buffer_dimensions())
+        .unwrap();
+      let pixels = drawn_pixels(&renderer);
+
+      assert!(!pixels.is_empty());
+      assert!(pixels.iter().all(|pixel| text_box.contains(*pixel)));
+    }
+
+    #[test]
+    fn points_just_outside_are_not_contained() {
+      let renderer = renderer_with_font();
+      let text_box = TextBox::new(&renderer, 0, "Hold", &POSITION, 16.0);
+      let (position, dimensions) = text_box.bounds();
+      let middle_y = position.y + (dimensions.height / 2);
+
+      assert!(text_box.contains(LogicalPosition::new(position.x, middle_y)));
+      assert!(!text_box.contains(LogicalPosition::new(position.x - 1, middle_y)));
+      assert!(!text_box.contains(LogicalPosition::new(
+        position.x + dimensions.width,
+        middle_y
+      )));
+      assert!(!text_box.contains(LogicalPosition::new(
+        position.x,
+        position.y + dimensions.height
+      )));
+    }
+
+    #[test]
+    fn empty_text_box_contains_nothing() {
+      let renderer = renderer_with_font();
+      let text_box = TextBox::new(&renderer, 0, "", &POSITION, 16.0);
+
+      assert_eq!(text_box.bounds(), (POSITION, LogicalSize::new(0, 0)));
+      assert!(!text_box.contains(POSITION));
+    }
+  }
+
+  mod decoration_logic {
+    use super::*;
+
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+    const POSITION: LogicalPosition<u32> = LogicalPosition::new(10, 10);
+
+    fn is_drawn(renderer: &Renderer, x: i32, y: i32) -> bool {
+      let index = ((x as u32 + (y as u32 * renderer.buffer_dimensions().width)) * 4) as usize;
+
+      renderer.frame()[index..index + 3] != [0, 0, 0]
+    }
+
+    #[test]
+    fn underline_is_drawn_along_the_bottom_of_the_text() {
+      let mut renderer = renderer_with_font();
+      let text_box = TextBox::new(&renderer, 0, "Hold", &POSITION, 16.0).with_underline(true);
+      let (text_position, text_dimensions) = text_box.bounds();
+
+      renderer
+        .render_text_box(&text_box, WHITE, &renderer.buffer_dimensions())
+        .unwrap();
+
+      let decoration_lines = text_box.decoration_lines();
+      assert_eq!(decoration_lines.len(), 1);
+
+      let (start, end) = decoration_lines[0];
+      assert_eq!(start.x, text_position.x as i32);
+      assert_eq!(end.x, (text_position.x + text_dimensions.width) as i32 - 1);
+      assert!(start.y >= (text_position.y + text_dimensions.height / 2) as i32);
+      assert!((start.x..=end.x).all(|x| is_drawn(&renderer, x, start.y)));
+    }
+
+    #[test]
+    fn undecorated_text_has_no_lines() {
+      let renderer = renderer_with_font();
+      let text_box = TextBox::new(&renderer, 0, "Hold", &POSITION, 16.0);
+
+      assert!(text_box.decoration_lines().is_empty());
+    }
+
+    #[test]
+    fn strikethrough_is_above_the_underline() {
+      let renderer = renderer_with_font();
+      let text_box = TextBox::new(&renderer, 0, "Hold", &POSITION, 16.0)
+        .with_underline(true)
+        .with_strikethrough(true);
+
+      let [(underline, _), (strikethrough, _)] = text_box.decoration_lines()[..] else {
+        panic!("Expected an underline and a strikethrough.");
+      };
+
+      assert!(strikethrough.y < underline.y);
+    }
+
+    #[test]
+    fn every_wrapped_line_is_underlined_where_its_aligned() {
+      let renderer = renderer_with_font();
+      let underlined_lines = |alignment: Alignment| {
+        TextBox::new_aligned(
+          &renderer,
+          0,
+          "Rotate the piece clockwise",
+          &POSITION,
+          12.0,
+          alignment,
+          60,
+        )
+        .with_underline(true)
+        .decoration_lines()
+      };
+
+      let left_lines = underlined_lines(Alignment::Left);
+      let right_lines = underlined_lines(Alignment::Right);
+
+      assert!(right_lines.len() >= 2);
+      assert_eq!(left_lines.len(), right_lines.len());
+      assert!(right_lines
+        .windows(2)
+        .all(|lines| lines[0].0.y < lines[1].0.y));
+
+      for ((left_start, left_end), (right_start, right_end)) in left_lines.iter().zip(&right_lines)
+      {
+        let shift = right_start.x - left_start.x;
+
+        assert!(shift >= 0);
+        assert_eq!(right_end.x - left_end.x, shift);
+      }
+      assert!(left_lines
+        .iter()
+        .zip(&right_lines)
+        .any(|(left_line, right_line)| right_line.0.x > left_line.0.x));
+    }
+  }
+}
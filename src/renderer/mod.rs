@@ -1,47 +1,204 @@
 #![forbid(unsafe_code)]
 
 use anyhow::anyhow;
+use fontdue::layout::GlyphPosition;
 use fontdue::Font;
 use image::DynamicImage;
 use pixels::Pixels;
+use std::borrow::Cow;
+use std::path::Path;
 use winit::dpi::*;
 
-use self::fonts::TextBox;
+use self::color::{BlendMode, Color};
+use self::draw_target::{DrawTarget, InMemoryTarget, PixelsTarget};
+use self::fonts::{GlyphCache, TextBox};
 
+pub mod color;
+pub mod draw_target;
 pub mod fonts;
 
+/// A region of the buffer that drawing is limited to, as its top left position and dimensions.
+pub type ClipRegion = (LogicalPosition<u32>, LogicalSize<u32>);
+/// The area of the window the buffer is shown in, as its top left position and dimensions.
+pub type Viewport = (PhysicalPosition<u32>, PhysicalSize<u32>);
+
 pub struct Renderer {
-  pixels: Pixels,
+  frame_buffer: Box<dyn DrawTarget>,
+  buffer_dimensions: LogicalSize<u32>,
+  /// When set, pixels outside of this region are skipped when drawing.
+  clip: Option<ClipRegion>,
+  /// How shapes are combined with what's already in the buffer.
+  blend_mode: BlendMode,
+  /// Where the buffer is shown in the window, with black bars filling the rest.
+  viewport: Viewport,
 
   loaded_fonts: Vec<Font>,
   font_layout_by_name: Vec<&'static str>,
+  glyph_cache: GlyphCache,
+}
+
+/// The directions a drawn shape, such as an [`arrow`](Renderer::draw_arrow), can point in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+  Up,
+  Down,
+  Left,
+  Right,
+}
+
+impl Direction {
+  /// Returns the x and y offset of a single pixel in this direction, with y pointing down.
+  fn offset(&self) -> (i32, i32) {
+    match self {
+      Direction::Up => (0, -1),
+      Direction::Down => (0, 1),
+      Direction::Left => (-1, 0),
+      Direction::Right => (1, 0),
+    }
+  }
 }
 
 impl Renderer {
-  pub fn new(pixels: Pixels) -> Self {
+  /// The level at which the background gradient stops intensifying.
+  pub const MAX_BACKGROUND_LEVEL: u32 = 15;
+
+  /// Creates a renderer that presents to the window the pixels were created for.
+  ///
+  /// Any part of the window the buffer isn't shown in is cleared to black.
+  pub fn new(pixels: Pixels, buffer_dimensions: LogicalSize<u32>) -> Self {
+    Self::with_target(PixelsTarget::new(pixels, buffer_dimensions))
+  }
+
+  /// Creates a renderer that draws to a buffer in memory instead of a window.
+  ///
+  /// The buffer starts out as opaque black.
+  /// Nothing is ever presented, making this useful for testing what gets drawn.
+  pub fn new_in_memory(buffer_dimensions: LogicalSize<u32>) -> Self {
+    Self::with_target(InMemoryTarget::new(buffer_dimensions))
+  }
+
+  /// Creates a renderer that draws to the given target, with a buffer the size of the target's frame.
+  pub fn with_target(target: impl DrawTarget + 'static) -> Self {
+    let buffer_dimensions = target.dimensions();
+
     Self {
-      pixels,
+      frame_buffer: Box::new(target),
+      buffer_dimensions,
+      clip: None,
+      blend_mode: BlendMode::Normal,
+      viewport: (
+        PhysicalPosition::new(0, 0),
+        PhysicalSize::new(buffer_dimensions.width, buffer_dimensions.height),
+      ),
       loaded_fonts: Vec::with_capacity(2),
       font_layout_by_name: Vec::with_capacity(2),
+      glyph_cache: GlyphCache::new(),
     }
   }
 
-  /// Calls `.render()` on the contained pixels::Pixels.
+  /// Swaps the draw target for another, such as one presenting differently, keeping the loaded fonts.
+  ///
+  /// The buffer takes on the size of the new target's frame, and the old target is dropped.
+  pub fn set_target(&mut self, target: impl DrawTarget + 'static) {
+    self.buffer_dimensions = target.dimensions();
+    self.frame_buffer = Box::new(target);
+  }
+
+  /// Presents the frame on the draw target, such as by calling `.render()` on the contained pixels::Pixels.
+  ///
+  /// Does nothing when drawing to a buffer in memory.
   pub fn complete_render(&self) -> anyhow::Result<()> {
-    self.pixels.render().map_err(Into::into)
+    self.frame_buffer.present()
   }
 
-  /// Resizes the internal surface.
+  /// Resizes the internal surface to the new window dimensions, keeping the buffer's aspect ratio.
+  ///
+  /// The buffer is shown in the [`viewport`](DrawTarget::viewport) of the draw target,
+  /// with the space around it left as black bars.
+  /// The surface itself is only resized when drawing to a window.
   pub fn resize_surface(&mut self, new_dimensions: PhysicalSize<u32>) -> anyhow::Result<()> {
+    self.frame_buffer.resize_surface(new_dimensions)?;
+    self.viewport = self.frame_buffer.viewport(new_dimensions);
+
+    Ok(())
+  }
+
+  /// Returns where in the window the buffer is shown, as of the last [`resize`](Renderer::resize_surface).
+  pub fn viewport(&self) -> Viewport {
+    self.viewport
+  }
+
+  /// Converts a position in the window to the pixel of the buffer shown there.
+  ///
+  /// None is returned for positions in the black bars around the buffer.
+  pub fn window_to_buffer_position(
+    &self,
+    window_position: PhysicalPosition<f32>,
+  ) -> Option<LogicalPosition<u32>> {
     self
-      .pixels
-      .resize_surface(new_dimensions.width.max(1), new_dimensions.height.max(1))
-      .map_err(Into::into)
+      .frame_buffer
+      .window_to_buffer_position(window_position, &self.viewport)
+  }
+
+  /// Returns the largest rect that fits in the window with the same aspect ratio as the buffer, centered in the window.
+  ///
+  /// Wide windows get bars on the left and right, while tall windows get them above and below.
+  pub fn letterbox_rect(
+    window_dimensions: PhysicalSize<u32>,
+    buffer_dimensions: LogicalSize<u32>,
+  ) -> Viewport {
+    let window_width = window_dimensions.width as u64;
+    let window_height = window_dimensions.height as u64;
+    let buffer_width = buffer_dimensions.width.max(1) as u64;
+    let buffer_height = buffer_dimensions.height.max(1) as u64;
+
+    // Comparing the cross products avoids rounding, the window is wider than the buffer if this is true.
+    let (width, height) = if window_width * buffer_height > window_height * buffer_width {
+      (window_height * buffer_width / buffer_height, window_height)
+    } else {
+      (window_width, window_width * buffer_height / buffer_width)
+    };
+
+    (
+      PhysicalPosition::new(
+        ((window_width - width) / 2) as u32,
+        ((window_height - height) / 2) as u32,
+      ),
+      PhysicalSize::new(width as u32, height as u32),
+    )
+  }
+
+  /// Limits drawing to the given region of the buffer, or removes the limit with None.
+  ///
+  /// Drawing methods skip any pixel outside of the region instead of drawing it.
+  /// Clearing and filling the whole buffer with [`set_color()`](Renderer::set_color) ignore the region.
+  pub fn set_clip(&mut self, clip: Option<ClipRegion>) {
+    self.clip = clip;
+  }
+
+  /// Returns the region drawing is currently limited to.
+  pub fn clip(&self) -> Option<ClipRegion> {
+    self.clip
+  }
+
+  /// Changes how shapes are combined with what's already in the buffer.
+  ///
+  /// Applies to [`apply_color()`](Renderer::apply_color), rectangles, and lines.
+  /// Images and text are always drawn with [`BlendMode::Normal`].
+  pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+    self.blend_mode = blend_mode;
+  }
+
+  pub fn blend_mode(&self) -> BlendMode {
+    self.blend_mode
   }
 
   /// Replaces every pixel in the buffer with the given color.
-  pub fn set_color(&mut self, rgb: [u8; 3]) -> anyhow::Result<()> {
-    for (iteration, byte) in self.pixels.frame_mut().iter_mut().enumerate() {
+  pub fn set_color(&mut self, color: impl Into<Color>) -> anyhow::Result<()> {
+    let Color { r, g, b, .. } = color.into();
+    let rgb = [r, g, b];
+
+    for (iteration, byte) in self.frame_mut().iter_mut().enumerate() {
       *byte = match iteration % 4 {
         3 => 255,
         n => rgb[2 - n],
@@ -52,7 +209,7 @@ impl Renderer {
   }
 
   pub fn clear(&mut self) -> anyhow::Result<()> {
-    for (iteration, byte) in self.pixels.frame_mut().iter_mut().enumerate() {
+    for (iteration, byte) in self.frame_mut().iter_mut().enumerate() {
       *byte = if iteration % 4 == 3 { 255 } else { 0 };
     }
 
@@ -60,35 +217,339 @@ impl Renderer {
   }
 
   /// Applies the color with the given alpha to every pixel on the screen.
-  pub fn apply_color(&mut self, rgba: [u8; 4]) -> anyhow::Result<()> {
-    let buffer = self.pixels.frame_mut();
+  pub fn apply_color(&mut self, color: impl Into<Color>) -> anyhow::Result<()> {
+    let rgba = color.into().to_array();
+    let (clip, blend_mode, buffer_width) =
+      (self.clip, self.blend_mode, self.buffer_dimensions.width);
+    let buffer = self.frame_mut();
     let pixel_count = buffer.len() / 4;
 
     for index in 0..pixel_count {
-      Self::draw_at_pixel_with_rgba(buffer, index, &rgba)?;
+      Self::draw_at_blended_pixel(
+        buffer,
+        index,
+        &rgba,
+        clip.as_ref(),
+        buffer_width,
+        blend_mode,
+      )?;
+    }
+
+    Ok(())
+  }
+
+  /// Fills the buffer with the background gradient, intensified based on the given level.
+  ///
+  /// Refer to [`background_gradient_color()`](Renderer::background_gradient_color) for how each pixel is colored.
+  pub fn draw_background_gradient(
+    &mut self,
+    level: u32,
+    buffer_dimensions: &LogicalSize<u32>,
+  ) -> anyhow::Result<()> {
+    let pixel_buffer = self.frame_mut();
+    let pixel_count = buffer_dimensions.width * buffer_dimensions.height;
+
+    for index in 0..pixel_count {
+      let (x, y) = (
+        index % buffer_dimensions.width,
+        index / buffer_dimensions.width,
+      );
+      let rgb = Self::background_gradient_color(x, y, buffer_dimensions, level);
+
+      Self::draw_at_pixel_with_rgb(pixel_buffer, index as usize, &rgb)?;
     }
 
     Ok(())
   }
 
+  /// Returns the color of the background gradient at the given pixel.
+  ///
+  /// Red increases going down the buffer and blue increases going right.
+  /// Every level past 1 shifts both endpoints towards red, up to [`MAX_BACKGROUND_LEVEL`](Renderer::MAX_BACKGROUND_LEVEL).
+  pub fn background_gradient_color(
+    x: u32,
+    y: u32,
+    buffer_dimensions: &LogicalSize<u32>,
+    level: u32,
+  ) -> [u8; 3] {
+    let x_percentage = x as f64 / buffer_dimensions.width as f64;
+    let y_percentage = y as f64 / buffer_dimensions.height as f64;
+
+    let pressure = (level.clamp(1, Self::MAX_BACKGROUND_LEVEL) - 1) * 8;
+
+    let red = (255.0 * y_percentage).cast::<u32>() + pressure;
+    let blue = (255.0 * x_percentage)
+      .cast::<u32>()
+      .saturating_sub(pressure);
+
+    [red.min(255) as u8, 0, blue as u8]
+  }
+
+  /// Moves everything drawn in the frame by the given offset, with y going down.
+  ///
+  /// Pixels moved outside of the buffer are dropped, and the uncovered edges are filled with black.
+  pub fn offset_frame(&mut self, offset_x: i32, offset_y: i32) {
+    let LogicalSize { width, height } = self.buffer_dimensions;
+    let frame = self.frame_mut();
+    let original_frame = frame.to_vec();
+
+    for (index, pixel) in frame.chunks_exact_mut(4).enumerate() {
+      let (x, y) = ((index as u32 % width) as i32, (index as u32 / width) as i32);
+      let (source_x, source_y) = (x - offset_x, y - offset_y);
+
+      if !(0..width as i32).contains(&source_x) || !(0..height as i32).contains(&source_y) {
+        pixel.copy_from_slice(&Color::BLACK.to_array());
+
+        continue;
+      }
+
+      let source_index = ((source_x as u32 + (source_y as u32 * width)) * 4) as usize;
+      pixel.copy_from_slice(&original_frame[source_index..(source_index + 4)]);
+    }
+  }
+
+  /// Blurs the given region of the frame buffer.
+  ///
+  /// Refer to [`box_blur_buffer_region()`](Renderer::box_blur_buffer_region) for how the region is blurred.
+  pub fn box_blur_region(
+    &mut self,
+    origin: &LogicalPosition<u32>,
+    dimensions: &LogicalSize<u32>,
+    radius: u32,
+  ) {
+    let buffer_dimensions = self.buffer_dimensions;
+
+    Self::box_blur_buffer_region(
+      self.frame_mut(),
+      &buffer_dimensions,
+      origin,
+      dimensions,
+      radius,
+    )
+  }
+
+  /// Replaces every pixel in the region with the average color of the pixels within `radius` of it.
+  ///
+  /// Averages are taken from a copy of the region, so already blurred pixels don't feed into their neighbors.
+  /// Only pixels inside the region are sampled, and the region is clamped to the buffer.
+  /// The alpha channel is left untouched.
+  pub fn box_blur_buffer_region(
+    pixel_buffer: &mut [u8],
+    buffer_dimensions: &LogicalSize<u32>,
+    origin: &LogicalPosition<u32>,
+    dimensions: &LogicalSize<u32>,
+    radius: u32,
+  ) {
+    let region_width = dimensions
+      .width
+      .min(buffer_dimensions.width.saturating_sub(origin.x));
+    let region_height = dimensions
+      .height
+      .min(buffer_dimensions.height.saturating_sub(origin.y));
+
+    if radius == 0 || region_width == 0 || region_height == 0 {
+      return;
+    }
+
+    let buffer_index =
+      |x: u32, y: u32| (((origin.x + x) + ((origin.y + y) * buffer_dimensions.width)) * 4) as usize;
+
+    let region_copy: Vec<[u8; 3]> = (0..(region_width * region_height))
+      .map(|index| {
+        let start = buffer_index(index % region_width, index / region_width);
+
+        [
+          pixel_buffer[start],
+          pixel_buffer[start + 1],
+          pixel_buffer[start + 2],
+        ]
+      })
+      .collect();
+
+    for y in 0..region_height {
+      for x in 0..region_width {
+        let (left, right) = (x.saturating_sub(radius), (x + radius).min(region_width - 1));
+        let (top, bottom) = (
+          y.saturating_sub(radius),
+          (y + radius).min(region_height - 1),
+        );
+
+        let mut channel_sums = [0_u32; 3];
+        let mut sample_count = 0;
+
+        for sample_y in top..=bottom {
+          for sample_x in left..=right {
+            let sample = region_copy[(sample_x + (sample_y * region_width)) as usize];
+
+            for (sum, channel) in channel_sums.iter_mut().zip(sample) {
+              *sum += channel as u32;
+            }
+
+            sample_count += 1;
+          }
+        }
+
+        let start = buffer_index(x, y);
+        let averaged_color = channel_sums.map(|sum| (sum / sample_count) as u8);
+
+        pixel_buffer[start..(start + 3)].copy_from_slice(&averaged_color);
+      }
+    }
+  }
+
+  /// Returns the dimensions of the frame buffer in pixels.
+  pub fn buffer_dimensions(&self) -> LogicalSize<u32> {
+    self.buffer_dimensions
+  }
+
+  /// Returns a copy of the current frame, shrunk by the given factor.
+  ///
+  /// Refer to [`downscale_buffer()`](Renderer::downscale_buffer) for how the frame is shrunk.
+  pub fn downscale_frame(&self, factor: u32) -> (Vec<u8>, LogicalSize<u32>) {
+    Self::downscale_buffer(self.frame(), &self.buffer_dimensions, factor)
+  }
+
+  /// Shrinks an rgba buffer by an integer factor, returning the new buffer and its dimensions.
+  ///
+  /// Each pixel in the new buffer is the average of the `factor x factor` block of pixels it covers.
+  /// Any leftover rows or columns that don't fill an entire block are dropped.
+  /// A factor of 0 is treated as 1.
+  pub fn downscale_buffer(
+    pixel_buffer: &[u8],
+    buffer_dimensions: &LogicalSize<u32>,
+    factor: u32,
+  ) -> (Vec<u8>, LogicalSize<u32>) {
+    let factor = factor.max(1);
+    let downscaled_dimensions = LogicalSize::new(
+      buffer_dimensions.width / factor,
+      buffer_dimensions.height / factor,
+    );
+    let block_size = factor * factor;
+
+    let mut downscaled_buffer =
+      Vec::with_capacity((downscaled_dimensions.width * downscaled_dimensions.height * 4) as usize);
+
+    for y in 0..downscaled_dimensions.height {
+      for x in 0..downscaled_dimensions.width {
+        let mut channel_sums = [0_u32; 4];
+
+        for index in 0..block_size {
+          let source_x = (x * factor) + (index % factor);
+          let source_y = (y * factor) + (index / factor);
+          let source_index = ((source_x + (source_y * buffer_dimensions.width)) * 4) as usize;
+
+          for (channel, sum) in channel_sums.iter_mut().enumerate() {
+            *sum += pixel_buffer[source_index + channel] as u32;
+          }
+        }
+
+        downscaled_buffer.extend(channel_sums.map(|sum| (sum / block_size) as u8));
+      }
+    }
+
+    (downscaled_buffer, downscaled_dimensions)
+  }
+
   /// Returns a mutable reference to the frame buffer.
   pub fn frame_mut(&mut self) -> &mut [u8] {
-    self.pixels.frame_mut()
+    self.frame_buffer.frame_mut()
   }
 
   /// Returns a reference to the frame buffer.
   pub fn frame(&self) -> &[u8] {
-    self.pixels.frame()
+    self.frame_buffer.frame()
+  }
+
+  /// Saves a copy of the current frame buffer as a PNG at the given path.
+  ///
+  /// The image format is decided by the path's extension, so it should end in `.png`.
+  pub fn save_screenshot(&self, path: &Path) -> anyhow::Result<()> {
+    let Some(screenshot) = image::RgbaImage::from_raw(
+      self.buffer_dimensions.width,
+      self.buffer_dimensions.height,
+      self.frame().to_vec(),
+    ) else {
+      return Err(anyhow!(
+        "The frame buffer doesn't match the buffer dimensions: {:?}",
+        self.buffer_dimensions
+      ));
+    };
+
+    screenshot.save(path)?;
+
+    Ok(())
+  }
+
+  /// Draws a rectangle filled with the given color, with its top left at the given position.
+  ///
+  /// Opaque rectangles drawn normally within the buffer are filled a row at a time,
+  /// anything else is blended a pixel at a time.
+  pub fn filled_rectangle(
+    &mut self,
+    position: &LogicalPosition<u32>,
+    dimensions: &LogicalSize<u32>,
+    color: impl Into<Color>,
+  ) -> anyhow::Result<()> {
+    let color = color.into().to_array();
+    let fits_in_buffer = position.x + dimensions.width <= self.buffer_dimensions.width
+      && position.y + dimensions.height <= self.buffer_dimensions.height;
+
+    if color[3] == 255 && self.blend_mode == BlendMode::Normal && fits_in_buffer {
+      self.fill_opaque_rectangle(position, dimensions, color);
+
+      return Ok(());
+    }
+
+    self.filled_rectangle_per_pixel(position, dimensions, color)
   }
 
-  pub fn draw_rectangle(
+  /// Fills the rectangle by copying the color into each row, skipping anything outside of the clip region.
+  ///
+  /// The rectangle must fit within the buffer. Only the color channels are written, like
+  /// [`draw_at_pixel_with_rgba()`](Renderer::draw_at_pixel_with_rgba) does for opaque colors.
+  fn fill_opaque_rectangle(
+    &mut self,
+    position: &LogicalPosition<u32>,
+    dimensions: &LogicalSize<u32>,
+    color: [u8; 4],
+  ) {
+    let buffer_width = self.buffer_dimensions.width;
+    let (mut left, mut top) = (position.x, position.y);
+    let (mut right, mut bottom) = (left + dimensions.width, top + dimensions.height);
+
+    if let Some((clip_position, clip_dimensions)) = self.clip {
+      left = left.max(clip_position.x);
+      top = top.max(clip_position.y);
+      right = right.min(clip_position.x + clip_dimensions.width);
+      bottom = bottom.min(clip_position.y + clip_dimensions.height);
+    }
+
+    if left >= right || top >= bottom {
+      return;
+    }
+
+    let buffer = self.frame_mut();
+
+    for y in top..bottom {
+      let row_start = ((left + (y * buffer_width)) * 4) as usize;
+      let row_end = ((right + (y * buffer_width)) * 4) as usize;
+
+      buffer[row_start..row_end]
+        .chunks_exact_mut(4)
+        .for_each(|pixel| pixel[..3].copy_from_slice(&color[..3]));
+    }
+  }
+
+  /// Draws the rectangle by blending each of its pixels with the current blend mode.
+  fn filled_rectangle_per_pixel(
     &mut self,
     position: &LogicalPosition<u32>,
     dimensions: &LogicalSize<u32>,
     color: [u8; 4],
-    buffer_dimensions: &LogicalSize<u32>,
   ) -> anyhow::Result<()> {
-    let buffer = self.pixels.frame_mut();
+    let (clip, blend_mode, buffer_dimensions) =
+      (self.clip, self.blend_mode, self.buffer_dimensions);
+    let buffer = self.frame_mut();
 
     let LogicalSize {
       width: rectangle_width,
@@ -102,36 +563,317 @@ impl Renderer {
         + (index % rectangle_width)
         + ((index / rectangle_width) * buffer_dimensions.width);
 
-      Self::draw_at_pixel_with_rgba(buffer, window_index as usize, &color)?;
+      Self::draw_at_blended_pixel(
+        buffer,
+        window_index as usize,
+        &color,
+        clip.as_ref(),
+        buffer_dimensions.width,
+        blend_mode,
+      )?;
+    }
+
+    Ok(())
+  }
+
+  /// Draws the 1 pixel wide outline of a rectangle, with both given corners being part of the outline.
+  ///
+  /// Every pixel of the outline is drawn exactly once, so translucent colors have even corners.
+  /// The corners can be given in either order.
+  pub fn bounding_rectangle(
+    &mut self,
+    top_left: &LogicalPosition<i32>,
+    bottom_right: &LogicalPosition<i32>,
+    color: impl Into<Color>,
+  ) -> anyhow::Result<()> {
+    let color = color.into();
+    let (left, right) = (
+      top_left.x.min(bottom_right.x),
+      top_left.x.max(bottom_right.x),
+    );
+    let (top, bottom) = (
+      top_left.y.min(bottom_right.y),
+      top_left.y.max(bottom_right.y),
+    );
+
+    // The top and bottom edges own the corners, the sides only cover the rows between them.
+    self.line(
+      &LogicalPosition::new(left, top),
+      &LogicalPosition::new(right, top),
+      color,
+    )?;
+
+    if bottom == top {
+      return Ok(());
+    }
+
+    self.line(
+      &LogicalPosition::new(left, bottom),
+      &LogicalPosition::new(right, bottom),
+      color,
+    )?;
+
+    if bottom - top < 2 {
+      return Ok(());
+    }
+
+    let side_columns = if left == right {
+      vec![left]
+    } else {
+      vec![left, right]
+    };
+
+    for x in side_columns {
+      self.line(
+        &LogicalPosition::new(x, top + 1),
+        &LogicalPosition::new(x, bottom - 1),
+        color,
+      )?;
+    }
+
+    Ok(())
+  }
+
+  /// Draws a 1 pixel wide line between both positions, including both ends.
+  ///
+  /// Any part of the line outside of the buffer is skipped.
+  pub fn line(
+    &mut self,
+    start: &LogicalPosition<i32>,
+    end: &LogicalPosition<i32>,
+    color: impl Into<Color>,
+  ) -> anyhow::Result<()> {
+    let color = color.into().to_array();
+    let (clip, blend_mode, buffer_dimensions) =
+      (self.clip, self.blend_mode, self.buffer_dimensions);
+    let buffer = self.frame_mut();
+
+    let (delta_x, delta_y) = ((end.x - start.x).abs(), -(end.y - start.y).abs());
+    let (step_x, step_y) = ((end.x - start.x).signum(), (end.y - start.y).signum());
+    let (mut x, mut y) = (start.x, start.y);
+    let mut error = delta_x + delta_y;
+
+    loop {
+      if (0..buffer_dimensions.width as i32).contains(&x)
+        && (0..buffer_dimensions.height as i32).contains(&y)
+      {
+        let pixel_index = x as u32 + (y as u32 * buffer_dimensions.width);
+
+        Self::draw_at_blended_pixel(
+          buffer,
+          pixel_index as usize,
+          &color,
+          clip.as_ref(),
+          buffer_dimensions.width,
+          blend_mode,
+        )?;
+      }
+
+      if x == end.x && y == end.y {
+        return Ok(());
+      }
+
+      let doubled_error = error * 2;
+
+      if doubled_error >= delta_y {
+        error += delta_y;
+        x += step_x;
+      }
+
+      if doubled_error <= delta_x {
+        error += delta_x;
+        y += step_y;
+      }
+    }
+  }
+
+  /// Draws an arrow of the given length pointing in the given direction, with its tip at the end position.
+  ///
+  /// Refer to [`arrow_segments()`](Renderer::arrow_segments) for the lines that make up the arrow.
+  pub fn draw_arrow(
+    &mut self,
+    end: &LogicalPosition<i32>,
+    length: u32,
+    direction: Direction,
+    color: impl Into<Color>,
+  ) -> anyhow::Result<()> {
+    let color = color.into();
+
+    for (start, end) in Self::arrow_segments(end, length, direction) {
+      self.line(&start, &end, color)?;
     }
 
     Ok(())
   }
 
+  /// Returns the start and end of the shaft and both wings of an arrow, each ending at the tip.
+  ///
+  /// The shaft is the given length, and each wing reaches half that length back from the tip on either side.
+  pub fn arrow_segments(
+    end: &LogicalPosition<i32>,
+    length: u32,
+    direction: Direction,
+  ) -> [(LogicalPosition<i32>, LogicalPosition<i32>); 3] {
+    let (direction_x, direction_y) = direction.offset();
+    // The wings are offset along the axis perpendicular to the arrow, starting with the top or left wing.
+    let (side_x, side_y) = (direction_y.abs(), direction_x.abs());
+    let length = length as i32;
+    let wing_length = length / 2;
+
+    let shaft_start =
+      LogicalPosition::new(end.x - direction_x * length, end.y - direction_y * length);
+    let wing_back = (
+      end.x - direction_x * wing_length,
+      end.y - direction_y * wing_length,
+    );
+    let first_wing_start = LogicalPosition::new(
+      wing_back.0 - side_x * wing_length,
+      wing_back.1 - side_y * wing_length,
+    );
+    let second_wing_start = LogicalPosition::new(
+      wing_back.0 + side_x * wing_length,
+      wing_back.1 + side_y * wing_length,
+    );
+
+    [
+      (shaft_start, *end),
+      (first_wing_start, *end),
+      (second_wing_start, *end),
+    ]
+  }
+
+  /// Draws the image to the frame buffer with its top left at the given offset.
+  ///
+  /// Refer to [`draw_image_to_buffer()`](Renderer::draw_image_to_buffer) for how non-rgba8 images are handled.
   pub fn render_image(
     &mut self,
     offset: &LogicalPosition<u32>,
     image: &DynamicImage,
     window_dimensions: &LogicalSize<u32>,
+  ) -> anyhow::Result<()> {
+    let clip = self.clip;
+
+    Self::draw_clipped_image_to_buffer(
+      self.frame_mut(),
+      offset,
+      image,
+      window_dimensions,
+      clip.as_ref(),
+    )
+  }
+
+  /// Draws the image to the frame buffer with its top left at the given offset, resized by the given scale.
+  ///
+  /// Each drawn pixel takes the color of the nearest pixel in the source image.
+  /// Any part of the scaled image outside of the buffer is skipped.
+  ///
+  /// # Errors
+  ///
+  /// - When the scale is zero, negative, or not a finite number.
+  pub fn render_image_scaled(
+    &mut self,
+    offset: &LogicalPosition<u32>,
+    image: &DynamicImage,
+    scale: f32,
+  ) -> anyhow::Result<()> {
+    if !scale.is_finite() || scale <= 0.0 {
+      return Err(anyhow!(
+        "Attempted to draw an image with an invalid scale: `{}`",
+        scale
+      ));
+    }
+
+    let (clip, buffer_dimensions) = (self.clip, self.buffer_dimensions);
+    let (image_width, image_height) = (image.width(), image.height());
+    let scaled_width = (image_width as f32 * scale).round() as u32;
+    let scaled_height = (image_height as f32 * scale).round() as u32;
+
+    let image_buffer = match image.as_rgba8() {
+      Some(image_buffer) => Cow::Borrowed(image_buffer),
+      None => Cow::Owned(image.to_rgba8()),
+    };
+    let buffer = self.frame_mut();
+
+    for y in 0..scaled_height {
+      let buffer_y = offset.y + y;
+
+      if buffer_y >= buffer_dimensions.height {
+        break;
+      }
+
+      let source_y = ((y as f32 / scale) as u32).min(image_height - 1);
+
+      for x in 0..scaled_width {
+        let buffer_x = offset.x + x;
+
+        if buffer_x >= buffer_dimensions.width {
+          break;
+        }
+
+        let source_x = ((x as f32 / scale) as u32).min(image_width - 1);
+        let rgba = image_buffer.get_pixel(source_x, source_y).0;
+        let buffer_index = buffer_x + (buffer_y * buffer_dimensions.width);
+
+        Self::draw_at_clipped_pixel(
+          buffer,
+          buffer_index as usize,
+          &rgba,
+          clip.as_ref(),
+          buffer_dimensions.width,
+        )?;
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Draws the image to the given buffer with its top left at the given offset.
+  ///
+  /// Images that aren't stored as rgba8 (such as grayscale or rgb images) are converted before drawing.
+  /// This conversion allocates a new copy of the image every call, so assets drawn every frame should be
+  /// stored as rgba8 ahead of time.
+  pub fn draw_image_to_buffer(
+    pixel_buffer: &mut [u8],
+    offset: &LogicalPosition<u32>,
+    image: &DynamicImage,
+    buffer_dimensions: &LogicalSize<u32>,
+  ) -> anyhow::Result<()> {
+    Self::draw_clipped_image_to_buffer(pixel_buffer, offset, image, buffer_dimensions, None)
+  }
+
+  /// Draws the image to the given buffer like [`draw_image_to_buffer()`](Renderer::draw_image_to_buffer),
+  /// skipping any pixel outside of the clip region.
+  fn draw_clipped_image_to_buffer(
+    pixel_buffer: &mut [u8],
+    offset: &LogicalPosition<u32>,
+    image: &DynamicImage,
+    buffer_dimensions: &LogicalSize<u32>,
+    clip: Option<&ClipRegion>,
   ) -> anyhow::Result<()> {
     let image_width = image.width();
     let image_height = image.height();
 
-    let Some(image_buffer) = image.as_rgba8() else {
-      return Err(anyhow!("Failed to read image as rgba8 when rendering."));
+    let image_buffer = match image.as_rgba8() {
+      Some(image_buffer) => Cow::Borrowed(image_buffer),
+      None => Cow::Owned(image.to_rgba8()),
     };
 
-    let frame_buffer = self.pixels.frame_mut();
     let position = offset;
-    let top_left = position.x + (position.y * window_dimensions.width);
+    let top_left = position.x + (position.y * buffer_dimensions.width);
     let image_buffer = image_buffer.chunks_exact(4);
 
     for (index, rgba) in (0..(image_width * image_height)).zip(image_buffer) {
       let rgba: &[u8; 4] = rgba.try_into()?;
       let (x, y) = (index % image_width, index / image_width);
-      let buffer_index = (top_left + x + (y * window_dimensions.width)) as usize;
+      let buffer_index = (top_left + x + (y * buffer_dimensions.width)) as usize;
 
-      Self::draw_at_pixel_with_rgba(frame_buffer, buffer_index, rgba)?
+      Self::draw_at_clipped_pixel(
+        pixel_buffer,
+        buffer_index,
+        rgba,
+        clip,
+        buffer_dimensions.width,
+      )?
     }
 
     Ok(())
@@ -140,12 +882,24 @@ impl Renderer {
   /// Loads a font into memory from a font file's bytes.
   ///
   /// Stored in a list, [`render_text_box()`](Renderer::render_text_box) uses the index of these stored fonts.
-  /// The index is in the order by which the fonts were loaded.
+  /// The index is in the order by which the fonts were loaded, and can be found from the font's name with
+  /// [`font_index()`](Renderer::font_index).
+  ///
+  /// # Errors
+  /// - When the bytes aren't a valid font.
+  /// - When a font has already been loaded with the name.
   pub fn load_font_from_bytes(
     &mut self,
     font_data: &[u8],
     font_name: &'static str,
   ) -> anyhow::Result<()> {
+    if self.font_index(font_name).is_some() {
+      return Err(anyhow!(
+        "Attempted to load a second font named `{}`.",
+        font_name
+      ));
+    }
+
     let font = match Font::from_bytes(font_data, fontdue::FontSettings::default()) {
       Ok(font) => font,
       Err(error) => return Err(anyhow!("Failed to load the font: `{:?}`", error)),
@@ -161,16 +915,15 @@ impl Renderer {
   pub fn render_text_box(
     &mut self,
     text_box: &TextBox,
-    color: [u8; 4],
+    color: impl Into<Color>,
     buffer_dimensions: &LogicalSize<u32>,
   ) -> anyhow::Result<()> {
+    let color = color.into().to_array();
     let Some(font_index) = text_box.font_index() else {
       log::warn!("Attempted to render an empty text box.");
 
       return Ok(());
     };
-    let position = text_box.position().unwrap();
-
     let Some(font) = self.loaded_fonts.get(font_index) else {
       return Err(anyhow!(
         "Attempted to load a font that didn't exist. Index: {}, Font count: {}",
@@ -179,23 +932,31 @@ impl Renderer {
       ));
     };
 
-    let buffer = self.pixels.frame_mut();
-    let top_left_placement = position.x + (position.y * buffer_dimensions.width);
+    let clip = self.clip;
+    let buffer = self.frame_buffer.frame_mut();
+    let glyph_cache = &mut self.glyph_cache;
 
     let result: anyhow::Result<()> = text_box.character_data().iter().try_for_each(|glyph| {
-      if !glyph.parent.is_ascii() {
-        return Err(anyhow!(
-          "Attempted to render a non-ascii character: `{:?}`",
-          glyph.parent
-        ));
+      // Characters the font has no glyph for are drawn as a box, so they don't silently disappear.
+      if font.lookup_glyph_index(glyph.parent) == 0
+        && !glyph.parent.is_whitespace()
+        && !glyph.parent.is_control()
+      {
+        return Self::draw_missing_glyph_box(
+          buffer,
+          glyph,
+          &color,
+          clip.as_ref(),
+          buffer_dimensions,
+        );
       }
 
-      let (metadata, bitmap) = font.rasterize(glyph.parent, glyph.key.px);
+      let (metadata, bitmap) = glyph_cache.rasterize(font, font_index, glyph.parent, glyph.key.px);
       let (text_width, text_height) = (glyph.width as u32, metadata.height as u32);
 
-      let top_left_placement = top_left_placement
-        + glyph.x.cast::<u32>()
-        + (glyph.y.cast::<u32>() * buffer_dimensions.width);
+      // Glyph positions already include the text box's position.
+      let top_left_placement =
+        glyph.x.cast::<u32>() + (glyph.y.cast::<u32>() * buffer_dimensions.width);
 
       for index in 0..(text_width * text_height) {
         let position = top_left_placement
@@ -215,7 +976,13 @@ impl Renderer {
           color[3],
         ];
 
-        Self::draw_at_pixel_with_rgba(buffer, position as usize, &color)?;
+        Self::draw_at_clipped_pixel(
+          buffer,
+          position as usize,
+          &color,
+          clip.as_ref(),
+          buffer_dimensions.width,
+        )?;
       }
 
       Ok(())
@@ -227,6 +994,50 @@ impl Renderer {
       return Err(anyhow!("Failed to render the text. `{:?}`", error));
     }
 
+    text_box
+      .decoration_lines()
+      .iter()
+      .try_for_each(|(start, end)| self.line(start, end, color))
+  }
+
+  /// Returns the cache of glyphs rasterized when rendering text.
+  pub fn glyph_cache(&self) -> &GlyphCache {
+    &self.glyph_cache
+  }
+
+  /// Draws the outline of a box in place of a glyph the font doesn't contain.
+  ///
+  /// The box is half as wide as the text size, and most of its height.
+  fn draw_missing_glyph_box(
+    pixel_buffer: &mut [u8],
+    glyph: &GlyphPosition<()>,
+    color: &[u8; 4],
+    clip: Option<&ClipRegion>,
+    buffer_dimensions: &LogicalSize<u32>,
+  ) -> anyhow::Result<()> {
+    let box_width = ((glyph.key.px * 0.5).ceil() as u32).max(2);
+    let box_height = ((glyph.key.px * 0.7).ceil() as u32).max(2);
+    let (left, top) = (glyph.x.cast::<u32>(), glyph.y.cast::<u32>());
+
+    for y in 0..box_height {
+      for x in 0..box_width {
+        let is_edge = x == 0 || y == 0 || x == box_width - 1 || y == box_height - 1;
+        let (pixel_x, pixel_y) = (left + x, top + y);
+
+        if !is_edge || pixel_x >= buffer_dimensions.width || pixel_y >= buffer_dimensions.height {
+          continue;
+        }
+
+        Self::draw_at_clipped_pixel(
+          pixel_buffer,
+          (pixel_x + (pixel_y * buffer_dimensions.width)) as usize,
+          color,
+          clip,
+          buffer_dimensions.width,
+        )?;
+      }
+    }
+
     Ok(())
   }
 
@@ -234,13 +1045,26 @@ impl Renderer {
     &self.loaded_fonts
   }
 
-  pub fn fonts_with_names(&self) -> Vec<(&'static str, &Font)> {
+  /// Returns the index of the font loaded with the given name.
+  pub fn font_index(&self, font_name: &str) -> Option<usize> {
     self
       .font_layout_by_name
       .iter()
-      .zip(self.loaded_fonts.iter())
-      .map(|(font_name, font)| (*font_name, font))
-      .collect()
+      .position(|name| *name == font_name)
+  }
+
+  /// Returns the font loaded with the given name.
+  pub fn get_font(&self, font_name: &str) -> Option<&Font> {
+    self.loaded_fonts.get(self.font_index(font_name)?)
+  }
+
+  pub fn fonts_with_names(&self) -> Vec<(&'static str, &Font)> {
+    self
+      .font_layout_by_name
+      .iter()
+      .zip(self.loaded_fonts.iter())
+      .map(|(font_name, font)| (*font_name, font))
+      .collect()
   }
 
   /// Draws at the pixel in the frame buffer.
@@ -299,6 +1123,107 @@ impl Renderer {
     Ok(())
   }
 
+  /// Draws at the pixel like [`draw_at_pixel_with_rgba()`](Renderer::draw_at_pixel_with_rgba),
+  /// unless the pixel is outside of the given clip region.
+  #[inline]
+  fn draw_at_clipped_pixel(
+    pixel_buffer: &mut [u8],
+    pixel_index: usize,
+    rgba: &[u8; 4],
+    clip: Option<&ClipRegion>,
+    buffer_width: u32,
+  ) -> anyhow::Result<()> {
+    Self::draw_at_blended_pixel(
+      pixel_buffer,
+      pixel_index,
+      rgba,
+      clip,
+      buffer_width,
+      BlendMode::Normal,
+    )
+  }
+
+  /// Draws at the pixel like [`draw_at_pixel_with_blend()`](Renderer::draw_at_pixel_with_blend),
+  /// unless the pixel is outside of the given clip region.
+  #[inline]
+  fn draw_at_blended_pixel(
+    pixel_buffer: &mut [u8],
+    pixel_index: usize,
+    rgba: &[u8; 4],
+    clip: Option<&ClipRegion>,
+    buffer_width: u32,
+    blend_mode: BlendMode,
+  ) -> anyhow::Result<()> {
+    if let Some((clip_position, clip_dimensions)) = clip {
+      let (x, y) = (
+        pixel_index as u32 % buffer_width,
+        pixel_index as u32 / buffer_width,
+      );
+
+      if x < clip_position.x
+        || y < clip_position.y
+        || x >= clip_position.x + clip_dimensions.width
+        || y >= clip_position.y + clip_dimensions.height
+      {
+        return Ok(());
+      }
+    }
+
+    Self::draw_at_pixel_with_blend(pixel_buffer, pixel_index, rgba, blend_mode)
+  }
+
+  /// Draws at the pixel in the frame buffer, combining the color with the existing one using the blend mode.
+  ///
+  /// [`BlendMode::Normal`] is the same as [`draw_at_pixel_with_rgba()`](Renderer::draw_at_pixel_with_rgba).
+  #[inline]
+  pub fn draw_at_pixel_with_blend(
+    pixel_buffer: &mut [u8],
+    pixel_index: usize,
+    rgba: &[u8; 4],
+    blend_mode: BlendMode,
+  ) -> anyhow::Result<()> {
+    if blend_mode == BlendMode::Normal {
+      return Self::draw_at_pixel_with_rgba(pixel_buffer, pixel_index, rgba);
+    }
+
+    // Alpha is 0, meaning this rgb value is completely transparent.
+    if rgba[3] == 0 {
+      return Ok(());
+    }
+
+    let adjusted_pixel_index = pixel_index * 4;
+    let pixel_buffer_length = pixel_buffer.len();
+
+    if pixel_buffer_length < adjusted_pixel_index + 4 {
+      return Err(anyhow!(
+        "Attempted to index out of bounds of the pixel buffer. buffer_length: {}, max_index: {}",
+        pixel_buffer_length,
+        adjusted_pixel_index + 4
+      ));
+    }
+
+    let pixel_color = &mut pixel_buffer[(adjusted_pixel_index)..(adjusted_pixel_index + 3)];
+    let alpha = rgba[3] as u16;
+
+    for index in 0..3 {
+      let top_color = rgba[index] as u16;
+      let bottom_color = pixel_color[index] as u16;
+
+      pixel_color[index] = match blend_mode {
+        BlendMode::Additive => (bottom_color + ((top_color * alpha) / 255)).min(255) as u8,
+        // The product is mixed with the existing color by the alpha, so a translucent color darkens less.
+        BlendMode::Multiply => {
+          let product = (bottom_color * top_color) / 255;
+
+          (((bottom_color * (255 - alpha)) + (product * alpha)) / 255) as u8
+        }
+        BlendMode::Normal => unreachable!("Normal blending is handled by draw_at_pixel_with_rgba."),
+      };
+    }
+
+    Ok(())
+  }
+
   /// Draws at the pixel in the frame buffer.
   ///
   /// This method allows for easier calculating for the index into this buffer.
@@ -416,4 +1341,851 @@ mod tests {
       assert_eq!(pixel_buffer, expected_color);
     }
   }
+
+  mod clip_logic {
+    use super::*;
+
+    const DIMENSIONS: LogicalSize<u32> = LogicalSize::new(4, 4);
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+
+    fn white_pixels(renderer: &Renderer) -> Vec<(u32, u32)> {
+      renderer
+        .frame()
+        .chunks_exact(4)
+        .enumerate()
+        .filter(|(_, pixel)| pixel == &WHITE)
+        .map(|(index, _)| {
+          (
+            index as u32 % DIMENSIONS.width,
+            index as u32 / DIMENSIONS.width,
+          )
+        })
+        .collect()
+    }
+
+    #[test]
+    fn only_the_clipped_region_is_drawn_to() {
+      let mut renderer = Renderer::new_in_memory(DIMENSIONS);
+      renderer.set_clip(Some((LogicalPosition::new(1, 1), LogicalSize::new(2, 1))));
+
+      renderer
+        .filled_rectangle(&LogicalPosition::new(0, 0), &DIMENSIONS, WHITE)
+        .unwrap();
+
+      assert_eq!(white_pixels(&renderer), vec![(1, 1), (2, 1)]);
+    }
+
+    #[test]
+    fn removing_the_clip_draws_everywhere() {
+      let mut renderer = Renderer::new_in_memory(DIMENSIONS);
+      renderer.set_clip(Some((LogicalPosition::new(0, 0), LogicalSize::new(1, 1))));
+      renderer.set_clip(None);
+
+      renderer.apply_color(WHITE).unwrap();
+
+      assert_eq!(white_pixels(&renderer).len(), 16);
+    }
+
+    #[test]
+    fn images_are_clipped() {
+      let mut renderer = Renderer::new_in_memory(DIMENSIONS);
+      renderer.set_clip(Some((LogicalPosition::new(0, 0), LogicalSize::new(1, 4))));
+      let image = DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(2, 2, image::Rgba(WHITE)));
+
+      renderer
+        .render_image(&LogicalPosition::new(0, 0), &image, &DIMENSIONS)
+        .unwrap();
+
+      assert_eq!(white_pixels(&renderer), vec![(0, 0), (0, 1)]);
+    }
+  }
+
+  mod draw_target_logic {
+    use super::*;
+    use std::cell::Cell;
+
+    /// A target that counts how many times it was presented.
+    struct MockTarget {
+      frame: InMemoryTarget,
+      present_count: Cell<u32>,
+    }
+
+    impl DrawTarget for MockTarget {
+      fn frame(&self) -> &[u8] {
+        self.frame.frame()
+      }
+
+      fn frame_mut(&mut self) -> &mut [u8] {
+        self.frame.frame_mut()
+      }
+
+      fn dimensions(&self) -> LogicalSize<u32> {
+        self.frame.dimensions()
+      }
+
+      fn present(&self) -> anyhow::Result<()> {
+        self.present_count.set(self.present_count.get() + 1);
+
+        Ok(())
+      }
+    }
+
+    #[test]
+    fn shapes_are_drawn_to_a_mock_target() {
+      let mut renderer = Renderer::with_target(MockTarget {
+        frame: InMemoryTarget::new(LogicalSize::new(4, 3)),
+        present_count: Cell::new(0),
+      });
+      const RED: [u8; 4] = [255, 0, 0, 255];
+      const BLUE: [u8; 4] = [0, 0, 255, 255];
+      const BLACK: [u8; 4] = [0, 0, 0, 255];
+
+      assert_eq!(renderer.buffer_dimensions(), LogicalSize::new(4, 3));
+
+      renderer
+        .filled_rectangle(&LogicalPosition::new(0, 0), &LogicalSize::new(2, 2), RED)
+        .unwrap();
+      renderer
+        .line(
+          &LogicalPosition::new(0, 2),
+          &LogicalPosition::new(3, 2),
+          BLUE,
+        )
+        .unwrap();
+      renderer.complete_render().unwrap();
+
+      let expected_frame = [
+        [RED, RED, BLACK, BLACK],
+        [RED, RED, BLACK, BLACK],
+        [BLUE, BLUE, BLUE, BLUE],
+      ]
+      .concat()
+      .concat();
+
+      assert_eq!(renderer.frame(), expected_frame);
+    }
+
+    #[test]
+    fn replacing_the_target_keeps_the_fonts() {
+      let assets = crate::asset_loader::Assets::load_assets();
+      let mut renderer = Renderer::new_in_memory(LogicalSize::new(4, 3));
+      renderer
+        .load_font_from_bytes(assets.get_font("gadugi").unwrap(), "gadugi")
+        .unwrap();
+
+      renderer.set_target(InMemoryTarget::new(LogicalSize::new(5, 2)));
+
+      assert_eq!(renderer.fonts().len(), 1);
+      assert_eq!(renderer.buffer_dimensions(), LogicalSize::new(5, 2));
+      assert_eq!(renderer.frame().len(), 5 * 2 * 4);
+    }
+  }
+
+  mod letterbox_logic {
+    use super::*;
+
+    const BUFFER_DIMENSIONS: LogicalSize<u32> = LogicalSize::new(250, 400);
+
+    #[test]
+    fn wide_window_has_bars_on_the_sides() {
+      let (position, size) =
+        Renderer::letterbox_rect(PhysicalSize::new(1920, 1080), BUFFER_DIMENSIONS);
+
+      assert_eq!(size, PhysicalSize::new(675, 1080));
+      assert_eq!(position, PhysicalPosition::new(622, 0));
+    }
+
+    #[test]
+    fn tall_window_has_bars_above_and_below() {
+      let (position, size) =
+        Renderer::letterbox_rect(PhysicalSize::new(500, 1000), BUFFER_DIMENSIONS);
+
+      assert_eq!(size, PhysicalSize::new(500, 800));
+      assert_eq!(position, PhysicalPosition::new(0, 100));
+    }
+
+    #[test]
+    fn window_positions_map_into_the_buffer() {
+      let mut renderer = Renderer::new_in_memory(BUFFER_DIMENSIONS);
+      renderer
+        .resize_surface(PhysicalSize::new(700, 800))
+        .unwrap();
+
+      assert_eq!(
+        renderer.window_to_buffer_position(PhysicalPosition::new(100.0, 0.0)),
+        Some(LogicalPosition::new(0, 0))
+      );
+      assert_eq!(
+        renderer.window_to_buffer_position(PhysicalPosition::new(351.0, 401.0)),
+        Some(LogicalPosition::new(125, 200))
+      );
+      assert_eq!(
+        renderer.window_to_buffer_position(PhysicalPosition::new(99.0, 400.0)),
+        None
+      );
+      assert_eq!(
+        renderer.window_to_buffer_position(PhysicalPosition::new(600.0, 400.0)),
+        None
+      );
+    }
+
+    #[test]
+    fn resizing_updates_the_viewport() {
+      let mut renderer = Renderer::new_in_memory(BUFFER_DIMENSIONS);
+
+      renderer
+        .resize_surface(PhysicalSize::new(750, 1200))
+        .unwrap();
+
+      assert_eq!(
+        renderer.viewport(),
+        (PhysicalPosition::new(0, 0), PhysicalSize::new(750, 1200))
+      );
+    }
+
+    #[test]
+    fn viewport_is_where_the_target_shows_the_frame() {
+      /// A target that only scales its frame by whole numbers, leaving wider bars than the letterbox rect.
+      struct IntegerScaledTarget(InMemoryTarget);
+
+      impl DrawTarget for IntegerScaledTarget {
+        fn frame(&self) -> &[u8] {
+          self.0.frame()
+        }
+
+        fn frame_mut(&mut self) -> &mut [u8] {
+          self.0.frame_mut()
+        }
+
+        fn dimensions(&self) -> LogicalSize<u32> {
+          self.0.dimensions()
+        }
+
+        fn viewport(&self, window_dimensions: PhysicalSize<u32>) -> Viewport {
+          let dimensions = self.dimensions();
+          let scale = (window_dimensions.height / dimensions.height).max(1);
+          let (width, height) = (dimensions.width * scale, dimensions.height * scale);
+
+          (
+            PhysicalPosition::new(
+              (window_dimensions.width - width) / 2,
+              (window_dimensions.height - height) / 2,
+            ),
+            PhysicalSize::new(width, height),
+          )
+        }
+      }
+
+      let mut renderer =
+        Renderer::with_target(IntegerScaledTarget(InMemoryTarget::new(BUFFER_DIMENSIONS)));
+
+      renderer
+        .resize_surface(PhysicalSize::new(1000, 1000))
+        .unwrap();
+
+      assert_eq!(
+        renderer.viewport(),
+        (PhysicalPosition::new(250, 100), PhysicalSize::new(500, 800))
+      );
+      assert_eq!(
+        renderer.window_to_buffer_position(PhysicalPosition::new(200.0, 500.0)),
+        None
+      );
+      assert_eq!(
+        renderer.window_to_buffer_position(PhysicalPosition::new(250.0, 100.0)),
+        Some(LogicalPosition::new(0, 0))
+      );
+    }
+  }
+
+  mod offset_logic {
+    use super::*;
+
+    #[test]
+    fn offset_moves_pixels_and_fills_the_gap_with_black() {
+      let mut renderer = Renderer::new_in_memory(LogicalSize::new(3, 1));
+      renderer
+        .frame_mut()
+        .copy_from_slice(&[10, 10, 10, 255, 20, 20, 20, 255, 30, 30, 30, 255]);
+
+      renderer.offset_frame(1, 0);
+
+      assert_eq!(
+        renderer.frame(),
+        &[0, 0, 0, 255, 10, 10, 10, 255, 20, 20, 20, 255]
+      );
+    }
+  }
+
+  mod filled_rectangle_logic {
+    use super::*;
+
+    const DIMENSIONS: LogicalSize<u32> = LogicalSize::new(16, 12);
+    const POSITION: LogicalPosition<u32> = LogicalPosition::new(3, 2);
+    const SIZE: LogicalSize<u32> = LogicalSize::new(9, 7);
+
+    /// Returns a renderer with a pattern drawn over it, so any skipped or extra pixels stand out.
+    fn patterned_renderer() -> Renderer {
+      let mut renderer = Renderer::new_in_memory(DIMENSIONS);
+
+      for (index, byte) in renderer.frame_mut().iter_mut().enumerate() {
+        if index % 4 != 3 {
+          *byte = (index * 7 % 251) as u8;
+        }
+      }
+
+      renderer
+    }
+
+    #[test]
+    fn opaque_fill_matches_the_per_pixel_fill() {
+      let color = [0x12, 0x34, 0x56, 255];
+      let mut fast_renderer = patterned_renderer();
+      let mut per_pixel_renderer = patterned_renderer();
+
+      fast_renderer
+        .filled_rectangle(&POSITION, &SIZE, color)
+        .unwrap();
+      per_pixel_renderer
+        .filled_rectangle_per_pixel(&POSITION, &SIZE, color)
+        .unwrap();
+
+      assert_eq!(fast_renderer.frame(), per_pixel_renderer.frame());
+    }
+
+    #[test]
+    fn clipped_opaque_fill_matches_the_per_pixel_fill() {
+      let color = [0x12, 0x34, 0x56, 255];
+      let clip = Some((LogicalPosition::new(5, 0), LogicalSize::new(4, 5)));
+      let mut fast_renderer = patterned_renderer();
+      let mut per_pixel_renderer = patterned_renderer();
+      fast_renderer.set_clip(clip);
+      per_pixel_renderer.set_clip(clip);
+
+      fast_renderer
+        .filled_rectangle(&POSITION, &SIZE, color)
+        .unwrap();
+      per_pixel_renderer
+        .filled_rectangle_per_pixel(&POSITION, &SIZE, color)
+        .unwrap();
+
+      assert_eq!(fast_renderer.frame(), per_pixel_renderer.frame());
+    }
+  }
+
+  mod blend_logic {
+    use super::*;
+
+    const MID_GRAY: [u8; 4] = [128, 128, 128, 255];
+
+    /// Draws a mid-gray pixel over a mid-gray pixel with the blend mode, returning the resulting pixel.
+    fn blend_mid_grays(blend_mode: BlendMode) -> Vec<u8> {
+      let mut pixel_buffer = MID_GRAY.to_vec();
+
+      Renderer::draw_at_pixel_with_blend(&mut pixel_buffer, 0, &MID_GRAY, blend_mode).unwrap();
+
+      pixel_buffer
+    }
+
+    #[test]
+    fn normal_replaces_the_color() {
+      assert_eq!(blend_mid_grays(BlendMode::Normal), vec![128, 128, 128, 255]);
+    }
+
+    #[test]
+    fn additive_clamps_at_full_brightness() {
+      assert_eq!(
+        blend_mid_grays(BlendMode::Additive),
+        vec![255, 255, 255, 255]
+      );
+    }
+
+    #[test]
+    fn multiply_darkens_the_color() {
+      assert_eq!(blend_mid_grays(BlendMode::Multiply), vec![64, 64, 64, 255]);
+    }
+
+    #[test]
+    fn shapes_use_the_renderers_blend_mode() {
+      let mut renderer = Renderer::new_in_memory(LogicalSize::new(2, 2));
+      renderer.set_color([128, 128, 128]).unwrap();
+      renderer.set_blend_mode(BlendMode::Additive);
+
+      renderer
+        .filled_rectangle(
+          &LogicalPosition::new(0, 0),
+          &LogicalSize::new(1, 1),
+          Color::rgb(100, 100, 100),
+        )
+        .unwrap();
+
+      assert_eq!(renderer.frame()[0..4], [228, 228, 228, 255]);
+      assert_eq!(renderer.frame()[4..8], [128, 128, 128, 255]);
+    }
+  }
+
+  mod bounding_rectangle_logic {
+    use super::*;
+
+    const DIMENSIONS: LogicalSize<u32> = LogicalSize::new(6, 6);
+    const HALF_WHITE: [u8; 4] = [255, 255, 255, 0x80];
+
+    /// Returns the red channel of every pixel, row by row.
+    fn red_channels(renderer: &Renderer) -> Vec<u8> {
+      renderer
+        .frame()
+        .chunks_exact(4)
+        .map(|pixel| pixel[0])
+        .collect()
+    }
+
+    #[test]
+    fn every_outline_pixel_is_drawn_once() {
+      let mut renderer = Renderer::new_in_memory(DIMENSIONS);
+
+      renderer
+        .bounding_rectangle(
+          &LogicalPosition::new(1, 1),
+          &LogicalPosition::new(4, 3),
+          HALF_WHITE,
+        )
+        .unwrap();
+
+      let red_channels = red_channels(&renderer);
+      let outline_value = red_channels[1 + DIMENSIONS.width as usize];
+
+      assert_ne!(outline_value, 0);
+
+      for y in 0..DIMENSIONS.height {
+        for x in 0..DIMENSIONS.width {
+          let on_outline =
+            (1..=4).contains(&x) && (1..=3).contains(&y) && (x == 1 || x == 4 || y == 1 || y == 3);
+          let expected_value = if on_outline { outline_value } else { 0 };
+
+          assert_eq!(
+            red_channels[(x + y * DIMENSIONS.width) as usize],
+            expected_value,
+            "Pixel at ({x}, {y})"
+          );
+        }
+      }
+    }
+
+    #[test]
+    fn swapped_corners_draw_the_same_outline() {
+      let mut renderer = Renderer::new_in_memory(DIMENSIONS);
+      let mut swapped_renderer = Renderer::new_in_memory(DIMENSIONS);
+
+      renderer
+        .bounding_rectangle(
+          &LogicalPosition::new(0, 1),
+          &LogicalPosition::new(3, 5),
+          HALF_WHITE,
+        )
+        .unwrap();
+      swapped_renderer
+        .bounding_rectangle(
+          &LogicalPosition::new(3, 5),
+          &LogicalPosition::new(0, 1),
+          HALF_WHITE,
+        )
+        .unwrap();
+
+      assert_eq!(renderer.frame(), swapped_renderer.frame());
+    }
+
+    #[test]
+    fn thin_rectangles_have_no_doubled_pixels() {
+      let mut renderer = Renderer::new_in_memory(DIMENSIONS);
+
+      renderer
+        .bounding_rectangle(
+          &LogicalPosition::new(2, 0),
+          &LogicalPosition::new(2, 5),
+          HALF_WHITE,
+        )
+        .unwrap();
+
+      let red_channels = red_channels(&renderer);
+      let column: Vec<u8> = (0..DIMENSIONS.height)
+        .map(|y| red_channels[(2 + y * DIMENSIONS.width) as usize])
+        .collect();
+
+      assert!(column
+        .iter()
+        .all(|value| *value == column[0] && *value != 0));
+    }
+  }
+
+  mod line_logic {
+    use super::*;
+
+    const DIMENSIONS: LogicalSize<u32> = LogicalSize::new(8, 8);
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+
+    fn filled_pixels(renderer: &Renderer) -> Vec<(u32, u32)> {
+      renderer
+        .frame()
+        .chunks_exact(4)
+        .enumerate()
+        .filter(|(_, pixel)| pixel[0] == 255)
+        .map(|(index, _)| {
+          (
+            index as u32 % DIMENSIONS.width,
+            index as u32 / DIMENSIONS.width,
+          )
+        })
+        .collect()
+    }
+
+    #[test]
+    fn diagonal_line_includes_both_ends() {
+      let mut renderer = Renderer::new_in_memory(DIMENSIONS);
+
+      renderer
+        .line(
+          &LogicalPosition::new(1, 1),
+          &LogicalPosition::new(3, 3),
+          WHITE,
+        )
+        .unwrap();
+
+      assert_eq!(filled_pixels(&renderer), vec![(1, 1), (2, 2), (3, 3)]);
+    }
+
+    #[test]
+    fn line_outside_the_buffer_is_clipped() {
+      let mut renderer = Renderer::new_in_memory(DIMENSIONS);
+
+      renderer
+        .line(
+          &LogicalPosition::new(-4, 0),
+          &LogicalPosition::new(1, 0),
+          WHITE,
+        )
+        .unwrap();
+
+      assert_eq!(filled_pixels(&renderer), vec![(0, 0), (1, 0)]);
+    }
+  }
+
+  mod arrow_logic {
+    use super::*;
+
+    #[test]
+    fn up_arrow_segments_end_at_the_tip() {
+      let tip = LogicalPosition::new(10, 10);
+
+      let segments = Renderer::arrow_segments(&tip, 6, Direction::Up);
+
+      let expected_segments = [
+        (LogicalPosition::new(10, 16), tip),
+        (LogicalPosition::new(7, 13), tip),
+        (LogicalPosition::new(13, 13), tip),
+      ];
+      assert_eq!(segments, expected_segments);
+    }
+
+    #[test]
+    fn left_arrow_wings_are_offset_vertically() {
+      let tip = LogicalPosition::new(10, 10);
+
+      let segments = Renderer::arrow_segments(&tip, 6, Direction::Left);
+
+      let expected_segments = [
+        (LogicalPosition::new(16, 10), tip),
+        (LogicalPosition::new(13, 7), tip),
+        (LogicalPosition::new(13, 13), tip),
+      ];
+      assert_eq!(segments, expected_segments);
+    }
+  }
+
+  mod blur_logic {
+    use super::*;
+
+    #[test]
+    fn sharp_edge_becomes_a_gradient() {
+      let buffer_dimensions = LogicalSize::new(4, 1);
+      #[rustfmt::skip]
+      let mut pixel_buffer = [
+        0x00, 0x00, 0x00, 0xFF,   0x00, 0x00, 0x00, 0xFF,
+        0xFF, 0xFF, 0xFF, 0xFF,   0xFF, 0xFF, 0xFF, 0xFF,
+      ];
+
+      Renderer::box_blur_buffer_region(
+        &mut pixel_buffer,
+        &buffer_dimensions,
+        &LogicalPosition::new(0, 0),
+        &buffer_dimensions,
+        1,
+      );
+
+      let red_channel: Vec<u8> = pixel_buffer.chunks_exact(4).map(|pixel| pixel[0]).collect();
+
+      // The edges only average the pixels within the region.
+      assert_eq!(red_channel, [0x00, 0x55, 0xAA, 0xFF]);
+      assert!(pixel_buffer.chunks_exact(4).all(|pixel| pixel[3] == 0xFF));
+    }
+
+    #[test]
+    fn pixels_outside_the_region_are_untouched() {
+      let buffer_dimensions = LogicalSize::new(3, 1);
+      #[rustfmt::skip]
+      let mut pixel_buffer = [
+        0x00, 0x00, 0x00, 0xFF,   0xFF, 0xFF, 0xFF, 0xFF,   0x00, 0x00, 0x00, 0xFF,
+      ];
+
+      Renderer::box_blur_buffer_region(
+        &mut pixel_buffer,
+        &buffer_dimensions,
+        &LogicalPosition::new(1, 0),
+        &LogicalSize::new(2, 1),
+        1,
+      );
+
+      assert_eq!(&pixel_buffer[0..4], &[0x00, 0x00, 0x00, 0xFF]);
+      assert_eq!(&pixel_buffer[4..8], &[0x7F, 0x7F, 0x7F, 0xFF]);
+      assert_eq!(&pixel_buffer[8..12], &[0x7F, 0x7F, 0x7F, 0xFF]);
+    }
+  }
+
+  mod image_logic {
+    use super::*;
+    use image::{GrayImage, Luma, Rgba, RgbaImage};
+
+    #[test]
+    fn rgba_image_is_drawn_at_offset() {
+      let buffer_dimensions = LogicalSize::new(2, 2);
+      let mut pixel_buffer = [0x00, 0x00, 0x00, 0xFF].repeat(4);
+      let image =
+        DynamicImage::ImageRgba8(RgbaImage::from_pixel(1, 1, Rgba([0x11, 0x22, 0x33, 0xFF])));
+
+      Renderer::draw_image_to_buffer(
+        &mut pixel_buffer,
+        &LogicalPosition::new(1, 1),
+        &image,
+        &buffer_dimensions,
+      )
+      .unwrap();
+
+      assert_eq!(&pixel_buffer[12..], &[0x11, 0x22, 0x33, 0xFF]);
+      assert_eq!(&pixel_buffer[..12], [0x00, 0x00, 0x00, 0xFF].repeat(3));
+    }
+
+    #[test]
+    fn grayscale_image_is_drawn() {
+      let buffer_dimensions = LogicalSize::new(2, 2);
+      let mut pixel_buffer = [0x00, 0x00, 0x00, 0xFF].repeat(4);
+      let image = DynamicImage::ImageLuma8(GrayImage::from_pixel(2, 2, Luma([0x80])));
+
+      assert!(image.as_rgba8().is_none());
+
+      Renderer::draw_image_to_buffer(
+        &mut pixel_buffer,
+        &LogicalPosition::new(0, 0),
+        &image,
+        &buffer_dimensions,
+      )
+      .unwrap();
+
+      assert_eq!(pixel_buffer, [0x80, 0x80, 0x80, 0xFF].repeat(4));
+    }
+
+    /// Returns a 2x2 image with a different color in each pixel.
+    fn quadrant_image() -> DynamicImage {
+      DynamicImage::ImageRgba8(RgbaImage::from_fn(2, 2, |x, y| {
+        Rgba([(x * 0x80) as u8, (y * 0x80) as u8, 0x40, 0xFF])
+      }))
+    }
+
+    #[test]
+    fn doubled_image_covers_four_times_the_pixels() {
+      let mut renderer = Renderer::new_in_memory(LogicalSize::new(5, 5));
+
+      renderer
+        .render_image_scaled(&LogicalPosition::new(0, 0), &quadrant_image(), 2.0)
+        .unwrap();
+
+      for (index, pixel) in renderer.frame().chunks_exact(4).enumerate() {
+        let (x, y) = (index as u32 % 5, index as u32 / 5);
+
+        let expected_pixel = if x < 4 && y < 4 {
+          [((x / 2) * 0x80) as u8, ((y / 2) * 0x80) as u8, 0x40, 0xFF]
+        } else {
+          [0, 0, 0, 0xFF]
+        };
+
+        assert_eq!(pixel, expected_pixel, "Pixel at ({}, {})", x, y);
+      }
+    }
+
+    #[test]
+    fn scaled_image_past_the_buffer_is_clipped() {
+      let mut renderer = Renderer::new_in_memory(LogicalSize::new(2, 2));
+
+      renderer
+        .render_image_scaled(&LogicalPosition::new(1, 1), &quadrant_image(), 2.0)
+        .unwrap();
+
+      assert_eq!(&renderer.frame()[12..], &[0, 0, 0x40, 0xFF]);
+    }
+
+    #[test]
+    fn screenshot_matches_the_frame_buffer() {
+      let buffer_dimensions = LogicalSize::new(3, 2);
+      let mut renderer = Renderer::new_in_memory(buffer_dimensions);
+      renderer
+        .filled_rectangle(
+          &LogicalPosition::new(0, 0),
+          &buffer_dimensions,
+          [0x11, 0x22, 0x33, 0xFF],
+        )
+        .unwrap();
+      let screenshot_path = std::env::temp_dir().join(format!(
+        "rustris_screenshot_test_{}.png",
+        std::process::id()
+      ));
+
+      renderer.save_screenshot(&screenshot_path).unwrap();
+      let screenshot = image::open(&screenshot_path).unwrap().to_rgba8();
+      std::fs::remove_file(&screenshot_path).unwrap();
+
+      assert_eq!(screenshot.dimensions(), (3, 2));
+      assert!(screenshot
+        .pixels()
+        .all(|pixel| pixel.0 == [0x11, 0x22, 0x33, 0xFF]));
+      assert_eq!(renderer.frame(), [0x11, 0x22, 0x33, 0xFF].repeat(6));
+    }
+
+    #[test]
+    fn invalid_scale_errors() {
+      let mut renderer = Renderer::new_in_memory(LogicalSize::new(2, 2));
+      let image = quadrant_image();
+
+      for scale in [0.0, -1.0, f32::NAN] {
+        assert!(renderer
+          .render_image_scaled(&LogicalPosition::new(0, 0), &image, scale)
+          .is_err());
+      }
+    }
+  }
+
+  mod downscale_logic {
+    use super::*;
+
+    #[test]
+    fn uniform_buffer_keeps_its_color() {
+      let buffer_dimensions = LogicalSize::new(4, 6);
+      let pixel_buffer = [0x12, 0x34, 0x56, 0xFF].repeat(4 * 6);
+
+      let (downscaled_buffer, downscaled_dimensions) =
+        Renderer::downscale_buffer(&pixel_buffer, &buffer_dimensions, 2);
+
+      assert_eq!(downscaled_dimensions, LogicalSize::new(2, 3));
+      assert_eq!(downscaled_buffer, [0x12, 0x34, 0x56, 0xFF].repeat(2 * 3));
+    }
+
+    #[test]
+    fn blocks_are_averaged() {
+      let buffer_dimensions = LogicalSize::new(2, 2);
+      #[rustfmt::skip]
+      let pixel_buffer = [
+        0x00, 0x00, 0x00, 0xFF,   0xFF, 0xFF, 0xFF, 0xFF,
+        0x00, 0x00, 0x00, 0xFF,   0xFF, 0xFF, 0xFF, 0xFF,
+      ];
+
+      let (downscaled_buffer, downscaled_dimensions) =
+        Renderer::downscale_buffer(&pixel_buffer, &buffer_dimensions, 2);
+
+      assert_eq!(downscaled_dimensions, LogicalSize::new(1, 1));
+      assert_eq!(downscaled_buffer, [0x7F, 0x7F, 0x7F, 0xFF]);
+    }
+
+    #[test]
+    fn zero_factor_returns_the_same_buffer() {
+      let buffer_dimensions = LogicalSize::new(2, 1);
+      let pixel_buffer = [0x00, 0x11, 0x22, 0xFF, 0x33, 0x44, 0x55, 0xFF];
+
+      let (downscaled_buffer, downscaled_dimensions) =
+        Renderer::downscale_buffer(&pixel_buffer, &buffer_dimensions, 0);
+
+      assert_eq!(downscaled_dimensions, buffer_dimensions);
+      assert_eq!(downscaled_buffer, pixel_buffer);
+    }
+  }
+
+  mod background_gradient_logic {
+    use super::*;
+
+    const BUFFER_DIMENSIONS: LogicalSize<u32> = LogicalSize::new(250, 400);
+
+    #[test]
+    fn level_one_keeps_the_base_gradient() {
+      let top_left = Renderer::background_gradient_color(0, 0, &BUFFER_DIMENSIONS, 1);
+      let middle = Renderer::background_gradient_color(125, 200, &BUFFER_DIMENSIONS, 1);
+
+      assert_eq!(top_left, [0, 0, 0]);
+      assert_eq!(middle, [128, 0, 128]);
+    }
+
+    #[test]
+    fn higher_level_changes_the_gradient() {
+      let base_color = Renderer::background_gradient_color(125, 200, &BUFFER_DIMENSIONS, 1);
+      let intense_color = Renderer::background_gradient_color(125, 200, &BUFFER_DIMENSIONS, 10);
+
+      assert_ne!(base_color, intense_color);
+      assert!(intense_color[0] > base_color[0]);
+      assert!(intense_color[2] < base_color[2]);
+    }
+
+    #[test]
+    fn gradient_stops_intensifying_past_the_max_level() {
+      let max_level = Renderer::MAX_BACKGROUND_LEVEL;
+
+      let max_color = Renderer::background_gradient_color(125, 200, &BUFFER_DIMENSIONS, max_level);
+      let past_max_color =
+        Renderer::background_gradient_color(125, 200, &BUFFER_DIMENSIONS, max_level + 10);
+
+      assert_eq!(max_color, past_max_color);
+    }
+  }
+
+  mod font_logic {
+    use super::*;
+    use crate::asset_loader::Assets;
+
+    #[test]
+    fn fonts_are_found_by_name_at_their_load_index() {
+      let assets = Assets::load_assets();
+      let font_bytes = assets.get_font("gadugi").unwrap();
+      let mut renderer = Renderer::new_in_memory(LogicalSize::new(4, 4));
+
+      renderer.load_font_from_bytes(font_bytes, "hud").unwrap();
+      renderer.load_font_from_bytes(font_bytes, "title").unwrap();
+
+      assert_eq!(renderer.font_index("hud"), Some(0));
+      assert_eq!(renderer.font_index("title"), Some(1));
+      assert_eq!(renderer.font_index("unknown"), None);
+
+      for (index, font_name) in ["hud", "title"].into_iter().enumerate() {
+        let font = renderer.get_font(font_name).unwrap();
+
+        assert!(std::ptr::eq(font, &renderer.fonts()[index]));
+        assert_eq!(renderer.fonts_with_names()[index].0, font_name);
+      }
+    }
+
+    #[test]
+    fn duplicate_font_names_are_rejected() {
+      let assets = Assets::load_assets();
+      let font_bytes = assets.get_font("gadugi").unwrap();
+      let mut renderer = Renderer::new_in_memory(LogicalSize::new(4, 4));
+
+      renderer.load_font_from_bytes(font_bytes, "gadugi").unwrap();
+
+      assert!(renderer.load_font_from_bytes(font_bytes, "gadugi").is_err());
+      assert_eq!(renderer.fonts().len(), 1);
+    }
+  }
 }
@@ -1,71 +1,135 @@
 use crate::asset_loader::Assets;
-use crate::game::{actions::*, game_settings::GameSettings, world_data::WorldData};
+use crate::game::game_settings::{GameSettings, PresentMode};
+use crate::game::leaderboard::Leaderboard;
+use crate::game::{actions::*, world_data::WorldData};
+use crate::game::{scoring::Scoring, world_state::WorldState};
+use crate::general_data::frame_rate::FrameRate;
+use crate::general_data::rng::Rng;
+use crate::general_data::timer::Timer;
 use crate::general_data::winit_traits::*;
+use crate::renderer::color::Color;
+use crate::renderer::draw_target::{InMemoryTarget, PixelsTarget};
 use crate::renderer::fonts::TextBox;
 use crate::renderer::Renderer;
-use anyhow::anyhow;
 use game_loop::{game_loop, GameLoop, Time, TimeTrait};
-use pixels::{Pixels, SurfaceTexture};
+use pixels::{wgpu, wgpu::SurfaceError, Pixels, PixelsBuilder, SurfaceTexture};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
-use winit::window::{Window, WindowBuilder};
-use winit::{dpi::*, event::Event, event_loop::EventLoop, keyboard::KeyCode};
+use winit::event::{ElementState, Event, WindowEvent};
+use winit::keyboard::{Key, KeyCode, NamedKey};
+use winit::window::{Fullscreen, Window, WindowBuilder};
+use winit::{dpi::*, event_loop::EventLoop};
 use winit_input_helper::WinitInputHelper;
 
+/// The dimensions of the buffer at the default [`RenderResolution`](crate::game::resolution::RenderResolution).
 pub const RENDERED_WINDOW_DIMENSIONS: LogicalSize<u32> = LogicalSize::new(250, 400);
+/// The environment variable a seed can be set through, making the order pieces are handed out in reproducible.
+pub const SEED_ENV_VAR: &str = "RUSTRIS_SEED";
+/// The environment variable a directory of extra images can be set through, loaded over the embedded images.
+pub const ASSETS_DIR_ENV_VAR: &str = "RUSTRIS_ASSETS_DIR";
+/// How many frames in a row can fail with a fatal error before the program exits.
+const MAX_FATAL_RENDER_ERRORS: u32 = 3;
+/// The index winit_input_helper uses for the left mouse button.
+const LEFT_MOUSE_BUTTON: usize = 0;
+/// The size of the text in the debug overlay.
+const DEBUG_OVERLAY_TEXT_SIZE: f32 = 8.0;
+/// The title of the window outside of a game.
+const WINDOW_TITLE: &str = "Rustris";
+/// The least amount of time between changes to the window title.
+const WINDOW_TITLE_UPDATE_INTERVAL: Duration = Duration::from_millis(500);
 
 pub struct RustrisConfig {
   world_data: WorldData,
   player_action: Option<PlayerAction>,
   renderer: Renderer,
   text_boxes: HashMap<&'static str, TextBox>,
-  settings: GameSettings,
   input: WinitInputHelper,
   assets: Assets,
+  render_errors: RenderErrors,
+  frame_rate: FrameRate,
+  /// The text showing the frame and update rate in the top left, only drawn while this is Some.
+  debug_overlay: Option<TextBox>,
+  /// The present mode the window's surface was last created with.
+  present_mode: PresentMode,
+  /// The title last given to the window.
+  window_title: String,
+  /// Runs from the last time the window title changed, holding off the next change until it's finished.
+  window_title_timer: Timer,
+}
+
+/// What the game loop does after the world has been updated.
+#[derive(Debug, PartialEq, Eq)]
+enum UpdateOutcome {
+  Continue,
+  /// The player asked to close the program from a menu.
+  ExitRequested,
+  /// The world failed to update, and can't continue.
+  Failed,
+}
+
+/// Keeps track of frames that failed to render, deciding when the program can't recover and has to exit.
+#[derive(Debug, Default)]
+struct RenderErrors {
+  consecutive_fatal_errors: u32,
 }
 
 impl RustrisConfig {
   pub fn new() -> anyhow::Result<(Self, EventLoop<()>, Window)> {
     let event_loop = EventLoop::new()?;
 
-    let primary_monitor_dimensions = get_primary_monitor_dimensions(&event_loop);
-    let window_scale =
-      (primary_monitor_dimensions.height / RENDERED_WINDOW_DIMENSIONS.height).max(1);
-    let scaled_window_dimensions = RENDERED_WINDOW_DIMENSIONS.multiply(window_scale);
+    let settings = GameSettings::initialize()?;
+    let render_dimensions = settings.render_resolution().dimensions();
+
+    let primary_monitor_dimensions = get_primary_monitor_dimensions(&event_loop, render_dimensions);
+    let window_scale = (primary_monitor_dimensions.height / render_dimensions.height).max(1);
+    let scaled_window_dimensions = render_dimensions.multiply(window_scale);
 
+    log::info!("render resolution: {:?}", render_dimensions);
     log::info!("window scale: {:?}", window_scale);
 
+    let fullscreen = settings
+      .fullscreen()
+      .then_some(Fullscreen::Borderless(None));
+
     let window = WindowBuilder::new()
-      .with_title("Rustris")
+      .with_title(WINDOW_TITLE)
+      .with_fullscreen(fullscreen)
       .with_inner_size(scaled_window_dimensions)
-      .with_min_inner_size(RENDERED_WINDOW_DIMENSIONS)
+      .with_min_inner_size(render_dimensions)
       .build(&event_loop)?;
-    let window_size = window.inner_size();
+    let present_mode = settings.present_mode();
+    let pixels = create_pixels(&window, render_dimensions, present_mode)?;
 
-    let surface = SurfaceTexture::new(window_size.width, window_size.height, &window);
-    let pixels = Pixels::new(
-      RENDERED_WINDOW_DIMENSIONS.width,
-      RENDERED_WINDOW_DIMENSIONS.height,
-      surface,
-    )?;
-
-    let settings = GameSettings::initialize()?;
     let input = WinitInputHelper::new();
 
-    let game = WorldData::new();
-    let renderer = Renderer::new(pixels);
+    let seed = game_seed();
+    log::info!("game seed: {}", seed);
 
-    let assets = Assets::load_assets();
+    let mut game = WorldData::new(settings, seed);
+    game.set_leaderboard(Leaderboard::load_from_disk());
+    let renderer = Renderer::new(pixels, render_dimensions);
+
+    let mut assets = Assets::preload_all();
+
+    if let Some(image_dir) = image_dir() {
+      load_external_images(&mut assets, &image_dir);
+    }
 
     let mut rustris_config = Self {
       world_data: game,
       player_action: None,
       renderer,
       text_boxes: HashMap::with_capacity(5),
-      settings,
       input,
       assets,
+      render_errors: RenderErrors::default(),
+      frame_rate: FrameRate::default(),
+      debug_overlay: None,
+      present_mode,
+      window_title: WINDOW_TITLE.to_string(),
+      window_title_timer: Timer::new(WINDOW_TITLE_UPDATE_INTERVAL),
     };
 
     rustris_config.load_fonts()?;
@@ -101,7 +165,7 @@ impl RustrisConfig {
 
   pub fn run(self, event_loop: EventLoop<()>, window: Window) -> anyhow::Result<()> {
     let window = Arc::new(window);
-    let fps = self.settings.fps();
+    let fps = self.world_data.settings().fps();
 
     game_loop(
       event_loop,
@@ -118,71 +182,242 @@ impl RustrisConfig {
   }
 
   fn update_game(game_loop: &mut GameLoop<Self, Time, Arc<Window>>) {
-    if let Err(error) = game_loop
-      .game
-      .world_data
-      .update_world(game_loop.game.player_action.clone())
-    {
-      log::error!("An error occurred when updating the world: {:?}", error);
+    let player_action = game_loop.game.player_action.clone();
+
+    match update_world(&mut game_loop.game.world_data, player_action) {
+      UpdateOutcome::Continue => (),
+      UpdateOutcome::ExitRequested => {
+        if let Err(error) = game_loop.game.world_data.settings().save_to_disk() {
+          log::error!("Failed to save the settings: `{:?}`", error);
+        }
+
+        game_loop.exit();
+
+        return;
+      }
+      UpdateOutcome::Failed => {
+        game_loop.exit();
+
+        return;
+      }
+    }
+
+    // Fullscreen can also be switched from the settings menu, which only changes the setting.
+    let fullscreen = game_loop.game.world_data.settings().fullscreen();
+
+    if fullscreen != game_loop.window.fullscreen().is_some() {
+      game_loop
+        .window
+        .set_fullscreen(fullscreen.then_some(Fullscreen::Borderless(None)));
+    }
+
+    if game_loop.game.world_data.settings().fps() != game_loop.updates_per_second {
+      game_loop.set_updates_per_second(game_loop.game.world_data.settings().fps());
+    }
+
+    Self::update_present_mode(game_loop);
+    Self::update_window_title(game_loop);
+  }
+
+  /// Recreates the window's surface when the present mode setting has changed since it was created.
+  ///
+  /// If the surface can't be created with the new mode, the previous mode is used again.
+  fn update_present_mode(game_loop: &mut GameLoop<Self, Time, Arc<Window>>) {
+    let present_mode = game_loop.game.world_data.settings().present_mode();
+    let previous_present_mode = game_loop.game.present_mode;
+
+    if present_mode == previous_present_mode {
+      return;
+    }
+
+    log::info!("present mode: {:?}", present_mode);
+    game_loop.game.present_mode = present_mode;
+
+    let Err(error) = Self::recreate_surface(game_loop, present_mode) else {
+      return;
+    };
+
+    log::error!(
+      "Failed to switch the present mode to {:?}, keeping {:?}. `{:?}`",
+      present_mode,
+      previous_present_mode,
+      error
+    );
+
+    if let Err(error) = Self::recreate_surface(game_loop, previous_present_mode) {
+      log::error!(
+        "Failed to recreate the window's surface, exiting. `{:?}`",
+        error
+      );
 
       game_loop.exit();
+    }
+  }
+
+  fn recreate_surface(
+    game_loop: &mut GameLoop<Self, Time, Arc<Window>>,
+    present_mode: PresentMode,
+  ) -> anyhow::Result<()> {
+    let renderer = &mut game_loop.game.renderer;
+    let buffer_dimensions = renderer.buffer_dimensions();
+
+    // A window can only have one surface at a time, so the current one has to be dropped first.
+    renderer.set_target(InMemoryTarget::new(buffer_dimensions));
+
+    let pixels = create_pixels(&game_loop.window, buffer_dimensions, present_mode)?;
+    renderer.set_target(PixelsTarget::new(pixels, buffer_dimensions));
+
+    Ok(())
+  }
+
+  /// Shows the score and level in the window title during a game, and the plain title in menus.
+  ///
+  /// The title changes at most once every [`WINDOW_TITLE_UPDATE_INTERVAL`], any change in between is shown once it's passed.
+  fn update_window_title(game_loop: &mut GameLoop<Self, Time, Arc<Window>>) {
+    let game = &mut game_loop.game;
+    let window_title = match game.world_data.world_state() {
+      WorldState::Menu => WINDOW_TITLE.to_string(),
+      _ => game_window_title(game.world_data.scoring()),
+    };
 
+    if window_title == game.window_title {
       return;
     }
 
-    if game_loop.game.settings.fps() != game_loop.updates_per_second {
-      game_loop.set_updates_per_second(game_loop.game.settings.fps());
+    if game.window_title_timer.running() && !game.window_title_timer.is_finished() {
+      return;
     }
+
+    game_loop.window.set_title(&window_title);
+    game.window_title = window_title;
+    game.window_title_timer.start();
   }
 
   fn render(game_loop: &mut GameLoop<Self, Time, Arc<Window>>) {
-    if let Err(error) = game_loop.game.renderer.clear() {
-      log::error!("Failed to render to clear the frame buffer. `{:?}`", error);
+    let updates_per_second = game_loop.updates_per_second;
+    game_loop.game.frame_rate.frame_finished();
 
-      game_loop.exit();
+    match game_loop.game.render_frame(updates_per_second) {
+      Ok(()) => game_loop.game.render_errors.frame_rendered(),
+      Err(error) => {
+        log::error!("Failed to render the frame, skipping it. `{:?}`", error);
 
-      return;
+        if game_loop.game.render_errors.should_exit(&error) {
+          log::error!(
+            "Failed to render {} frames in a row, exiting.",
+            MAX_FATAL_RENDER_ERRORS
+          );
+
+          game_loop.exit();
+
+          return;
+        }
+      }
     }
 
-    if let Err(error) = game_loop
-      .game
-      .world_data
-      .render(&game_loop.game.assets, &mut game_loop.game.renderer)
+    let settings = game_loop.game.world_data.settings();
+    let frame_time = Time::now().sub(&game_loop.current_instant());
+
+    // Sleep the main thread to limit drawing to the fixed time step.
+    // https://github.com/parasyte/pixels/issues/174
+    if let Some(sleep_duration) =
+      frame_sleep_duration(settings.present_mode(), settings.fps(), frame_time)
     {
-      log::error!("Failed to render the game world: `{:?}`", error);
+      std::thread::sleep(sleep_duration);
     }
+  }
 
-    if let Err(error) = game_loop.game.renderer.complete_render() {
-      log::error!("Failed to render to the frame buffer. '{:?}'", error);
+  /// Draws the world to the frame buffer, along with the debug overlay if it's shown, then presents it.
+  fn render_frame(&mut self, updates_per_second: u32) -> anyhow::Result<()> {
+    self.renderer.clear()?;
+    self.world_data.render(&self.assets, &mut self.renderer)?;
+    self.render_debug_overlay(updates_per_second)?;
 
-      game_loop.exit();
+    self.renderer.complete_render()
+  }
 
-      return;
+  /// Draws the measured frame rate and the update rate, if the overlay is shown.
+  fn render_debug_overlay(&mut self, updates_per_second: u32) -> anyhow::Result<()> {
+    let Some(debug_overlay) = self.debug_overlay.as_mut() else {
+      return Ok(());
+    };
+    let overlay_text = format!(
+      "FPS {:.0} UPS {}",
+      self.frame_rate.fps(),
+      updates_per_second
+    );
+
+    if debug_overlay.text() != overlay_text {
+      debug_overlay.update_text(
+        &self.renderer,
+        &overlay_text,
+        DEBUG_OVERLAY_TEXT_SIZE,
+        &LogicalPosition::new(0, 0),
+      );
     }
 
-    let fps = game_loop.game.settings.fps() as f64;
-    let time_step = 1.0 / fps;
-    let delta_time = time_step - Time::now().sub(&game_loop.current_instant());
+    let buffer_dimensions = self.renderer.buffer_dimensions();
 
-    if delta_time > 0.0 {
-      // Sleep the main thread to limit drawing to the fixed time step.
-      // https://github.com/parasyte/pixels/issues/174
-      std::thread::sleep(Duration::from_secs_f64(delta_time));
-    }
+    self
+      .renderer
+      .render_text_box(debug_overlay, Color::WHITE, &buffer_dimensions)
+  }
+
+  /// Shows the debug overlay if it's hidden, otherwise hides it.
+  fn toggle_debug_overlay(&mut self) {
+    self.debug_overlay = match self.debug_overlay {
+      Some(_) => None,
+      None => Some(TextBox::new(
+        &self.renderer,
+        0,
+        "",
+        &LogicalPosition::new(0, 0),
+        DEBUG_OVERLAY_TEXT_SIZE,
+      )),
+    };
   }
 
   #[allow(clippy::needless_return)]
   fn handle_winit_events(game_loop: &mut GameLoop<Self, Time, Arc<Window>>, event: &Event<()>) {
+    game_loop.game.update_name_entry(event);
+
     if !game_loop.game.input.update(event) {
       return;
     }
 
     if game_loop.game.input.close_requested() {
+      if let Err(error) = game_loop.game.world_data.settings().save_to_disk() {
+        log::error!("Failed to save the settings: `{:?}`", error);
+      }
+
       game_loop.exit();
 
       return;
     }
 
+    let fullscreen_key = game_loop
+      .game
+      .world_data
+      .settings()
+      .controls()
+      .fullscreen_key();
+
+    if game_loop.game.input.key_pressed(fullscreen_key) {
+      Self::toggle_fullscreen(game_loop);
+    }
+
+    let debug_overlay_key = game_loop
+      .game
+      .world_data
+      .settings()
+      .controls()
+      .debug_overlay_key();
+
+    if game_loop.game.input.key_pressed(debug_overlay_key) {
+      game_loop.game.toggle_debug_overlay();
+    }
+
+    // Switching to or from fullscreen also lands here once the window has changed size.
     if let Some(new_dimensions) = game_loop.game.input.window_resized() {
       if let Err(error) = game_loop.game.renderer.resize_surface(new_dimensions) {
         log::error!("Failed to change surface dimensions: '{:?}'", error);
@@ -196,33 +431,129 @@ impl RustrisConfig {
     game_loop.game.update_input(event);
   }
 
-  fn update_input(&mut self, event: &Event<()>) {
-    // This will change once keybind settings are implemented.
-    const TEMP_VALID_KEYS: &[KeyCode] = &[
-      KeyCode::ArrowLeft,
-      KeyCode::ArrowRight,
-      KeyCode::ArrowUp,
-      KeyCode::ArrowDown,
-      KeyCode::Space,
-      KeyCode::Escape,
-      KeyCode::Enter,
-      KeyCode::Backspace,
-      KeyCode::KeyW,
-      KeyCode::KeyA,
-      KeyCode::KeyS,
-      KeyCode::KeyD,
-    ];
+  /// Switches the window between borderless fullscreen and windowed, saving the choice to the settings.
+  fn toggle_fullscreen(game_loop: &mut GameLoop<Self, Time, Arc<Window>>) {
+    let world_data = &mut game_loop.game.world_data;
+    let fullscreen = world_data
+      .toggle_fullscreen()
+      .then_some(Fullscreen::Borderless(None));
+
+    log::info!("fullscreen: {}", fullscreen.is_some());
+
+    game_loop.window.set_fullscreen(fullscreen);
+
+    if let Err(error) = world_data.settings().save_to_disk() {
+      log::error!("Failed to save the settings: `{:?}`", error);
+    }
+  }
+
+  /// Types the pressed keys into the name asked for when a score makes it onto the leaderboard.
+  ///
+  /// The name is submitted by [`update_input`](RustrisConfig::update_input), once the step is finished.
+  fn update_name_entry(&mut self, event: &Event<()>) {
+    if !self.world_data.is_entering_name() {
+      return;
+    }
+
+    let Event::WindowEvent {
+      event: WindowEvent::KeyboardInput { event, .. },
+      ..
+    } = event
+    else {
+      return;
+    };
+
+    if event.state != ElementState::Pressed {
+      return;
+    }
+
+    match &event.logical_key {
+      Key::Named(NamedKey::Enter) => (),
+      Key::Named(NamedKey::Backspace) => self.world_data.erase_name_character(),
+      _ => event
+        .text
+        .iter()
+        .flat_map(|text| text.chars())
+        .for_each(|character| self.world_data.type_name_character(character)),
+    }
+  }
+
+  /// Places the score on the leaderboard under the entered name, saving the leaderboard to disk.
+  fn submit_name(&mut self) {
+    self.world_data.submit_name();
+
+    if let Err(error) = self.world_data.leaderboard().save_to_disk() {
+      log::error!("Failed to save the leaderboard: `{:?}`", error);
+    }
+  }
+
+  /// Moves the menu cursor to the option under the mouse when the mouse moves or is clicked.
+  ///
+  /// Returns whether an option was left clicked, or None if the cursor isn't over the buffer.
+  fn update_mouse(&mut self) -> Option<bool> {
+    let (cursor_x, cursor_y) = self.input.cursor()?;
+    let buffer_position = self
+      .renderer
+      .window_to_buffer_position(PhysicalPosition::new(cursor_x, cursor_y))?;
+    let clicked = self.input.mouse_pressed(LEFT_MOUSE_BUTTON);
+
+    // Only following the cursor when it moves leaves the keyboard free to move off of a resting cursor.
+    if !clicked && self.input.cursor_diff() == (0.0, 0.0) {
+      return Some(false);
+    }
+
+    let over_option = self.world_data.hover_menu_option(&buffer_position);
 
+    Some(clicked && over_option)
+  }
+
+  fn update_input(&mut self, event: &Event<()>) {
     if self.input.update(event) {
+      // The keys pressed while entering a name are only typed, so Enter can't also pick a game over option.
+      if self.world_data.is_entering_name() {
+        if self.input.key_pressed(KeyCode::Enter) {
+          self.submit_name();
+        }
+
+        self.player_action = None;
+
+        return;
+      }
+
       let world_state = self.world_data.world_state();
+      let controls = self.world_data.settings().controls();
       let input = &self.input;
 
-      let keys_pressed: Vec<KeyCode> = TEMP_VALID_KEYS
-        .iter()
-        .filter_map(|key| input.key_pressed(*key).then_some(*key))
+      let keys_pressed: Vec<KeyCode> = controls
+        .bound_keys()
+        .into_iter()
+        .filter(|key| input.key_pressed(*key))
+        .collect();
+
+      let held_actions: Vec<GameAction> = controls
+        .bound_keys()
+        .into_iter()
+        .filter(|key| input.key_held(*key))
+        .map(|key| controls.game_action(&key))
         .collect();
 
-      let player_action = PlayerAction::from((world_state, keys_pressed));
+      let held_menu_actions: Vec<MenuAction> = controls
+        .bound_keys()
+        .into_iter()
+        .filter(|key| input.key_held(*key))
+        .map(|key| controls.menu_action(&key))
+        .collect();
+
+      let mut player_action = PlayerAction::from((world_state, keys_pressed, controls));
+
+      self.world_data.update_held_actions(&held_actions);
+      self.world_data.update_held_menu_actions(&held_menu_actions);
+
+      if let Some(option_clicked) = self.update_mouse() {
+        if option_clicked && player_action.is_empty() {
+          player_action = PlayerAction::MenuAction(MenuAction::Select);
+        }
+      }
 
       if !player_action.is_empty() {
         self.player_action = Some(player_action)
@@ -233,10 +564,294 @@ impl RustrisConfig {
   }
 }
 
-fn get_primary_monitor_dimensions(event_loop: &EventLoop<()>) -> PhysicalSize<u32> {
+impl RenderErrors {
+  /// Records a frame that failed to render, returning true if the program should exit.
+  ///
+  /// Most errors only affect the frame they happened in, and never cause an exit.
+  /// Losing the surface or device is fatal, but is retried until it happens [`MAX_FATAL_RENDER_ERRORS`] frames in a row.
+  fn should_exit(&mut self, error: &anyhow::Error) -> bool {
+    if !Self::is_fatal(error) {
+      return false;
+    }
+
+    self.consecutive_fatal_errors += 1;
+
+    self.consecutive_fatal_errors >= MAX_FATAL_RENDER_ERRORS
+  }
+
+  /// Records a frame that rendered successfully, resetting the count of fatal errors.
+  fn frame_rendered(&mut self) {
+    self.consecutive_fatal_errors = 0;
+  }
+
+  fn is_fatal(error: &anyhow::Error) -> bool {
+    matches!(
+      error.downcast_ref::<pixels::Error>(),
+      Some(
+        pixels::Error::AdapterNotFound
+          | pixels::Error::DeviceNotFound(_)
+          | pixels::Error::CreateSurface(_)
+          | pixels::Error::Surface(SurfaceError::Lost | SurfaceError::OutOfMemory)
+      )
+    )
+  }
+}
+
+/// Returns the window title shown during a game with the given score and level.
+/// Updates the world with the player's action, logging the error if the update failed.
+fn update_world(world_data: &mut WorldData, player_action: Option<PlayerAction>) -> UpdateOutcome {
+  match world_data.update_world(player_action) {
+    Ok(false) => UpdateOutcome::Continue,
+    Ok(true) => UpdateOutcome::ExitRequested,
+    Err(error) => {
+      log::error!("An error occurred when updating the world: {:?}", error);
+
+      UpdateOutcome::Failed
+    }
+  }
+}
+
+fn game_window_title(scoring: &Scoring) -> String {
+  format!(
+    "{} — Score {} Lvl {}",
+    WINDOW_TITLE,
+    scoring.score(),
+    scoring.level()
+  )
+}
+
+/// Returns the seed set through [`SEED_ENV_VAR`](SEED_ENV_VAR), or a random one if it isn't set or isn't a valid u64.
+fn game_seed() -> u64 {
+  let Ok(seed) = std::env::var(SEED_ENV_VAR) else {
+    return Rng::random_seed();
+  };
+
+  seed.trim().parse().unwrap_or_else(|_| {
+    log::warn!(
+      "Ignoring the invalid seed `{}` set in {}, using a random seed.",
+      seed,
+      SEED_ENV_VAR
+    );
+
+    Rng::random_seed()
+  })
+}
+
+/// Returns the directory set through [`ASSETS_DIR_ENV_VAR`](ASSETS_DIR_ENV_VAR),
+/// or the images directory inside of the platform's data directory if it isn't set.
+///
+/// None is returned if it isn't set and the platform has no data directory.
+fn image_dir() -> Option<PathBuf> {
+  match std::env::var_os(ASSETS_DIR_ENV_VAR) {
+    Some(image_dir) => Some(PathBuf::from(image_dir)),
+    None => Some(dirs::data_dir()?.join("rustris").join("images")),
+  }
+}
+
+/// Loads the images in the directory over the embedded ones, so images can be added or replaced without a rebuild.
+///
+/// Nothing happens if the directory doesn't exist, and the embedded images are kept if it can't be read.
+fn load_external_images(assets: &mut Assets, image_dir: &Path) {
+  if !image_dir.exists() {
+    return;
+  }
+
+  if let Err(error) = assets.load_image_dir(image_dir) {
+    log::error!(
+      "Failed to load the images in {:?}, using the embedded images. `{:?}`",
+      image_dir,
+      error
+    );
+  }
+}
+
+/// Returns how long to sleep after drawing a frame that took the given seconds, to keep drawing at the fps.
+///
+/// With vsync, presenting already waits for the display, so there's never a need to sleep.
+fn frame_sleep_duration(present_mode: PresentMode, fps: u32, frame_time: f64) -> Option<Duration> {
+  if present_mode == PresentMode::Vsync {
+    return None;
+  }
+
+  let delta_time = (1.0 / fps as f64) - frame_time;
+
+  (delta_time > 0.0).then(|| Duration::from_secs_f64(delta_time))
+}
+
+/// Creates the pixels drawing the buffer to the window, presenting frames with the given mode.
+fn create_pixels(
+  window: &Window,
+  buffer_dimensions: LogicalSize<u32>,
+  present_mode: PresentMode,
+) -> anyhow::Result<Pixels> {
+  let window_size = window.inner_size();
+  let surface = SurfaceTexture::new(window_size.width, window_size.height, window);
+  let present_mode = match present_mode {
+    PresentMode::Vsync => wgpu::PresentMode::AutoVsync,
+    PresentMode::Immediate => wgpu::PresentMode::AutoNoVsync,
+  };
+
+  let pixels = PixelsBuilder::new(buffer_dimensions.width, buffer_dimensions.height, surface)
+    .present_mode(present_mode)
+    .build()?;
+
+  Ok(pixels)
+}
+
+fn get_primary_monitor_dimensions(
+  event_loop: &EventLoop<()>,
+  render_dimensions: LogicalSize<u32>,
+) -> PhysicalSize<u32> {
   let Some(primary_monitor) = event_loop.primary_monitor() else {
-    return RENDERED_WINDOW_DIMENSIONS.to_physical(1.0);
+    return render_dimensions.to_physical(1.0);
   };
 
   primary_monitor.size()
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  mod update_logic {
+    use super::*;
+
+    #[test]
+    fn back_on_the_main_menu_exits_when_set() {
+      let mut settings = GameSettings::default();
+      settings.set_back_exits_main_menu(true);
+      let mut world_data = WorldData::new(settings, 0);
+
+      let outcome = update_world(
+        &mut world_data,
+        Some(PlayerAction::MenuAction(MenuAction::Back)),
+      );
+
+      assert_eq!(outcome, UpdateOutcome::ExitRequested);
+    }
+
+    #[test]
+    fn back_on_the_main_menu_continues_by_default() {
+      let mut world_data = WorldData::new(GameSettings::default(), 0);
+
+      let outcome = update_world(
+        &mut world_data,
+        Some(PlayerAction::MenuAction(MenuAction::Back)),
+      );
+
+      assert_eq!(outcome, UpdateOutcome::Continue);
+    }
+  }
+
+  mod render_error_logic {
+    use super::*;
+
+    fn surface_lost() -> anyhow::Error {
+      pixels::Error::Surface(SurfaceError::Lost).into()
+    }
+
+    #[test]
+    fn recoverable_errors_never_exit() {
+      let mut render_errors = RenderErrors::default();
+      let out_of_bounds = anyhow::anyhow!("Attempted to draw to a pixel outside of the buffer.");
+
+      for _ in 0..(MAX_FATAL_RENDER_ERRORS * 2) {
+        assert!(!render_errors.should_exit(&out_of_bounds));
+      }
+
+      assert!(!render_errors.should_exit(&pixels::Error::Surface(SurfaceError::Timeout).into()));
+    }
+
+    #[test]
+    fn repeated_fatal_errors_exit() {
+      let mut render_errors = RenderErrors::default();
+
+      for _ in 1..MAX_FATAL_RENDER_ERRORS {
+        assert!(!render_errors.should_exit(&surface_lost()));
+      }
+
+      assert!(render_errors.should_exit(&surface_lost()));
+    }
+
+    #[test]
+    fn rendered_frame_resets_the_fatal_error_count() {
+      let mut render_errors = RenderErrors::default();
+
+      for _ in 1..MAX_FATAL_RENDER_ERRORS {
+        render_errors.should_exit(&surface_lost());
+      }
+
+      render_errors.frame_rendered();
+
+      assert!(!render_errors.should_exit(&surface_lost()));
+    }
+  }
+
+  mod window_title_logic {
+    use super::*;
+
+    #[test]
+    fn game_title_shows_the_score_and_level() {
+      let mut scoring = Scoring::new();
+
+      assert_eq!(game_window_title(&scoring), "Rustris — Score 0 Lvl 1");
+
+      scoring.award_hard_drop(10);
+
+      assert_eq!(game_window_title(&scoring), "Rustris — Score 20 Lvl 1");
+    }
+  }
+
+  mod external_image_logic {
+    use super::*;
+
+    #[test]
+    fn missing_directory_keeps_the_embedded_images() {
+      let mut assets = Assets::load_assets();
+      let image_count = assets.get_image_list().len();
+
+      load_external_images(
+        &mut assets,
+        &std::env::temp_dir().join("rustris_missing_images"),
+      );
+
+      assert_eq!(assets.get_image_list().len(), image_count);
+    }
+
+    #[test]
+    fn images_in_the_directory_are_loaded() {
+      let image_dir = std::env::temp_dir().join(format!("rustris_images_{}", std::process::id()));
+      std::fs::create_dir_all(&image_dir).unwrap();
+      image::RgbaImage::new(2, 2)
+        .save(image_dir.join("external_image.png"))
+        .unwrap();
+      let mut assets = Assets::load_assets();
+
+      load_external_images(&mut assets, &image_dir);
+      std::fs::remove_dir_all(&image_dir).unwrap();
+
+      assert!(assets.get_image("external_image").is_some());
+    }
+  }
+
+  mod frame_pacing_logic {
+    use super::*;
+
+    #[test]
+    fn vsync_never_sleeps() {
+      assert_eq!(frame_sleep_duration(PresentMode::Vsync, 60, 0.0), None);
+    }
+
+    #[test]
+    fn immediate_sleeps_for_the_rest_of_the_time_step() {
+      let sleep_duration = frame_sleep_duration(PresentMode::Immediate, 50, 0.005).unwrap();
+
+      assert!((sleep_duration.as_secs_f64() - 0.015).abs() < 1e-9);
+    }
+
+    #[test]
+    fn slow_frames_dont_sleep() {
+      assert_eq!(frame_sleep_duration(PresentMode::Immediate, 50, 0.05), None);
+    }
+  }
+}
@@ -0,0 +1,100 @@
+use rustris::game::actions::{GameAction, MenuAction, PlayerAction};
+use rustris::game::game_settings::GameSettings;
+use rustris::game::world_data::WorldData;
+use rustris::game::world_state::WorldState;
+use rustris::headless::HeadlessGame;
+
+const SEED: u64 = 1234;
+
+fn started_game() -> HeadlessGame {
+  let mut settings = GameSettings::default();
+  settings.set_countdown_ms(0);
+
+  let mut game = HeadlessGame::new(settings, SEED).unwrap();
+
+  // Start, then pick the first game mode.
+  for _ in 0..2 {
+    game
+      .step(Some(PlayerAction::MenuAction(MenuAction::Select)))
+      .unwrap();
+  }
+
+  game
+}
+
+fn game_action(action: GameAction) -> Option<PlayerAction> {
+  Some(PlayerAction::GameAction(vec![action]))
+}
+
+fn filled_cells(world_data: &WorldData) -> Vec<(usize, usize)> {
+  let board_width = WorldData::LOGICAL_BOARD_WIDTH as usize;
+
+  world_data
+    .board()
+    .iter()
+    .enumerate()
+    .filter(|(_, cell)| cell.is_some())
+    .map(|(index, _)| (index % board_width, index / board_width))
+    .collect()
+}
+
+#[test]
+fn selecting_start_and_a_mode_begins_a_game() {
+  let game = started_game();
+
+  assert_eq!(game.world_data().world_state(), WorldState::Game);
+  assert!(game.world_data().active_piece().is_some());
+  assert_eq!(game.ticks(), 2);
+}
+
+#[test]
+fn scripted_hard_drops_land_on_the_floor() {
+  let mut game = started_game();
+  let first_piece = game.world_data().active_piece().unwrap().kind;
+  let bottom_row = WorldData::LOGICAL_BOARD_HEIGHT as usize - 1;
+
+  let script = [
+    game_action(GameAction::MoveLeft),
+    game_action(GameAction::MoveLeft),
+    game_action(GameAction::MoveLeft),
+    game_action(GameAction::MoveLeft),
+    None,
+    game_action(GameAction::HardDrop),
+  ];
+  let exit_requested = game.run_script(&script).unwrap();
+
+  let filled_cells = filled_cells(game.world_data());
+
+  assert!(!exit_requested);
+  assert_eq!(filled_cells.len(), 4);
+  assert!(filled_cells.contains(&(0, bottom_row)));
+  assert!(filled_cells
+    .iter()
+    .all(|&(x, y)| game.world_data().board()[x + y * 10] == Some(first_piece)));
+}
+
+#[test]
+fn same_seed_plays_out_the_same() {
+  let script: Vec<Option<PlayerAction>> = (0..10)
+    .flat_map(|_| {
+      [
+        game_action(GameAction::MoveRight),
+        game_action(GameAction::HardDrop),
+      ]
+    })
+    .collect();
+
+  let mut first_game = started_game();
+  let mut second_game = started_game();
+  first_game.run_script(&script).unwrap();
+  second_game.run_script(&script).unwrap();
+
+  assert_eq!(
+    first_game.world_data().board(),
+    second_game.world_data().board()
+  );
+  assert_eq!(
+    first_game.renderer().frame(),
+    second_game.renderer().frame()
+  );
+}
@@ -0,0 +1,31 @@
+use rustris::define_menu_items;
+use rustris::menus::menu_data::Menu;
+use rustris::menus::menu_items::*;
+
+define_menu_items! {
+  pub enum ExampleMenuItems(menu_name = "example") {
+    Volume(item_name = "volume", text = "Volume", slider = (0, 100, 10, 50)),
+    Mute(item_name = "mute", text = "Mute", toggle = true),
+  }
+}
+
+#[test]
+fn menu_items_can_be_built_from_outside_the_crate() {
+  let volume = MenuItem::new("volume", "unknown")
+    .with_label(Some("Volume"))
+    .with_slider(Some(Slider::new(0, 100, 10, 50)));
+  let mute = MenuItem::new("mute", "unknown")
+    .with_label(Some("Mute"))
+    .with_toggle(Some(Toggle::new(true)));
+
+  assert_eq!(volume.name(), "volume");
+  assert_eq!(volume.label(), Some("Volume"));
+  assert_eq!(volume.slider().map(Slider::value), Some(50));
+  assert_eq!(mute.toggle().map(Toggle::value), Some(true));
+
+  assert_eq!(MenuItem::from(&ExampleMenuItems::Volume), volume);
+  assert_eq!(MenuItem::from(&ExampleMenuItems::Mute), mute);
+
+  let menu = Menu::new::<ExampleMenuItems>();
+  assert_eq!(menu.current_option(), Some(&volume));
+}